@@ -0,0 +1,105 @@
+use crate::{Position, Value};
+
+impl Value {
+    /// Move every position by `dx` along the x axis and `dy` along the y axis, in place.
+    ///
+    /// Like the other affine transforms, this works in coordinate space: for longitude /
+    /// latitude data the offsets are in degrees, and shapes are not kept true on the ground.
+    /// Positions with fewer than two coordinates are left as they are, and any z coordinate
+    /// is unchanged.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut point = Value::Point(vec![1.0, 2.0, 3.0]);
+    /// point.translate(10.0, -1.0);
+    /// assert_eq!(point, Value::Point(vec![11.0, 1.0, 3.0]));
+    /// ```
+    pub fn translate(&mut self, dx: f64, dy: f64) {
+        self.map_xy(|x, y| (x + dx, y + dy))
+    }
+
+    /// Scale every position by `sx` along the x axis and `sy` along the y axis, away from
+    /// `origin`, in place.
+    ///
+    /// A negative factor mirrors the geometry, which reverses the winding of its rings. See
+    /// [`translate`](#method.translate) for which positions are affected.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut line = Value::LineString(vec![vec![1.0, 1.0], vec![2.0, 3.0]]);
+    /// line.scale(2.0, 0.5, [1.0, 1.0]);
+    /// assert_eq!(line, Value::LineString(vec![vec![1.0, 1.0], vec![3.0, 2.0]]));
+    /// ```
+    pub fn scale(&mut self, sx: f64, sy: f64, origin: [f64; 2]) {
+        let [ox, oy] = origin;
+        self.map_xy(|x, y| (ox + (x - ox) * sx, oy + (y - oy) * sy))
+    }
+
+    /// Rotate every position counter-clockwise by `radians` around `origin`, in place.
+    ///
+    /// See [`translate`](#method.translate) for which positions are affected.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut point = Value::Point(vec![2.0, 1.0]);
+    /// point.rotate(std::f64::consts::FRAC_PI_2, [1.0, 1.0]);
+    /// let (x, y) = point.points_iter().next().unwrap();
+    /// assert!((x - 1.0).abs() < 1e-12 && (y - 2.0).abs() < 1e-12);
+    /// ```
+    pub fn rotate(&mut self, radians: f64, origin: [f64; 2]) {
+        let [ox, oy] = origin;
+        let (sin, cos) = radians.sin_cos();
+        self.map_xy(|x, y| {
+            let (dx, dy) = (x - ox, y - oy);
+            (ox + dx * cos - dy * sin, oy + dx * sin + dy * cos)
+        })
+    }
+
+    fn map_xy(&mut self, f: impl Fn(f64, f64) -> (f64, f64)) {
+        self.map_coords(|position: &mut Position| {
+            if let [x, y, ..] = position[..] {
+                let (x, y) = f(x, y);
+                position[0] = x;
+                position[1] = y;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, Value};
+
+    #[test]
+    fn transforms_compose() {
+        let square = vec![vec![
+            vec![0., 0.],
+            vec![1., 0.],
+            vec![1., 1.],
+            vec![0., 1.],
+            vec![0., 0.],
+        ]];
+        let mut value = Value::GeometryCollection(vec![
+            Geometry::new(Value::Polygon(square.clone())),
+            Geometry::new(Value::Point(vec![5.])),
+        ]);
+        value.scale(2., 2., [0., 0.]);
+        value.rotate(std::f64::consts::PI, [1., 1.]);
+        value.translate(1., 1.);
+
+        let expected = [(3., 3.), (1., 3.), (1., 1.), (3., 1.), (3., 3.)];
+        let actual: Vec<_> = value.points_iter().collect();
+        assert_eq!(actual.len(), expected.len());
+        for ((x, y), (ex, ey)) in actual.into_iter().zip(expected) {
+            assert!((x - ex).abs() < 1e-12 && (y - ey).abs() < 1e-12);
+        }
+        assert_eq!(value.geometries()[1].value, Value::Point(vec![5.]));
+
+        let mut mirrored = Value::Polygon(square);
+        mirrored.scale(-1., 1., [0., 0.]);
+        assert!(mirrored.ring_signed_areas()[0] < 0.);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{Position, Value};
+
+/// The signed area of a ring by the shoelace formula: positive if counter-clockwise
+///
+/// Positions with fewer than two coordinates are skipped.
+pub(crate) fn ring_signed_area(ring: &[Position]) -> f64 {
+    let points = || ring.iter().filter(|position| position.len() >= 2);
+    let sum: f64 = points()
+        .zip(points().cycle().skip(1))
+        .map(|(a, b)| a[0] * b[1] - b[0] * a[1])
+        .sum();
+    sum / 2.
+}
+
+impl Value {
+    /// The signed area of every ring of a `Polygon` or `MultiPolygon`, in order.
+    ///
+    /// Areas are positive for counter-clockwise rings and negative for clockwise ones. They are
+    /// computed in the plane, in the squared units of the coordinates (square degrees for
+    /// longitude / latitude data), so they are only meaningful relative to each other. Positions
+    /// with fewer than two coordinates are skipped. Other variants, including
+    /// `GeometryCollection`, return an empty `Vec`.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let polygon = Value::Polygon(vec![
+    ///     vec![vec![0., 0.], vec![4., 0.], vec![4., 4.], vec![0., 4.], vec![0., 0.]],
+    ///     vec![vec![1., 1.], vec![1., 2.], vec![2., 2.], vec![2., 1.], vec![1., 1.]],
+    /// ]);
+    /// assert_eq!(polygon.ring_signed_areas(), vec![16., -1.]);
+    /// ```
+    pub fn ring_signed_areas(&self) -> Vec<f64> {
+        match self {
+            Value::Polygon(rings) => rings.iter().map(|ring| ring_signed_area(ring)).collect(),
+            Value::MultiPolygon(polygons) => polygons
+                .iter()
+                .flatten()
+                .map(|ring| ring_signed_area(ring))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+
+    #[test]
+    fn ring_signed_areas() {
+        let ccw = vec![vec![0., 0.], vec![2., 0.], vec![0., 2.], vec![0., 0.]];
+        let cw: Vec<_> = ccw.iter().rev().cloned().collect();
+        let value = Value::MultiPolygon(vec![vec![ccw], vec![cw]]);
+        assert_eq!(value.ring_signed_areas(), vec![2., -2.]);
+
+        // Unclosed rings are treated as implicitly closed
+        let open = Value::Polygon(vec![vec![vec![0., 0.], vec![2., 0.], vec![0., 2.]]]);
+        assert_eq!(open.ring_signed_areas(), vec![2.]);
+
+        assert!(Value::LineString(vec![]).ring_signed_areas().is_empty());
+
+        let short = Value::Polygon(vec![
+            vec![vec![0.], vec![1.], vec![2.], vec![0.]],
+            vec![
+                vec![0., 0.],
+                vec![2., 0.],
+                vec![],
+                vec![0., 2.],
+                vec![0., 0.],
+            ],
+        ]);
+        assert_eq!(short.ring_signed_areas(), vec![0., 2.]);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{Geometry, Position, Value};
+
+impl Value {
+    /// The boundary of this geometry, following the OGC Simple Features definition.
+    ///
+    /// - A `Polygon` or `MultiPolygon`'s boundary is all of its rings, as a `MultiLineString`.
+    /// - A `LineString`'s boundary is its two endpoints as a `MultiPoint`, which is empty if the
+    ///   line is closed.
+    /// - A `MultiLineString`'s boundary is the endpoints shared by an odd number of its lines
+    ///   (the "mod 2" rule), so lines joined end to end don't contribute their join.
+    /// - A `GeometryCollection`'s boundary is a `GeometryCollection` of its members'
+    ///   boundaries, leaving out members which have none.
+    ///
+    /// Returns `None` for `Point` and `MultiPoint`, whose boundaries are empty, and for
+    /// `Unknown` values.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let line = Value::LineString(vec![vec![0., 0.], vec![1., 0.], vec![1., 1.]]);
+    /// assert_eq!(
+    ///     line.boundary(),
+    ///     Some(Value::MultiPoint(vec![vec![0., 0.], vec![1., 1.]]))
+    /// );
+    /// ```
+    pub fn boundary(&self) -> Option<Value> {
+        match self {
+            Value::Point(_) | Value::MultiPoint(_) | Value::Unknown(_) => None,
+            Value::LineString(line) => {
+                Some(Value::MultiPoint(odd_endpoints(std::iter::once(line))))
+            }
+            Value::MultiLineString(lines) => Some(Value::MultiPoint(odd_endpoints(lines.iter()))),
+            Value::Polygon(rings) => Some(Value::MultiLineString(rings.clone())),
+            Value::MultiPolygon(polygons) => Some(Value::MultiLineString(
+                polygons.iter().flatten().cloned().collect(),
+            )),
+            Value::GeometryCollection(geometries) => Some(Value::GeometryCollection(
+                geometries
+                    .iter()
+                    .filter_map(|geometry| geometry.value.boundary())
+                    .map(Geometry::new)
+                    .collect(),
+            )),
+        }
+    }
+}
+
+/// The endpoints which occur an odd number of times among `lines`, in order of first occurrence
+fn odd_endpoints<'a>(lines: impl Iterator<Item = &'a Vec<Position>>) -> Vec<Position> {
+    let mut counts: Vec<(&Position, usize)> = Vec::new();
+    for line in lines {
+        if let (Some(first), Some(last)) = (line.first(), line.last()) {
+            for endpoint in [first, last].iter() {
+                match counts.iter_mut().find(|(seen, _)| seen == endpoint) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((endpoint, 1)),
+                }
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .filter(|(_, count)| count % 2 == 1)
+        .map(|(endpoint, _)| endpoint.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, Value};
+
+    #[test]
+    fn boundary() {
+        let ring = vec![vec![0., 0.], vec![1., 0.], vec![0., 1.], vec![0., 0.]];
+        assert_eq!(
+            Value::LineString(ring.clone()).boundary(),
+            Some(Value::MultiPoint(vec![]))
+        );
+        assert_eq!(
+            Value::MultiPolygon(vec![vec![ring.clone()], vec![ring.clone()]]).boundary(),
+            Some(Value::MultiLineString(vec![ring.clone(), ring]))
+        );
+
+        // Two lines joined at [1, 0], with a third ending there too
+        let lines = Value::MultiLineString(vec![
+            vec![vec![0., 0.], vec![1., 0.]],
+            vec![vec![1., 0.], vec![2., 0.]],
+            vec![vec![1., 5.], vec![1., 0.]],
+        ]);
+        assert_eq!(
+            lines.boundary(),
+            Some(Value::MultiPoint(vec![
+                vec![0., 0.],
+                vec![1., 0.],
+                vec![2., 0.],
+                vec![1., 5.]
+            ]))
+        );
+
+        let collection = Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![0., 0.])),
+            Geometry::new(Value::LineString(vec![vec![0., 0.], vec![3., 3.]])),
+        ]);
+        assert_eq!(
+            collection.boundary(),
+            Some(Value::GeometryCollection(vec![Geometry::new(
+                Value::MultiPoint(vec![vec![0., 0.], vec![3., 3.]])
+            )]))
+        );
+        assert_eq!(Value::MultiPoint(vec![vec![0., 0.]]).boundary(), None);
+    }
+}
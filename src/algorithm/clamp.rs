@@ -0,0 +1,109 @@
+use crate::Value;
+
+/// How [`Value::clamp_to_wgs84`](enum.Value.html#method.clamp_to_wgs84) handles longitudes
+/// outside of [-180, 180]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LongitudeMode {
+    /// Wrap around the antimeridian, so that e.g. 200° becomes -160°
+    ///
+    /// A longitude which wraps onto the antimeridian keeps its sign, so that 540° becomes 180°,
+    /// as 180° itself stays, and -540° becomes -180°.
+    Wrap,
+    /// Clamp to the nearest of -180° and 180°
+    Clamp,
+}
+
+fn in_range(position: &[f64]) -> bool {
+    match position {
+        [x, y, ..] => (-180.0..=180.0).contains(x) && (-90.0..=90.0).contains(y),
+        _ => true,
+    }
+}
+
+impl Value {
+    /// Returns `true` if any position has a longitude outside of [-180, 180], or a latitude
+    /// outside of [-90, 90].
+    pub fn has_out_of_range_coords(&self) -> bool {
+        !self.coords_iter().all(|position| in_range(position))
+    }
+
+    /// Bring every position within WGS84 ranges: latitudes are clamped to [-90, 90], and
+    /// longitudes are brought within [-180, 180] according to `longitude_mode`.
+    ///
+    /// Positions which are already within range are left untouched.
+    ///
+    /// ```
+    /// use geojson::{LongitudeMode, Value};
+    ///
+    /// let mut value = Value::MultiPoint(vec![vec![200.0, 95.0], vec![-190.0, -10.0]]);
+    /// assert!(value.has_out_of_range_coords());
+    ///
+    /// value.clamp_to_wgs84(LongitudeMode::Wrap);
+    /// assert_eq!(
+    ///     value,
+    ///     Value::MultiPoint(vec![vec![-160.0, 90.0], vec![170.0, -10.0]])
+    /// );
+    /// assert!(!value.has_out_of_range_coords());
+    /// ```
+    pub fn clamp_to_wgs84(&mut self, longitude_mode: LongitudeMode) {
+        for position in self.coords_iter_mut() {
+            if let [x, y, ..] = position.as_mut_slice() {
+                if !(-180.0..=180.0).contains(x) {
+                    *x = match longitude_mode {
+                        LongitudeMode::Wrap => match (*x + 180.0).rem_euclid(360.0) - 180.0 {
+                            wrapped if wrapped == -180.0 && *x > 0.0 => 180.0,
+                            wrapped => wrapped,
+                        },
+                        LongitudeMode::Clamp => x.clamp(-180.0, 180.0),
+                    };
+                }
+                *y = y.clamp(-90.0, 90.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LongitudeMode;
+    use crate::Value;
+
+    #[test]
+    fn clamp_longitudes() {
+        let mut value = Value::LineString(vec![vec![200.0, 0.0], vec![-540.0, 0.0, 12.0]]);
+        value.clamp_to_wgs84(LongitudeMode::Clamp);
+        assert_eq!(
+            value,
+            Value::LineString(vec![vec![180.0, 0.0], vec![-180.0, 0.0, 12.0]])
+        );
+    }
+
+    #[test]
+    fn wrap_longitudes() {
+        let mut value = Value::LineString(vec![
+            vec![180.0, 0.0],
+            vec![540.0, 0.0],
+            vec![-181.0, 0.0],
+            vec![-540.0, 0.0],
+            vec![900.0, 0.0],
+        ]);
+        value.clamp_to_wgs84(LongitudeMode::Wrap);
+        assert_eq!(
+            value,
+            Value::LineString(vec![
+                vec![180.0, 0.0],
+                vec![180.0, 0.0],
+                vec![179.0, 0.0],
+                vec![-180.0, 0.0],
+                vec![180.0, 0.0],
+            ])
+        );
+    }
+
+    #[test]
+    fn in_range_coords() {
+        let value = Value::Point(vec![-180.0, 90.0]);
+        assert!(!value.has_out_of_range_coords());
+        assert!(Value::Point(vec![0.0, -90.5]).has_out_of_range_coords());
+    }
+}
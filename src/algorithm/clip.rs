@@ -0,0 +1,382 @@
+use super::area::ring_signed_area;
+use crate::bbox::extent_2d;
+use crate::{Geometry, LineStringType, PolygonType, Position, Value};
+
+impl Value {
+    /// Clip this value to the rectangle `bbox`, returning the part of it inside the rectangle,
+    /// or `None` if nothing remains.
+    ///
+    /// Only the x / y extent of a 6-element `bbox` is used, and an invalid `bbox` clips away
+    /// everything. Positions on the rectangle's edges count as inside.
+    ///
+    /// * Points outside the rectangle are dropped.
+    /// * Lines are clipped with the Cohen–Sutherland algorithm. A `LineString` which leaves and
+    ///   re-enters the rectangle becomes a `MultiLineString`.
+    /// * Polygon rings are clipped with the Sutherland–Hodgman algorithm and stay closed, with
+    ///   their original winding. Rings left with no area are dropped, as is a polygon whose
+    ///   exterior ring is. Clipping a concave ring may leave zero-width spikes along the
+    ///   rectangle's edges where it crossed outside more than once.
+    /// * `GeometryCollection` members are clipped recursively, keeping their foreign members but
+    ///   not their `bbox`.
+    ///
+    /// Coordinates beyond x and y are interpolated for new vertices. `Unknown` values clip to
+    /// `None`.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let square = Value::Polygon(vec![vec![
+    ///     vec![0., 0.],
+    ///     vec![4., 0.],
+    ///     vec![4., 4.],
+    ///     vec![0., 4.],
+    ///     vec![0., 0.],
+    /// ]]);
+    /// assert_eq!(
+    ///     square.clip_to_bbox(&vec![2., 2., 6., 6.]),
+    ///     Some(Value::Polygon(vec![vec![
+    ///         vec![2., 2.],
+    ///         vec![4., 2.],
+    ///         vec![4., 4.],
+    ///         vec![2., 4.],
+    ///         vec![2., 2.],
+    ///     ]]))
+    /// );
+    ///
+    /// let line = Value::LineString(vec![vec![-1., 1.], vec![1., 1.], vec![3., 1.]]);
+    /// assert_eq!(
+    ///     line.clip_to_bbox(&vec![0., 0., 2., 2.]),
+    ///     Some(Value::LineString(vec![vec![0., 1.], vec![1., 1.], vec![2., 1.]]))
+    /// );
+    /// assert_eq!(Value::Point(vec![5., 5.]).clip_to_bbox(&vec![0., 0., 2., 2.]), None);
+    /// ```
+    pub fn clip_to_bbox(&self, bbox: &[f64]) -> Option<Value> {
+        let window = Window::new(bbox)?;
+        window.clip(self)
+    }
+}
+
+/// A clipping rectangle
+struct Window {
+    min: [f64; 2],
+    max: [f64; 2],
+}
+
+const LEFT: u8 = 1;
+const RIGHT: u8 = 2;
+const BOTTOM: u8 = 4;
+const TOP: u8 = 8;
+
+impl Window {
+    fn new(bbox: &[f64]) -> Option<Self> {
+        let [min_x, min_y, max_x, max_y] = extent_2d(bbox)?;
+        if min_x <= max_x && min_y <= max_y {
+            Some(Window {
+                min: [min_x, min_y],
+                max: [max_x, max_y],
+            })
+        } else {
+            None
+        }
+    }
+
+    fn clip(&self, value: &Value) -> Option<Value> {
+        match value {
+            Value::Point(position) => Some(position)
+                .filter(|position| self.outcode(position) == 0)
+                .map(|position| Value::Point(position.clone())),
+            Value::MultiPoint(positions) => {
+                let inside: Vec<Position> = positions
+                    .iter()
+                    .filter(|position| self.outcode(position) == 0)
+                    .cloned()
+                    .collect();
+                Some(inside)
+                    .filter(|inside| !inside.is_empty())
+                    .map(Value::MultiPoint)
+            }
+            Value::LineString(line) => {
+                let mut pieces = self.clip_line(line);
+                match pieces.len() {
+                    0 => None,
+                    1 => pieces.pop().map(Value::LineString),
+                    _ => Some(Value::MultiLineString(pieces)),
+                }
+            }
+            Value::MultiLineString(lines) => {
+                let pieces: Vec<LineStringType> =
+                    lines.iter().flat_map(|line| self.clip_line(line)).collect();
+                Some(pieces)
+                    .filter(|pieces| !pieces.is_empty())
+                    .map(Value::MultiLineString)
+            }
+            Value::Polygon(rings) => self.clip_polygon(rings).map(Value::Polygon),
+            Value::MultiPolygon(polygons) => {
+                let clipped: Vec<PolygonType> = polygons
+                    .iter()
+                    .filter_map(|rings| self.clip_polygon(rings))
+                    .collect();
+                Some(clipped)
+                    .filter(|clipped| !clipped.is_empty())
+                    .map(Value::MultiPolygon)
+            }
+            Value::GeometryCollection(geometries) => {
+                let clipped: Vec<Geometry> = geometries
+                    .iter()
+                    .filter_map(|geometry| {
+                        Some(Geometry {
+                            value: self.clip(&geometry.value)?,
+                            bbox: None,
+                            foreign_members: geometry.foreign_members.clone(),
+                        })
+                    })
+                    .collect();
+                Some(clipped)
+                    .filter(|clipped| !clipped.is_empty())
+                    .map(Value::GeometryCollection)
+            }
+            Value::Unknown(_) => None,
+        }
+    }
+
+    /// The Cohen–Sutherland region code of `position`, which is 0 inside the window
+    fn outcode(&self, position: &[f64]) -> u8 {
+        if position.len() < 2 {
+            return LEFT | RIGHT;
+        }
+        let (x, y) = (position[0], position[1]);
+        let mut code = 0;
+        if x < self.min[0] {
+            code |= LEFT;
+        } else if x > self.max[0] {
+            code |= RIGHT;
+        }
+        if y < self.min[1] {
+            code |= BOTTOM;
+        } else if y > self.max[1] {
+            code |= TOP;
+        }
+        code
+    }
+
+    /// The part of the segment from `a` to `b` inside the window
+    fn clip_segment(&self, a: &[f64], b: &[f64]) -> Option<(Position, Position)> {
+        let (mut a, mut b) = (a.to_vec(), b.to_vec());
+        let (mut code_a, mut code_b) = (self.outcode(&a), self.outcode(&b));
+        loop {
+            if code_a | code_b == 0 {
+                return Some((a, b));
+            }
+            if code_a & code_b != 0 || a.len() < 2 || b.len() < 2 {
+                return None;
+            }
+            let code = if code_a != 0 { code_a } else { code_b };
+            let (axis, bound) = if code & LEFT != 0 {
+                (0, self.min[0])
+            } else if code & RIGHT != 0 {
+                (0, self.max[0])
+            } else if code & BOTTOM != 0 {
+                (1, self.min[1])
+            } else {
+                (1, self.max[1])
+            };
+            let crossing = intersection(&a, &b, axis, bound);
+            if code == code_a {
+                a = crossing;
+                code_a = self.outcode(&a);
+            } else {
+                b = crossing;
+                code_b = self.outcode(&b);
+            }
+        }
+    }
+
+    /// The pieces of `line` inside the window, each with at least two positions
+    fn clip_line(&self, line: &[Position]) -> Vec<LineStringType> {
+        let mut pieces: Vec<LineStringType> = vec![];
+        for (a, b) in super::segments(line) {
+            let (start, end) = match self.clip_segment(a, b) {
+                Some(clipped) => clipped,
+                None => continue,
+            };
+            match pieces.last_mut() {
+                Some(piece) if piece.last() == Some(&start) => piece.push(end),
+                _ if start == end => {}
+                _ => pieces.push(vec![start, end]),
+            }
+        }
+        pieces
+    }
+
+    /// Clip every ring of a polygon, or return `None` if its exterior ring is clipped away
+    fn clip_polygon(&self, rings: &[LineStringType]) -> Option<PolygonType> {
+        let mut rings = rings.iter().map(|ring| self.clip_ring(ring));
+        let exterior = rings.next()??;
+        Some(std::iter::once(exterior).chain(rings.flatten()).collect())
+    }
+
+    /// Clip a closed ring with the Sutherland–Hodgman algorithm, or return `None` if no area
+    /// remains
+    fn clip_ring(&self, ring: &[Position]) -> Option<LineStringType> {
+        let mut positions: Vec<Position> = match ring.split_last() {
+            Some((last, rest)) if rest.first() == Some(last) => rest.to_vec(),
+            _ => ring.to_vec(),
+        };
+        if positions.iter().any(|position| position.len() < 2) {
+            return None;
+        }
+        let edges = [
+            (0, self.min[0], false),
+            (0, self.max[0], true),
+            (1, self.min[1], false),
+            (1, self.max[1], true),
+        ];
+        for &(axis, bound, is_max) in &edges {
+            let inside = |position: &Position| {
+                if is_max {
+                    position[axis] <= bound
+                } else {
+                    position[axis] >= bound
+                }
+            };
+            let mut clipped = Vec::with_capacity(positions.len() + 4);
+            for (i, current) in positions.iter().enumerate() {
+                let previous = &positions[(i + positions.len() - 1) % positions.len()];
+                match (inside(previous), inside(current)) {
+                    (true, true) => clipped.push(current.clone()),
+                    (true, false) => clipped.push(intersection(previous, current, axis, bound)),
+                    (false, true) => {
+                        clipped.push(intersection(previous, current, axis, bound));
+                        clipped.push(current.clone());
+                    }
+                    (false, false) => {}
+                }
+            }
+            clipped.dedup();
+            positions = clipped;
+        }
+        while positions.len() > 1 && positions.first() == positions.last() {
+            positions.pop();
+        }
+        if let Some(first) = positions.first().cloned() {
+            positions.push(first);
+        }
+        Some(positions).filter(|ring| ring.len() >= 4 && ring_signed_area(ring) != 0.)
+    }
+}
+
+/// The position where the segment from `a` to `b` crosses `position[axis] == bound`, with the
+/// other coordinates interpolated
+fn intersection(a: &[f64], b: &[f64], axis: usize, bound: f64) -> Position {
+    let t = (bound - a[axis]) / (b[axis] - a[axis]);
+    let mut crossing: Position = a.iter().zip(b).map(|(a, b)| a + (b - a) * t).collect();
+    crossing[axis] = bound;
+    crossing
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, Value};
+
+    fn square(min: f64, max: f64) -> Vec<Vec<f64>> {
+        vec![
+            vec![min, min],
+            vec![max, min],
+            vec![max, max],
+            vec![min, max],
+            vec![min, min],
+        ]
+    }
+
+    #[test]
+    fn clip_lines() {
+        let window = vec![0., 0., 2., 2.];
+        let zigzag = Value::LineString(vec![
+            vec![-1., 1., 0.],
+            vec![1., 1., 2.],
+            vec![1., 3., 4.],
+            vec![1.5, 1.5, 6.],
+            vec![3., 1.5, 8.],
+        ]);
+        assert_eq!(
+            zigzag.clip_to_bbox(&window),
+            Some(Value::MultiLineString(vec![
+                vec![vec![0., 1., 1.], vec![1., 1., 2.], vec![1., 2., 3.]],
+                vec![
+                    vec![1.3333333333333333, 2., 5.333333333333333],
+                    vec![1.5, 1.5, 6.],
+                    vec![2., 1.5, 6.666666666666667]
+                ],
+            ]))
+        );
+
+        let outside = Value::LineString(vec![vec![3., 0.], vec![4., 3.]]);
+        assert_eq!(outside.clip_to_bbox(&window), None);
+        let touching = Value::LineString(vec![vec![2., 3.], vec![3., 2.], vec![2., 2.]]);
+        assert_eq!(touching.clip_to_bbox(&window), None);
+    }
+
+    #[test]
+    fn clip_polygons() {
+        let window = vec![1., 1., 3., 3.];
+        let polygon = Value::Polygon(vec![square(0., 4.), square(1.5, 2.5), square(3.5, 3.9)]);
+        assert_eq!(
+            polygon.clip_to_bbox(&window),
+            Some(Value::Polygon(vec![
+                vec![
+                    vec![1., 3.],
+                    vec![1., 1.],
+                    vec![3., 1.],
+                    vec![3., 3.],
+                    vec![1., 3.]
+                ],
+                square(1.5, 2.5),
+            ]))
+        );
+
+        let adjacent = Value::MultiPolygon(vec![vec![square(3., 4.)], vec![square(5., 6.)]]);
+        assert_eq!(adjacent.clip_to_bbox(&window), None);
+
+        let triangle = Value::Polygon(vec![vec![
+            vec![0., 0.],
+            vec![4., 0.],
+            vec![0., 4.],
+            vec![0., 0.],
+        ]]);
+        match triangle.clip_to_bbox(&window) {
+            Some(Value::Polygon(rings)) => {
+                assert_eq!(rings.len(), 1);
+                assert_eq!(rings[0].first(), rings[0].last());
+                assert!(rings[0]
+                    .iter()
+                    .all(|p| (1.0..=3.0).contains(&p[0]) && (1.0..=3.0).contains(&p[1])));
+                assert_eq!(Value::Polygon(rings.clone()).ring_signed_areas(), vec![2.]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn clip_points_and_collections() {
+        let window = vec![0., 0., 0., 1., 1., 1.];
+        let mut point = Geometry::new(Value::Point(vec![0.5, 1.]));
+        point.bbox = Some(vec![0.5, 1., 0.5, 1.]);
+        let collection = Value::GeometryCollection(vec![
+            point,
+            Geometry::new(Value::MultiPoint(vec![vec![2., 2.]])),
+            Geometry::new(Value::MultiPoint(vec![vec![2., 2.], vec![0., 0.]])),
+        ]);
+        assert_eq!(
+            collection.clip_to_bbox(&window),
+            Some(Value::GeometryCollection(vec![
+                Geometry::new(Value::Point(vec![0.5, 1.])),
+                Geometry::new(Value::MultiPoint(vec![vec![0., 0.]])),
+            ]))
+        );
+        assert_eq!(Value::Point(vec![0.5, 0.5]).clip_to_bbox(&[1., 1.]), None);
+        assert_eq!(
+            Value::Point(vec![0.5, 0.5]).clip_to_bbox(&[1., 1., 0., 0.]),
+            None
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::Value;
+
+impl Value {
+    /// The smallest planar distance between any two of the value's positions, or `None` if it
+    /// has fewer than two
+    ///
+    /// The closing position of a closed ring or line isn't counted as a second point, but any
+    /// other repeated position gives a distance of zero. Found by divide and conquer in
+    /// `O(n log² n)` time.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let points = Value::MultiPoint(vec![vec![0., 0.], vec![5., 5.], vec![4., 1.], vec![4.5, 1.]]);
+    /// assert_eq!(points.min_interpoint_distance(), Some(0.5));
+    /// assert_eq!(Value::Point(vec![1., 2.]).min_interpoint_distance(), None);
+    /// ```
+    pub fn min_interpoint_distance(&self) -> Option<f64> {
+        let mut points: Vec<(f64, f64)> = self
+            .parts_iter()
+            .flat_map(|part| {
+                let closed = part.len() > 1 && part.first() == part.last();
+                &part[..part.len() - closed as usize]
+            })
+            .filter_map(|position| match position[..] {
+                [x, y, ..] => Some((x, y)),
+                _ => None,
+            })
+            .collect();
+        if points.len() < 2 {
+            return None;
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Some(closest_pair(&points))
+    }
+}
+
+/// The closest-pair distance of at least two `points`, sorted by `x`
+fn closest_pair(points: &[(f64, f64)]) -> f64 {
+    let distance = |a: &(f64, f64), b: &(f64, f64)| (b.0 - a.0).hypot(b.1 - a.1);
+    if points.len() <= 3 {
+        return points
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| points[i + 1..].iter().map(move |b| distance(a, b)))
+            .fold(f64::INFINITY, f64::min);
+    }
+    let mid = points.len() / 2;
+    let mid_x = points[mid].0;
+    let mut min = closest_pair(&points[..mid]).min(closest_pair(&points[mid..]));
+
+    // Only points within `min` of the dividing line can be closer across it
+    let mut strip: Vec<&(f64, f64)> = points
+        .iter()
+        .filter(|point| (point.0 - mid_x).abs() < min)
+        .collect();
+    strip.sort_by(|a, b| a.1.total_cmp(&b.1));
+    for (i, a) in strip.iter().enumerate() {
+        for b in &strip[i + 1..] {
+            if b.1 - a.1 >= min {
+                break;
+            }
+            min = min.min(distance(a, b));
+        }
+    }
+    min
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+
+    #[test]
+    fn matches_brute_force() {
+        // A deterministic scatter of points, some of them close
+        let mut seed = 12345u64;
+        let mut next = || {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            (seed >> 11) as f64 / (1u64 << 53) as f64 * 1000.
+        };
+        let positions: Vec<Vec<f64>> = (0..500).map(|_| vec![next(), next()]).collect();
+
+        let mut expected = f64::INFINITY;
+        for (i, a) in positions.iter().enumerate() {
+            for b in &positions[i + 1..] {
+                expected = expected.min((b[0] - a[0]).hypot(b[1] - a[1]));
+            }
+        }
+        let points = Value::MultiPoint(positions);
+        assert_eq!(points.min_interpoint_distance(), Some(expected));
+    }
+
+    #[test]
+    fn rings_and_degenerate_inputs() {
+        let square = Value::Polygon(vec![vec![
+            vec![0., 0.],
+            vec![3., 0.],
+            vec![3., 2.],
+            vec![0., 2.],
+            vec![0., 0.],
+        ]]);
+        assert_eq!(square.min_interpoint_distance(), Some(2.));
+
+        let repeated = Value::LineString(vec![vec![0., 0.], vec![1., 1.], vec![1., 1.]]);
+        assert_eq!(repeated.min_interpoint_distance(), Some(0.));
+
+        assert_eq!(Value::MultiPoint(vec![]).min_interpoint_distance(), None);
+        assert_eq!(
+            Value::LineString(vec![vec![1., 1.], vec![1., 1.]]).min_interpoint_distance(),
+            None
+        );
+    }
+}
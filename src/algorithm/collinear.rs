@@ -0,0 +1,183 @@
+use super::orientation;
+use crate::{Position, Value};
+
+impl Value {
+    /// Remove every vertex of a line or ring which is collinear with its neighbours, in place:
+    /// those forming a triangle of area less than `epsilon` with them. This simplifies without
+    /// changing the shape, beyond the `epsilon` allowed for nearly straight runs.
+    ///
+    /// Vertices are compared with the last vertex kept before them, so a long, gently curving
+    /// run isn't removed piece by piece. Lines keep their endpoints. Rings are treated as
+    /// cyclic, so their first vertex may be removed too, but a ring stays closed if it was and
+    /// keeps at least 3 vertices. `Point` and `MultiPoint` are left untouched, as are lines and
+    /// rings with a position of fewer than two coordinates. As the comparison
+    /// is strict, an `epsilon` of 0 removes nothing, while a tiny positive one removes only
+    /// exactly collinear vertices.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut line = Value::LineString(vec![
+    ///     vec![0., 0.],
+    ///     vec![1., 0.],
+    ///     vec![2., 0.001],
+    ///     vec![3., 0.],
+    ///     vec![3., 3.],
+    /// ]);
+    /// line.remove_collinear_points(0.01);
+    /// assert_eq!(
+    ///     line,
+    ///     Value::LineString(vec![vec![0., 0.], vec![3., 0.], vec![3., 3.]])
+    /// );
+    /// ```
+    pub fn remove_collinear_points(&mut self, epsilon: f64) {
+        let is_collinear = |area: f64| area < epsilon;
+        match self {
+            Value::Point(_) | Value::MultiPoint(_) | Value::Unknown(_) => {}
+            Value::LineString(line) => remove_from_line(line, is_collinear),
+            Value::MultiLineString(lines) => lines
+                .iter_mut()
+                .for_each(|line| remove_from_line(line, is_collinear)),
+            Value::Polygon(rings) => rings
+                .iter_mut()
+                .for_each(|ring| remove_from_closed_ring(ring, is_collinear)),
+            Value::MultiPolygon(polygons) => polygons
+                .iter_mut()
+                .flatten()
+                .for_each(|ring| remove_from_closed_ring(ring, is_collinear)),
+            Value::GeometryCollection(geometries) => geometries
+                .iter_mut()
+                .for_each(|geometry| geometry.value.remove_collinear_points(epsilon)),
+        }
+    }
+}
+
+/// The area of the triangle `a`, `b`, `c`
+fn triangle_area(a: &[f64], b: &[f64], c: &[f64]) -> f64 {
+    orientation(a, b, c).abs() / 2.
+}
+
+/// Whether any of `positions` lacks an `x` or `y`, so that no triangle can be formed with it
+fn has_short_position(positions: &[Position]) -> bool {
+    positions.iter().any(|position| position.len() < 2)
+}
+
+pub(crate) fn remove_from_line(line: &mut Vec<Position>, is_collinear: impl Fn(f64) -> bool) {
+    if line.len() < 3 || has_short_position(line) {
+        return;
+    }
+    let mut kept = 0;
+    for i in 1..line.len() - 1 {
+        if !is_collinear(triangle_area(&line[kept], &line[i], &line[i + 1])) {
+            kept += 1;
+            line.swap(kept, i);
+        }
+    }
+    let last = line.len() - 1;
+    line.swap(kept + 1, last);
+    line.truncate(kept + 2);
+}
+
+fn remove_from_closed_ring(ring: &mut Vec<Position>, is_collinear: impl Fn(f64) -> bool) {
+    let was_closed = ring.len() > 1 && ring.first() == ring.last();
+    if was_closed {
+        ring.pop();
+    }
+    remove_from_ring(ring, 3, is_collinear);
+    if was_closed {
+        ring.push(ring[0].clone());
+    }
+}
+
+/// Remove the vertices of the unclosed `ring` for which `is_collinear` holds of the area they
+/// form with their neighbours, treating it as cyclic, until only `min_len` are left
+pub(crate) fn remove_from_ring(
+    ring: &mut Vec<Position>,
+    min_len: usize,
+    is_collinear: impl Fn(f64) -> bool,
+) {
+    if has_short_position(ring) {
+        return;
+    }
+    // Removing a vertex changes its neighbours' triangles, so keep going until every remaining
+    // vertex turns
+    let mut i = 0;
+    while ring.len() > min_len && i < ring.len() {
+        let previous = &ring[(i + ring.len() - 1) % ring.len()];
+        let next = &ring[(i + 1) % ring.len()];
+        if is_collinear(triangle_area(previous, &ring[i], next)) {
+            ring.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, Value};
+
+    #[test]
+    fn rings_stay_closed_and_valid() {
+        let mut polygon = Value::Polygon(vec![
+            vec![
+                vec![1., 0.],
+                vec![2., 0.],
+                vec![2., 2.],
+                vec![0., 2.],
+                vec![0., 0.],
+                vec![1., 0.],
+            ],
+            // Nearly flat, but it can't lose any more vertices
+            vec![vec![0., 0.], vec![1., 0.001], vec![2., 0.], vec![0., 0.]],
+        ]);
+        polygon.remove_collinear_points(0.01);
+        assert_eq!(
+            polygon,
+            Value::Polygon(vec![
+                vec![
+                    vec![2., 0.],
+                    vec![2., 2.],
+                    vec![0., 2.],
+                    vec![0., 0.],
+                    vec![2., 0.],
+                ],
+                vec![vec![0., 0.], vec![1., 0.001], vec![2., 0.], vec![0., 0.]],
+            ])
+        );
+    }
+
+    #[test]
+    fn epsilon_controls_removal() {
+        let line = Value::MultiLineString(vec![
+            vec![vec![0., 0.], vec![1., 0.1], vec![2., 0.]],
+            vec![vec![0., 0.], vec![1., 1.]],
+        ]);
+        let mut exact = Value::GeometryCollection(vec![Geometry::new(line.clone())]);
+        exact.remove_collinear_points(f64::EPSILON);
+        assert_eq!(exact, Value::GeometryCollection(vec![Geometry::new(line)]));
+
+        let mut loose = Value::LineString(vec![vec![0., 0.], vec![1., 0.1], vec![2., 0.]]);
+        loose.remove_collinear_points(0.2);
+        assert_eq!(loose, Value::LineString(vec![vec![0., 0.], vec![2., 0.]]));
+    }
+
+    #[test]
+    fn short_positions_are_left_alone() {
+        let original = Value::GeometryCollection(vec![
+            Geometry::new(Value::LineString(vec![vec![0.], vec![1.], vec![2.]])),
+            Geometry::new(Value::Polygon(vec![vec![
+                vec![0., 0.],
+                vec![1., 0.],
+                vec![2.],
+                vec![2., 0.],
+                vec![0., 1.],
+                vec![0., 0.],
+            ]])),
+        ]);
+        let mut value = original.clone();
+        value.remove_collinear_points(0.01);
+        assert_eq!(value, original);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{Error, Position, Value};
+
+impl Value {
+    /// The number of coordinates in each position, if it is the same for all of them.
+    ///
+    /// Returns `Error::MixedDimensions` naming the first position whose length differs from the
+    /// positions before it, e.g. `coordinates[0][3]`, or `geometries[1].coordinates[2]` within a
+    /// `GeometryCollection`. A value without any positions has dimension `0`.
+    ///
+    /// ```
+    /// use geojson::{Error, Value};
+    ///
+    /// let value = Value::LineString(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    /// assert_eq!(value.dimension().unwrap(), 3);
+    ///
+    /// let mixed = Value::LineString(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0]]);
+    /// match mixed.dimension() {
+    ///     Err(Error::MixedDimensions { expected, found, path }) => {
+    ///         assert_eq!((expected, found), (3, 2));
+    ///         assert_eq!(path, "coordinates[1]");
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn dimension(&self) -> Result<usize, Error> {
+        let mut dimension = None;
+        self.check_dimension("", &mut dimension)?;
+        Ok(dimension.unwrap_or(0))
+    }
+
+    /// Check every position has the length in `dimension`, or if that's `None`, the length of
+    /// the first position, which it is then set to
+    pub(crate) fn check_dimension(
+        &self,
+        prefix: &str,
+        dimension: &mut Option<usize>,
+    ) -> Result<(), Error> {
+        let mut check = |position: &Position, path: &dyn Fn() -> String| match *dimension {
+            None => {
+                *dimension = Some(position.len());
+                Ok(())
+            }
+            Some(expected) if expected == position.len() => Ok(()),
+            Some(expected) => Err(Error::MixedDimensions {
+                expected,
+                found: position.len(),
+                path: format!("{}coordinates{}", prefix, path()),
+            }),
+        };
+        match self {
+            Value::Point(position) => check(position, &String::new),
+            Value::MultiPoint(positions) | Value::LineString(positions) => {
+                for (i, position) in positions.iter().enumerate() {
+                    check(position, &|| format!("[{}]", i))?;
+                }
+                Ok(())
+            }
+            Value::MultiLineString(lines) | Value::Polygon(lines) => {
+                for (i, line) in lines.iter().enumerate() {
+                    for (j, position) in line.iter().enumerate() {
+                        check(position, &|| format!("[{}][{}]", i, j))?;
+                    }
+                }
+                Ok(())
+            }
+            Value::MultiPolygon(polygons) => {
+                for (i, polygon) in polygons.iter().enumerate() {
+                    for (j, ring) in polygon.iter().enumerate() {
+                        for (k, position) in ring.iter().enumerate() {
+                            check(position, &|| format!("[{}][{}][{}]", i, j, k))?;
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Value::GeometryCollection(geometries) => {
+                for (i, geometry) in geometries.iter().enumerate() {
+                    let prefix = format!("{}geometries[{}].", prefix, i);
+                    geometry.value.check_dimension(&prefix, dimension)?;
+                }
+                Ok(())
+            }
+            Value::Unknown(_) => Ok(()),
+        }
+    }
+
+    /// Returns `true` if any position has a third (elevation) coordinate.
+    pub fn has_z(&self) -> bool {
+        self.coords_iter().any(|position| position.len() > 2)
+    }
+
+    /// Truncate every position to its first two coordinates, in place.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut value = Value::LineString(vec![vec![1.0, 2.0, 30.0], vec![3.0, 4.0]]);
+    /// assert!(value.has_z());
+    ///
+    /// value.drop_z();
+    /// assert_eq!(value, Value::LineString(vec![vec![1.0, 2.0], vec![3.0, 4.0]]));
+    /// assert!(!value.has_z());
+    /// ```
+    pub fn drop_z(&mut self) {
+        self.coords_iter_mut()
+            .for_each(|position| position.truncate(2));
+    }
+
+    /// Append `default_z` to every position which lacks a third coordinate, in place.
+    ///
+    /// Positions which already have an elevation are left untouched.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut value = Value::LineString(vec![vec![1.0, 2.0], vec![3.0, 4.0, 30.0]]);
+    /// value.add_z(0.0);
+    /// assert_eq!(
+    ///     value,
+    ///     Value::LineString(vec![vec![1.0, 2.0, 0.0], vec![3.0, 4.0, 30.0]])
+    /// );
+    /// ```
+    pub fn add_z(&mut self, default_z: f64) {
+        for position in self.coords_iter_mut() {
+            if position.len() == 2 {
+                position.push(default_z);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Geometry, Value};
+
+    #[test]
+    fn drop_z_keeps_rings_closed() {
+        let ring = vec![
+            vec![0.0, 0.0, 5.0],
+            vec![1.0, 0.0, 5.0],
+            vec![1.0, 1.0, 5.0],
+            vec![0.0, 0.0, 5.0],
+        ];
+        let mut value = Value::GeometryCollection(vec![Geometry::new(Value::Polygon(vec![ring]))]);
+        value.drop_z();
+
+        match value {
+            Value::GeometryCollection(geometries) => match &geometries[0].value {
+                Value::Polygon(rings) => {
+                    assert_eq!(rings[0].first(), rings[0].last());
+                    assert!(rings[0].iter().all(|position| position.len() == 2));
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn add_z_then_drop_z() {
+        let original = Value::MultiPoint(vec![vec![1.0, 2.0], vec![3.0, 4.0, 7.0]]);
+        let mut value = original.clone();
+        value.add_z(0.5);
+        assert_eq!(
+            value,
+            Value::MultiPoint(vec![vec![1.0, 2.0, 0.5], vec![3.0, 4.0, 7.0]])
+        );
+
+        value.drop_z();
+        let mut expected = original;
+        expected.drop_z();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn mixed_dimensions_in_collection() {
+        let value = Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![0., 0.])),
+            Geometry::new(Value::Polygon(vec![vec![
+                vec![0., 0.],
+                vec![1., 0.],
+                vec![1., 1., 4.],
+            ]])),
+        ]);
+        match value.dimension() {
+            Err(Error::MixedDimensions { path, .. }) => {
+                assert_eq!(path, "geometries[1].coordinates[0][2]")
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        assert_eq!(Value::MultiPolygon(vec![]).dimension().unwrap(), 0);
+    }
+}
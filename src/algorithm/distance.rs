@@ -0,0 +1,156 @@
+use crate::algorithm::{segments, segments_intersect};
+use crate::{Position, Value};
+
+/// Mean Earth radius in meters, as used by the haversine formula
+pub(crate) const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+/// Great-circle distance in meters between two `[longitude, latitude, ..]` positions
+pub(crate) fn haversine(a: &[f64], b: &[f64]) -> f64 {
+    let (lat_a, lat_b) = (a[1].to_radians(), b[1].to_radians());
+    let half_dlat = (lat_b - lat_a) / 2.;
+    let half_dlon = (b[0] - a[0]).to_radians() / 2.;
+    let h = half_dlat.sin().powi(2) + lat_a.cos() * lat_b.cos() * half_dlon.sin().powi(2);
+    2. * EARTH_RADIUS_METERS * h.sqrt().min(1.).asin()
+}
+
+impl Value {
+    /// The minimum great-circle distance in meters between this geometry and `other`.
+    ///
+    /// Positions are read as `[longitude, latitude]` in degrees. The distance between points is
+    /// exact (up to the spherical Earth model); the distance from a point to a segment is found by
+    /// projecting onto the segment in a local equirectangular approximation, which is accurate
+    /// for segments that are short relative to the Earth's radius. Lines which cross are
+    /// `0.0` apart. Only vertices and segments are considered: a point inside a polygon is as
+    /// far from it as from its nearest ring.
+    ///
+    /// Positions with fewer than two coordinates are skipped. Returns `f64::INFINITY` if either
+    /// geometry has no positions.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let london = Value::Point(vec![-0.1278, 51.5074]);
+    /// let paris = Value::Point(vec![2.3522, 48.8566]);
+    /// let distance = london.min_distance_meters(&paris);
+    /// assert!((distance - 343_556.).abs() < 100.);
+    /// ```
+    pub fn min_distance_meters(&self, other: &Value) -> f64 {
+        let others = parts(other);
+        let mut min = f64::INFINITY;
+        for a in parts(self) {
+            for b in &others {
+                min = min.min(part_distance(&a, b));
+                if min == 0. {
+                    return min;
+                }
+            }
+        }
+        min
+    }
+}
+
+/// The parts of `value`, without positions lacking an `x` or `y`
+fn parts(value: &Value) -> Vec<Vec<&Position>> {
+    value
+        .parts_iter()
+        .map(|part| part.iter().filter(|position| position.len() >= 2).collect())
+        .collect()
+}
+
+fn part_distance(a: &[&Position], b: &[&Position]) -> f64 {
+    let crosses =
+        segments(a).any(|(a1, a2)| segments(b).any(|(b1, b2)| segments_intersect(a1, a2, b1, b2)));
+    if crosses {
+        return 0.;
+    }
+    let a_to_b = a.iter().map(|point| point_to_part(point, b));
+    let b_to_a = b.iter().map(|point| point_to_part(point, a));
+    a_to_b.chain(b_to_a).fold(f64::INFINITY, f64::min)
+}
+
+fn point_to_part(point: &[f64], part: &[&Position]) -> f64 {
+    match part {
+        [] => f64::INFINITY,
+        [only] => haversine(point, only),
+        _ => segments(part)
+            .map(|(start, end)| point_to_segment(point, start, end))
+            .fold(f64::INFINITY, f64::min),
+    }
+}
+
+fn point_to_segment(point: &[f64], start: &[f64], end: &[f64]) -> f64 {
+    // Project into a plane centred on `point`, with longitudes scaled by the cosine of its
+    // latitude, then measure the real distance to the nearest point found there
+    let scale = point[1].to_radians().cos();
+    let local = |p: &[f64]| ((p[0] - point[0]) * scale, p[1] - point[1]);
+    let (sx, sy) = local(start);
+    let (ex, ey) = local(end);
+    let (dx, dy) = (ex - sx, ey - sy);
+    let length_squared = dx * dx + dy * dy;
+    let t = if length_squared == 0. {
+        0.
+    } else {
+        (-(sx * dx + sy * dy) / length_squared).clamp(0., 1.)
+    };
+    let nearest = [
+        start[0] + t * (end[0] - start[0]),
+        start[1] + t * (end[1] - start[1]),
+    ];
+    haversine(point, &nearest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::haversine;
+    use crate::Value;
+
+    #[test]
+    fn point_to_point() {
+        // One degree of latitude
+        let distance = haversine(&[0., 0.], &[0., 1.]);
+        assert!((distance - 111_195.).abs() < 1.);
+        assert_eq!(
+            Value::Point(vec![0., 0.]).min_distance_meters(&Value::Point(vec![0., 1.])),
+            distance
+        );
+    }
+
+    #[test]
+    fn point_to_line_projects_onto_segment() {
+        let line = Value::LineString(vec![vec![-1., 0.], vec![1., 0.]]);
+        let point = Value::Point(vec![0., 1.]);
+        let expected = haversine(&[0., 0.], &[0., 1.]);
+        assert!((point.min_distance_meters(&line) - expected).abs() < 1e-6);
+        assert_eq!(
+            point.min_distance_meters(&line),
+            line.min_distance_meters(&point)
+        );
+    }
+
+    #[test]
+    fn crossing_lines() {
+        let a = Value::LineString(vec![vec![-1., 0.], vec![1., 0.]]);
+        let b = Value::LineString(vec![vec![0., -1.], vec![0., 1.]]);
+        assert_eq!(a.min_distance_meters(&b), 0.);
+    }
+
+    #[test]
+    fn empty_geometry() {
+        let empty = Value::MultiPoint(vec![]);
+        assert_eq!(
+            empty.min_distance_meters(&Value::Point(vec![0., 0.])),
+            f64::INFINITY
+        );
+    }
+
+    #[test]
+    fn short_positions_are_skipped() {
+        let line = Value::LineString(vec![vec![0., 0.], vec![1.], vec![0., 1.]]);
+        let point = Value::Point(vec![0., 1.]);
+        assert_eq!(point.min_distance_meters(&line), 0.);
+        assert_eq!(
+            Value::MultiPoint(vec![vec![], vec![0.]]).min_distance_meters(&point),
+            f64::INFINITY
+        );
+    }
+}
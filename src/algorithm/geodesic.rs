@@ -0,0 +1,115 @@
+use crate::algorithm::distance::EARTH_RADIUS_METERS;
+
+/// The point halfway along the great circle from `a` to `b`
+///
+/// Positions are `[longitude, latitude]` in degrees, on the same spherical Earth as
+/// [`Value::min_distance_meters`](enum.Value.html#method.min_distance_meters). The longitude is normalized to `[-180, 180)`. The midpoint of antipodal positions is not
+/// well defined.
+///
+/// ```
+/// use geojson::geodesic_midpoint;
+///
+/// let [lon, lat] = geodesic_midpoint([0., 0.], [90., 0.]);
+/// assert!((lon - 45.).abs() < 1e-9 && lat.abs() < 1e-9);
+/// ```
+pub fn geodesic_midpoint(a: [f64; 2], b: [f64; 2]) -> [f64; 2] {
+    let (lon_a, lat_a) = (a[0].to_radians(), a[1].to_radians());
+    let lat_b = b[1].to_radians();
+    let dlon = b[0].to_radians() - lon_a;
+    let (bx, by) = (lat_b.cos() * dlon.cos(), lat_b.cos() * dlon.sin());
+    let lat = (lat_a.sin() + lat_b.sin()).atan2(((lat_a.cos() + bx).powi(2) + by.powi(2)).sqrt());
+    let lon = lon_a + by.atan2(lat_a.cos() + bx);
+    [normalize_longitude(lon.to_degrees()), lat.to_degrees()]
+}
+
+/// The initial bearing of the great circle from `a` to `b`, in degrees clockwise from north in
+/// `[0, 360)`
+///
+/// The bearing generally changes along the way; the final bearing is the reverse of
+/// `geodesic_bearing(b, a)`. Identical positions have a bearing of `0`.
+///
+/// ```
+/// use geojson::geodesic_bearing;
+///
+/// assert!((geodesic_bearing([0., 0.], [1., 0.]) - 90.).abs() < 1e-9);
+/// assert!((geodesic_bearing([0., 0.], [0., -1.]) - 180.).abs() < 1e-9);
+/// ```
+pub fn geodesic_bearing(a: [f64; 2], b: [f64; 2]) -> f64 {
+    let (lat_a, lat_b) = (a[1].to_radians(), b[1].to_radians());
+    let dlon = (b[0] - a[0]).to_radians();
+    let y = dlon.sin() * lat_b.cos();
+    let x = lat_a.cos() * lat_b.sin() - lat_a.sin() * lat_b.cos() * dlon.cos();
+    y.atan2(x).to_degrees().rem_euclid(360.)
+}
+
+/// The position reached by travelling `distance_m` meters along the great circle leaving
+/// `start` at `bearing` degrees clockwise from north
+///
+/// The longitude is normalized to `[-180, 180)`.
+///
+/// ```
+/// use geojson::{geodesic_bearing, geodesic_destination};
+///
+/// let [lon, lat] = geodesic_destination([0., 0.], 90., 111_195.);
+/// assert!((lon - 1.).abs() < 1e-4 && lat.abs() < 1e-9);
+/// ```
+pub fn geodesic_destination(start: [f64; 2], bearing: f64, distance_m: f64) -> [f64; 2] {
+    let (lon, lat) = (start[0].to_radians(), start[1].to_radians());
+    let bearing = bearing.to_radians();
+    let angle = distance_m / EARTH_RADIUS_METERS;
+    let end_lat = (lat.sin() * angle.cos() + lat.cos() * angle.sin() * bearing.cos()).asin();
+    let end_lon = lon
+        + (bearing.sin() * angle.sin() * lat.cos()).atan2(angle.cos() - lat.sin() * end_lat.sin());
+    [
+        normalize_longitude(end_lon.to_degrees()),
+        end_lat.to_degrees(),
+    ]
+}
+
+fn normalize_longitude(lon: f64) -> f64 {
+    (lon + 180.).rem_euclid(360.) - 180.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{geodesic_bearing, geodesic_destination, geodesic_midpoint};
+    use crate::algorithm::distance::haversine;
+
+    fn assert_close(a: [f64; 2], b: [f64; 2]) {
+        assert!(
+            (a[0] - b[0]).abs() < 1e-6 && (a[1] - b[1]).abs() < 1e-6,
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn route_is_consistent() {
+        let (london, new_york) = ([-0.1278, 51.5074], [-74.006, 40.7128]);
+        let distance = haversine(&london, &new_york);
+
+        let midpoint = geodesic_midpoint(london, new_york);
+        assert!((haversine(&london, &midpoint) - distance / 2.).abs() < 1e-3);
+        assert!((haversine(&midpoint, &new_york) - distance / 2.).abs() < 1e-3);
+        // The great circle bends north of both ends
+        assert!(midpoint[1] > london[1]);
+
+        let bearing = geodesic_bearing(london, new_york);
+        assert!((bearing - 288.).abs() < 1., "{:?}", bearing);
+        assert_close(geodesic_destination(london, bearing, distance), new_york);
+        assert_close(
+            geodesic_destination(london, bearing, distance / 2.),
+            midpoint,
+        );
+    }
+
+    #[test]
+    fn crossing_the_antimeridian() {
+        assert_close(geodesic_midpoint([179., 0.], [-179., 0.]), [-180., 0.]);
+        assert!((geodesic_bearing([179., 0.], [-179., 0.]) - 90.).abs() < 1e-9);
+        let [lon, _] = geodesic_destination([179., 0.], 90., 2. * 111_195.);
+        assert!((lon + 179.).abs() < 1e-3);
+        assert_eq!(geodesic_bearing([1., 2.], [1., 2.]), 0.);
+    }
+}
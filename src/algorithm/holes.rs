@@ -0,0 +1,73 @@
+use crate::Value;
+
+impl Value {
+    /// Drop the interior rings of every `Polygon` and `MultiPolygon` member, in place, leaving
+    /// only their exteriors.
+    ///
+    /// `GeometryCollection` members are handled recursively, and other geometries are left
+    /// untouched.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let exterior = vec![vec![0., 0.], vec![4., 0.], vec![0., 4.], vec![0., 0.]];
+    /// let hole = vec![vec![1., 1.], vec![1., 2.], vec![2., 1.], vec![1., 1.]];
+    /// let mut polygon = Value::Polygon(vec![exterior.clone(), hole]);
+    /// polygon.remove_holes();
+    /// assert_eq!(polygon, Value::Polygon(vec![exterior]));
+    /// ```
+    pub fn remove_holes(&mut self) {
+        match self {
+            Value::Polygon(rings) => rings.truncate(1),
+            Value::MultiPolygon(polygons) => {
+                polygons.iter_mut().for_each(|rings| rings.truncate(1))
+            }
+            Value::GeometryCollection(geometries) => geometries
+                .iter_mut()
+                .for_each(|geometry| geometry.value.remove_holes()),
+            Value::Point(_)
+            | Value::MultiPoint(_)
+            | Value::LineString(_)
+            | Value::MultiLineString(_)
+            | Value::Unknown(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, Value};
+
+    #[test]
+    fn remove_holes_recursively() {
+        let ring = |offset: f64| {
+            vec![
+                vec![offset, offset],
+                vec![offset + 1., offset],
+                vec![offset, offset + 1.],
+                vec![offset, offset],
+            ]
+        };
+        let lines = Value::MultiLineString(vec![ring(0.), ring(1.)]);
+        let mut value = Value::GeometryCollection(vec![
+            Geometry::new(Value::MultiPolygon(vec![
+                vec![ring(0.), ring(0.2), ring(0.4)],
+                vec![ring(5.)],
+                vec![],
+            ])),
+            Geometry::new(lines.clone()),
+        ]);
+        value.remove_holes();
+        assert_eq!(
+            value,
+            Value::GeometryCollection(vec![
+                Geometry::new(Value::MultiPolygon(vec![
+                    vec![ring(0.)],
+                    vec![ring(5.)],
+                    vec![]
+                ])),
+                Geometry::new(lines),
+            ])
+        );
+    }
+}
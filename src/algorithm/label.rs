@@ -0,0 +1,331 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::algorithm::area::ring_signed_area;
+use crate::algorithm::{closest_point_on_segment, segments};
+use crate::{Position, Value};
+
+impl Value {
+    /// A point at which to place a label for this geometry.
+    ///
+    /// For a `Polygon` this is the pole of inaccessibility: the point inside it farthest from
+    /// its edges, found to within a thousandth of its larger bounding box dimension by the
+    /// "polylabel" algorithm. Unlike the centroid, it always lies inside the polygon, even a
+    /// C-shaped one or one with a hole in the middle. A `MultiPolygon` is labelled at the pole of
+    /// inaccessibility of its largest part, so that a label isn't placed in the sea between
+    /// islands.
+    ///
+    /// Other geometries are labelled at their centroid: the mean of their points, or for lines
+    /// the length-weighted mean of the midpoints of their segments. A `GeometryCollection` is
+    /// labelled as if its members of the highest dimension were a single multi-part geometry.
+    ///
+    /// Returns `None` if there are no positions, if any position has fewer than two coordinates
+    /// or a non-finite `x` or `y`, and for `Unknown` geometries.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// // A U shape, whose centroid lies in the gap between its arms
+    /// let u = Value::Polygon(vec![vec![
+    ///     vec![0., 0.], vec![3., 0.], vec![3., 3.], vec![2., 3.],
+    ///     vec![2., 1.], vec![1., 1.], vec![1., 3.], vec![0., 3.], vec![0., 0.],
+    /// ]]);
+    /// let (x, y) = u.label_point().unwrap();
+    /// assert!(y < 1. || x < 1. || x > 2.);
+    /// ```
+    pub fn label_point(&self) -> Option<(f64, f64)> {
+        let malformed = |position: &Position| match position[..] {
+            [x, y, ..] => !x.is_finite() || !y.is_finite(),
+            _ => true,
+        };
+        if self.coords_iter().any(malformed) {
+            return None;
+        }
+        let mut parts = Parts::default();
+        parts.collect(self);
+        let largest = parts
+            .polygons
+            .iter()
+            .map(|polygon| (polygon_area(polygon), polygon))
+            .filter(|(_, polygon)| matches!(polygon.first(), Some(ring) if !ring.is_empty()))
+            .max_by(|(a, _), (b, _)| a.total_cmp(b));
+        if let Some((_, polygon)) = largest {
+            return Some(pole_of_inaccessibility(polygon));
+        }
+        line_centroid(&parts.lines).or_else(|| point_centroid(&parts.points))
+    }
+}
+
+/// The parts of a geometry, by dimension
+#[derive(Default)]
+struct Parts<'a> {
+    points: Vec<&'a Position>,
+    lines: Vec<&'a [Position]>,
+    polygons: Vec<&'a [Vec<Position>]>,
+}
+
+impl<'a> Parts<'a> {
+    fn collect(&mut self, value: &'a Value) {
+        match value {
+            Value::Point(position) => self.points.push(position),
+            Value::MultiPoint(positions) => self.points.extend(positions),
+            Value::LineString(line) => self.lines.push(line),
+            Value::MultiLineString(lines) => self.lines.extend(lines.iter().map(|l| &l[..])),
+            Value::Polygon(rings) => self.polygons.push(rings),
+            Value::MultiPolygon(polygons) => self.polygons.extend(polygons.iter().map(|p| &p[..])),
+            Value::GeometryCollection(geometries) => geometries
+                .iter()
+                .for_each(|geometry| self.collect(&geometry.value)),
+            Value::Unknown(_) => {}
+        }
+    }
+}
+
+fn polygon_area(rings: &[Vec<Position>]) -> f64 {
+    let mut rings = rings.iter().map(|ring| ring_signed_area(ring).abs());
+    let exterior = rings.next().unwrap_or(0.);
+    exterior - rings.sum::<f64>()
+}
+
+fn point_centroid(points: &[&Position]) -> Option<(f64, f64)> {
+    let points: Vec<_> = points.iter().filter(|p| p.len() >= 2).collect();
+    if points.is_empty() {
+        return None;
+    }
+    let n = points.len() as f64;
+    let (x, y) = points
+        .iter()
+        .fold((0., 0.), |(x, y), p| (x + p[0], y + p[1]));
+    Some((x / n, y / n))
+}
+
+fn line_centroid(lines: &[&[Position]]) -> Option<(f64, f64)> {
+    let (mut x, mut y, mut length) = (0., 0., 0.);
+    for line in lines {
+        for (a, b) in segments(line) {
+            let segment_length = (b[0] - a[0]).hypot(b[1] - a[1]);
+            x += segment_length * (a[0] + b[0]) / 2.;
+            y += segment_length * (a[1] + b[1]) / 2.;
+            length += segment_length;
+        }
+    }
+    if length > 0. {
+        return Some((x / length, y / length));
+    }
+    // Every line is a single point, or has no positions at all
+    let points: Vec<_> = lines.iter().flat_map(|line| line.iter()).collect();
+    point_centroid(&points)
+}
+
+/// A square of the search grid, centred on `x`, `y` with half-width `h`
+#[derive(Clone, Copy)]
+struct Cell {
+    x: f64,
+    y: f64,
+    h: f64,
+    /// The signed distance from the centre to the polygon's edges, positive inside
+    distance: f64,
+    /// The greatest distance any point within the cell could have
+    max: f64,
+}
+
+impl Cell {
+    fn new(x: f64, y: f64, h: f64, rings: &[Vec<Position>]) -> Self {
+        let distance = signed_distance(x, y, rings);
+        Cell {
+            x,
+            y,
+            h,
+            distance,
+            max: distance + h * std::f64::consts::SQRT_2,
+        }
+    }
+}
+
+impl PartialEq for Cell {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Cell {}
+
+impl PartialOrd for Cell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.max.total_cmp(&other.max)
+    }
+}
+
+/// Find the pole of inaccessibility of a polygon with a non-empty exterior
+fn pole_of_inaccessibility(rings: &[Vec<Position>]) -> (f64, f64) {
+    let exterior = &rings[0];
+    let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+    let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for position in exterior {
+        min_x = min_x.min(position[0]);
+        min_y = min_y.min(position[1]);
+        max_x = max_x.max(position[0]);
+        max_y = max_y.max(position[1]);
+    }
+    let cell_size = (max_x - min_x).min(max_y - min_y);
+    if cell_size == 0. {
+        return (exterior[0][0], exterior[0][1]);
+    }
+    let precision = (max_x - min_x).max(max_y - min_y) / 1000.;
+
+    let mut queue = BinaryHeap::new();
+    let h = cell_size / 2.;
+    let mut x = min_x;
+    while x < max_x {
+        let mut y = min_y;
+        while y < max_y {
+            queue.push(Cell::new(x + h, y + h, h, rings));
+            y += cell_size;
+        }
+        x += cell_size;
+    }
+
+    let (centroid_x, centroid_y) = ring_centroid(exterior);
+    let mut best = Cell::new(centroid_x, centroid_y, 0., rings);
+    let center = Cell::new((min_x + max_x) / 2., (min_y + max_y) / 2., 0., rings);
+    if center.distance > best.distance {
+        best = center;
+    }
+
+    while let Some(cell) = queue.pop() {
+        if cell.distance > best.distance {
+            best = cell;
+        }
+        if cell.max - best.distance <= precision {
+            continue;
+        }
+        let h = cell.h / 2.;
+        for (dx, dy) in [(-h, -h), (h, -h), (-h, h), (h, h)] {
+            queue.push(Cell::new(cell.x + dx, cell.y + dy, h, rings));
+        }
+    }
+    (best.x, best.y)
+}
+
+/// The centroid of the area enclosed by `ring`, or its first position if it has no area
+fn ring_centroid(ring: &[Position]) -> (f64, f64) {
+    let (mut x, mut y, mut twice_area) = (0., 0., 0.);
+    for (a, b) in segments(ring) {
+        let cross = a[0] * b[1] - b[0] * a[1];
+        x += (a[0] + b[0]) * cross;
+        y += (a[1] + b[1]) * cross;
+        twice_area += cross;
+    }
+    if twice_area == 0. {
+        return (ring[0][0], ring[0][1]);
+    }
+    (x / (3. * twice_area), y / (3. * twice_area))
+}
+
+/// The distance from `x`, `y` to the nearest edge of the polygon, negated if it is outside
+fn signed_distance(x: f64, y: f64, rings: &[Vec<Position>]) -> f64 {
+    let point = [x, y];
+    let mut inside = false;
+    let mut min_squared = f64::INFINITY;
+    for ring in rings {
+        for (a, b) in segments(ring) {
+            if (a[1] > y) != (b[1] > y) && x < (b[0] - a[0]) * (y - a[1]) / (b[1] - a[1]) + a[0] {
+                inside = !inside;
+            }
+            let closest = closest_point_on_segment(&point, a, b);
+            min_squared = min_squared.min((closest[0] - x).powi(2) + (closest[1] - y).powi(2));
+        }
+    }
+    let distance = min_squared.sqrt();
+    if inside {
+        distance
+    } else {
+        -distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, Value};
+
+    fn square(x: f64, y: f64, size: f64) -> Vec<Vec<f64>> {
+        vec![
+            vec![x, y],
+            vec![x + size, y],
+            vec![x + size, y + size],
+            vec![x, y + size],
+            vec![x, y],
+        ]
+    }
+
+    fn assert_near(actual: (f64, f64), expected: (f64, f64), tolerance: f64) {
+        assert!(
+            (actual.0 - expected.0).abs() <= tolerance
+                && (actual.1 - expected.1).abs() <= tolerance,
+            "{:?} is not near {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn polygons() {
+        let polygon = Value::Polygon(vec![square(0., 0., 10.)]);
+        assert_near(polygon.label_point().unwrap(), (5., 5.), 1e-9);
+
+        // A hole in the middle pushes the label into the thickest part of the ring
+        let framed = Value::Polygon(vec![square(0., 0., 10.), square(2., 2., 7.)]);
+        let (x, y) = framed.label_point().unwrap();
+        assert!(x < 2. || y < 2., "{:?}", (x, y));
+
+        let islands = Value::MultiPolygon(vec![
+            vec![square(0., 0., 1.)],
+            vec![square(10., 10., 4.)],
+            vec![square(20., 0., 2.)],
+        ]);
+        assert_near(islands.label_point().unwrap(), (12., 12.), 1e-2);
+    }
+
+    #[test]
+    fn other_geometries() {
+        let line = Value::LineString(vec![vec![0., 0.], vec![3., 0.], vec![3., 1.]]);
+        assert_near(line.label_point().unwrap(), (1.875, 0.125), 1e-9);
+        let points = Value::MultiPoint(vec![vec![0., 0.], vec![2., 4.]]);
+        assert_eq!(points.label_point(), Some((1., 2.)));
+
+        // Points are ignored beside lines
+        let collection = Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![100., 100.])),
+            Geometry::new(line.clone()),
+        ]);
+        assert_eq!(collection.label_point(), line.label_point());
+
+        assert_eq!(Value::MultiPolygon(vec![]).label_point(), None);
+        assert_eq!(
+            Value::LineString(vec![vec![1., 2.]]).label_point(),
+            Some((1., 2.))
+        );
+        assert_eq!(Value::GeometryCollection(vec![]).label_point(), None);
+    }
+
+    #[test]
+    fn malformed_positions() {
+        let short = Value::Polygon(vec![vec![vec![0.], vec![1.], vec![2.], vec![0.]]]);
+        assert_eq!(short.label_point(), None);
+        let mut nan = square(0., 0., 2.);
+        nan[2] = vec![f64::NAN, 2.];
+        assert_eq!(Value::Polygon(vec![nan]).label_point(), None);
+        let infinite = Value::LineString(vec![vec![0., 0.], vec![f64::INFINITY, 1.]]);
+        assert_eq!(infinite.label_point(), None);
+        assert_eq!(
+            Value::MultiPoint(vec![vec![1., 2.], vec![3.]]).label_point(),
+            None
+        );
+    }
+}
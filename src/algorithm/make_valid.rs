@@ -0,0 +1,276 @@
+use super::{area::ring_signed_area, collinear::remove_from_ring};
+use crate::{Position, Value};
+
+/// What [`Value::make_valid`](enum.Value.html#method.make_valid) changed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Rings which didn't end with their first position and had it appended
+    pub rings_closed: usize,
+    /// Rings which were reversed into RFC 7946 winding order
+    pub rings_reoriented: usize,
+    /// Repeated positions, and positions collinear with their neighbours in a ring, which were
+    /// removed
+    pub positions_removed: usize,
+    /// Rings which had fewer than three distinct vertices left, or a position with fewer than
+    /// two coordinates, and were dropped along with the interior rings of a dropped exterior
+    pub rings_dropped: usize,
+}
+
+impl RepairReport {
+    /// Whether anything was changed
+    pub fn has_changes(&self) -> bool {
+        *self != RepairReport::default()
+    }
+}
+
+impl Value {
+    /// Repair the rings and lines of this `Value` in place, so that it can be exported as valid
+    /// GeoJSON, and report what was changed.
+    ///
+    /// * Rings which don't end with their first position are closed.
+    /// * Consecutive repeated positions are removed from lines and rings, and positions
+    ///   collinear with their neighbours are removed from rings.
+    /// * Rings left with fewer than three distinct vertices are dropped, as are rings with a
+    ///   position of fewer than two coordinates, which can't be repaired. An exterior ring being
+    ///   dropped drops its whole polygon, which is removed from a `MultiPolygon` or leaves a
+    ///   `Polygon` empty.
+    /// * Rings are reversed where necessary to follow the RFC 7946 winding order:
+    ///   counter-clockwise exteriors, clockwise holes.
+    ///
+    /// `GeometryCollection` members are repaired recursively. This does not resolve
+    /// self-intersections, which need the rings to be split rather than edited; see
+    /// [`has_self_intersection`](#method.has_self_intersection) to detect them.
+    ///
+    /// ```
+    /// use geojson::{RepairReport, Value};
+    ///
+    /// let mut polygon = Value::Polygon(vec![vec![
+    ///     vec![0., 0.],
+    ///     vec![0., 2.],
+    ///     vec![0., 2.],
+    ///     vec![2., 2.],
+    ///     vec![2., 0.],
+    /// ]]);
+    /// let report = polygon.make_valid();
+    /// assert_eq!(
+    ///     polygon,
+    ///     Value::Polygon(vec![vec![
+    ///         vec![0., 0.],
+    ///         vec![2., 0.],
+    ///         vec![2., 2.],
+    ///         vec![0., 2.],
+    ///         vec![0., 0.],
+    ///     ]])
+    /// );
+    /// assert_eq!(
+    ///     report,
+    ///     RepairReport {
+    ///         rings_closed: 1,
+    ///         rings_reoriented: 1,
+    ///         positions_removed: 1,
+    ///         rings_dropped: 0,
+    ///     }
+    /// );
+    /// ```
+    pub fn make_valid(&mut self) -> RepairReport {
+        let mut report = RepairReport::default();
+        self.make_valid_into(&mut report);
+        report
+    }
+
+    fn make_valid_into(&mut self, report: &mut RepairReport) {
+        match self {
+            Value::LineString(line) => remove_repeated(line, report),
+            Value::MultiLineString(lines) => lines
+                .iter_mut()
+                .for_each(|line| remove_repeated(line, report)),
+            Value::Polygon(rings) => repair_polygon(rings, report),
+            Value::MultiPolygon(polygons) => polygons.retain_mut(|rings| {
+                repair_polygon(rings, report);
+                !rings.is_empty()
+            }),
+            Value::GeometryCollection(geometries) => geometries
+                .iter_mut()
+                .for_each(|geometry| geometry.value.make_valid_into(report)),
+            Value::Point(_) | Value::MultiPoint(_) | Value::Unknown(_) => {}
+        }
+    }
+}
+
+fn remove_repeated(line: &mut Vec<Position>, report: &mut RepairReport) {
+    let len = line.len();
+    line.dedup();
+    report.positions_removed += len - line.len();
+}
+
+fn repair_polygon(rings: &mut Vec<Vec<Position>>, report: &mut RepairReport) {
+    let exterior_kept = match rings.first_mut() {
+        Some(exterior) => repair_ring(exterior, true, report),
+        None => return,
+    };
+    if !exterior_kept {
+        report.rings_dropped += rings.len();
+        rings.clear();
+        return;
+    }
+    let mut is_exterior = true;
+    rings.retain_mut(|ring| {
+        let kept = is_exterior || repair_ring(ring, false, report);
+        is_exterior = false;
+        if !kept {
+            report.rings_dropped += 1;
+        }
+        kept
+    });
+}
+
+/// Repair a single ring, returning `false` if it's degenerate and should be dropped
+fn repair_ring(ring: &mut Vec<Position>, is_exterior: bool, report: &mut RepairReport) -> bool {
+    if ring.iter().any(|position| position.len() < 2) {
+        return false;
+    }
+    let was_closed = ring.len() > 1 && ring.first() == ring.last();
+    if was_closed {
+        ring.pop();
+    }
+
+    let len = ring.len();
+    ring.dedup();
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    remove_from_ring(ring, 2, |area| area == 0.);
+    report.positions_removed += len - ring.len();
+
+    if ring.len() < 3 {
+        return false;
+    }
+    if !was_closed {
+        report.rings_closed += 1;
+    }
+    ring.push(ring[0].clone());
+    if (ring_signed_area(ring) > 0.) != is_exterior {
+        ring.reverse();
+        report.rings_reoriented += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RepairReport;
+    use crate::{Geometry, Value};
+
+    #[test]
+    fn valid_geometries_are_unchanged() {
+        let original = Value::GeometryCollection(vec![
+            Geometry::new(Value::Polygon(vec![
+                vec![
+                    vec![0., 0.],
+                    vec![4., 0.],
+                    vec![4., 4.],
+                    vec![0., 4.],
+                    vec![0., 0.],
+                ],
+                vec![
+                    vec![1., 1.],
+                    vec![1., 2.],
+                    vec![2., 2.],
+                    vec![2., 1.],
+                    vec![1., 1.],
+                ],
+            ])),
+            Geometry::new(Value::LineString(vec![vec![0., 0.], vec![1., 1.]])),
+            Geometry::new(Value::Point(vec![0., 0.])),
+        ]);
+        let mut value = original.clone();
+        assert!(!value.make_valid().has_changes());
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn degenerate_rings_are_dropped() {
+        let square = vec![
+            vec![0., 0.],
+            vec![4., 0.],
+            vec![4., 4.],
+            vec![0., 4.],
+            vec![0., 0.],
+        ];
+        let spike = vec![vec![1., 1.], vec![2., 2.], vec![1., 1.], vec![1., 1.]];
+        let mut value = Value::MultiPolygon(vec![
+            vec![
+                spike.clone(),
+                vec![vec![1., 1.], vec![1., 2.], vec![2., 1.]],
+            ],
+            vec![square.clone(), spike],
+        ]);
+        let report = value.make_valid();
+        assert_eq!(value, Value::MultiPolygon(vec![vec![square]]));
+        assert_eq!(
+            report,
+            RepairReport {
+                rings_closed: 0,
+                rings_reoriented: 0,
+                positions_removed: 2,
+                rings_dropped: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn collinear_vertices_and_repeated_line_positions_are_removed() {
+        let mut polygon = Value::Polygon(vec![vec![
+            vec![0., 0.],
+            vec![1., 0.],
+            vec![2., 0.],
+            vec![2., 2.],
+            vec![0., 2.],
+            vec![0., 1.],
+            vec![0., 0.],
+        ]]);
+        assert_eq!(polygon.make_valid().positions_removed, 2);
+        assert_eq!(
+            polygon,
+            Value::Polygon(vec![vec![
+                vec![0., 0.],
+                vec![2., 0.],
+                vec![2., 2.],
+                vec![0., 2.],
+                vec![0., 0.],
+            ]])
+        );
+
+        let mut line = Value::MultiLineString(vec![vec![vec![0., 0.], vec![0., 0.], vec![1., 1.]]]);
+        assert_eq!(line.make_valid().positions_removed, 1);
+        assert_eq!(
+            line,
+            Value::MultiLineString(vec![vec![vec![0., 0.], vec![1., 1.]]])
+        );
+    }
+
+    #[test]
+    fn rings_with_short_positions_are_dropped() {
+        let square = vec![
+            vec![0., 0.],
+            vec![4., 0.],
+            vec![4., 4.],
+            vec![0., 4.],
+            vec![0., 0.],
+        ];
+        let mut value = Value::Polygon(vec![vec![vec![0.], vec![1.], vec![2.], vec![0.]]]);
+        assert_eq!(value.make_valid().rings_dropped, 1);
+        assert_eq!(value, Value::Polygon(vec![]));
+
+        let hole = vec![vec![1., 1.], vec![1.], vec![2., 2.], vec![1., 1.]];
+        let mut value = Value::Polygon(vec![square.clone(), hole]);
+        assert_eq!(
+            value.make_valid(),
+            RepairReport {
+                rings_dropped: 1,
+                ..RepairReport::default()
+            }
+        );
+        assert_eq!(value, Value::Polygon(vec![square]));
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{Feature, FeatureCollection, Geometry, Position, Value};
+
+impl Value {
+    /// Apply `f` to every `Position` in place, recursing into `GeometryCollection`s.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut value = Value::LineString(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    /// value.map_coords(|position| position[0] += 10.0);
+    /// assert_eq!(value, Value::LineString(vec![vec![11.0, 2.0], vec![13.0, 4.0]]));
+    /// ```
+    pub fn map_coords<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut Position),
+    {
+        self.coords_iter_mut().for_each(f)
+    }
+
+    /// Apply `f` once to a flat buffer of every position's first `stride` coordinates, for
+    /// vectorized transforms which work on contiguous arrays, then write the results back.
+    ///
+    /// The buffer holds the positions in the same order as `map_coords` visits them, with
+    /// `stride` values each: `[x0, y0, x1, y1, ...]` for a stride of 2. Positions with fewer
+    /// than `stride` coordinates are padded with `NaN`, which is discarded when writing back,
+    /// and any coordinates beyond `stride` are left as they are. `f` is called exactly once,
+    /// with an empty buffer if there are no positions.
+    ///
+    /// # Panics
+    ///
+    /// If `stride` is 0.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut value = Value::LineString(vec![vec![1.0, 2.0, 100.0], vec![3.0, 4.0]]);
+    /// value.map_coords_slice(2, |coords| {
+    ///     assert_eq!(coords, &[1.0, 2.0, 3.0, 4.0]);
+    ///     coords.iter_mut().for_each(|c| *c *= 10.0);
+    /// });
+    /// assert_eq!(
+    ///     value,
+    ///     Value::LineString(vec![vec![10.0, 20.0, 100.0], vec![30.0, 40.0]])
+    /// );
+    /// ```
+    pub fn map_coords_slice<F>(&mut self, stride: usize, mut f: F)
+    where
+        F: FnMut(&mut [f64]),
+    {
+        assert!(stride > 0, "stride must be at least 1");
+        let mut buffer = Vec::new();
+        for position in self.coords_iter() {
+            let end = buffer.len() + stride;
+            buffer.extend(position.iter().take(stride));
+            buffer.resize(end, f64::NAN);
+        }
+        f(&mut buffer);
+        for (position, chunk) in self.coords_iter_mut().zip(buffer.chunks(stride)) {
+            for (coordinate, &transformed) in position.iter_mut().zip(chunk) {
+                *coordinate = transformed;
+            }
+        }
+    }
+}
+
+impl Geometry {
+    /// Apply `f` to every `Position` of the geometry's `value`.
+    ///
+    /// If the geometry has a `bbox`, it is recomputed from the transformed coordinates.
+    pub fn map_coords<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut Position),
+    {
+        self.value.map_coords(f);
+        if self.bbox.is_some() {
+            self.bbox = self.bbox_calculated();
+        }
+    }
+}
+
+impl Feature {
+    /// Apply `f` to every `Position` of the feature's geometry, if it has one.
+    ///
+    /// Any `bbox` on the geometry or on the feature itself is recomputed from the transformed
+    /// coordinates.
+    pub fn map_coords<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut Position),
+    {
+        if let Some(geometry) = self.geometry.as_mut() {
+            geometry.map_coords(f);
+        }
+        if self.bbox.is_some() {
+            self.bbox = self
+                .geometry
+                .as_ref()
+                .and_then(|geometry| geometry.bbox_calculated());
+        }
+    }
+
+    /// Apply the fallible `f` to every `Position` of the feature's geometry, as `map_coords`
+    /// does.
+    ///
+    /// The geometry is transformed as a whole: if `f` fails for any position, its error is
+    /// returned and the feature is left unmodified.
+    pub fn try_map_coords<F, E>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&mut Position) -> Result<(), E>,
+    {
+        if let Some(geometry) = self.geometry.as_ref() {
+            let mut geometry = geometry.clone();
+            geometry.value.coords_iter_mut().try_for_each(&mut f)?;
+            if geometry.bbox.is_some() {
+                geometry.bbox = geometry.bbox_calculated();
+            }
+            if self.bbox.is_some() {
+                self.bbox = geometry.bbox_calculated();
+            }
+            self.geometry = Some(geometry);
+        }
+        Ok(())
+    }
+}
+
+impl FeatureCollection {
+    /// Apply `f` to every `Position` of every feature, e.g. to reproject the whole collection.
+    ///
+    /// Any `bbox` on the collection, its features or their geometries is recomputed from the
+    /// transformed coordinates.
+    pub fn map_coords<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut Position),
+    {
+        for feature in &mut self.features {
+            feature.map_coords(&mut f);
+        }
+        if self.bbox.is_some() {
+            self.bbox = self.bbox_calculated();
+        }
+    }
+
+    /// Apply the fallible `f` to every `Position` of every feature, e.g. to reproject into a
+    /// projection which isn't defined everywhere.
+    ///
+    /// Features for which `f` fails are left unmodified and the rest are still transformed.
+    /// Returns the index of each failed feature along with the first error `f` returned for
+    /// it. Bboxes are recomputed as with `map_coords`.
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection, Geometry, Value};
+    ///
+    /// let mut collection = FeatureCollection::from(vec![
+    ///     Feature::with_computed_bbox(Geometry::new(Value::Point(vec![10.0, 20.0]))),
+    ///     Feature::with_computed_bbox(Geometry::new(Value::Point(vec![10.0, 95.0]))),
+    /// ]);
+    ///
+    /// let failed = collection.try_map_coords(|position| {
+    ///     if position[1].abs() > 90.0 {
+    ///         return Err("latitude out of range");
+    ///     }
+    ///     position[0] *= 2.0;
+    ///     Ok(())
+    /// });
+    ///
+    /// assert_eq!(failed, vec![(1, "latitude out of range")]);
+    /// let point = |i: usize| collection.features[i].geometry.as_ref().unwrap().value.clone();
+    /// assert_eq!(point(0), Value::Point(vec![20.0, 20.0]));
+    /// assert_eq!(point(1), Value::Point(vec![10.0, 95.0]));
+    /// ```
+    pub fn try_map_coords<F, E>(&mut self, mut f: F) -> Vec<(usize, E)>
+    where
+        F: FnMut(&mut Position) -> Result<(), E>,
+    {
+        let failed = self
+            .features
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, feature)| feature.try_map_coords(&mut f).err().map(|e| (i, e)))
+            .collect();
+        if self.bbox.is_some() {
+            self.bbox = self.bbox_calculated();
+        }
+        failed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Feature, FeatureCollection, Geometry, Value};
+
+    fn translate(position: &mut crate::Position) {
+        position[0] += 1.0;
+        position[1] += 2.0;
+    }
+
+    #[test]
+    fn map_coords_recurses() {
+        let mut value = Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![0., 0.])),
+            Geometry::new(Value::Polygon(vec![vec![
+                vec![0., 0.],
+                vec![1., 0.],
+                vec![1., 1.],
+                vec![0., 0.],
+            ]])),
+        ]);
+        value.map_coords(translate);
+        assert_eq!(
+            value,
+            Value::GeometryCollection(vec![
+                Geometry::new(Value::Point(vec![1., 2.])),
+                Geometry::new(Value::Polygon(vec![vec![
+                    vec![1., 2.],
+                    vec![2., 2.],
+                    vec![2., 3.],
+                    vec![1., 2.],
+                ]])),
+            ])
+        );
+    }
+
+    #[test]
+    fn map_coords_slice_keeps_structure() {
+        let original = Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![0., 0., 9.])),
+            Geometry::new(Value::MultiLineString(vec![
+                vec![vec![1., 1.], vec![2., 2.]],
+                vec![vec![3.], vec![4., 4., 4.]],
+            ])),
+        ]);
+        let mut value = original.clone();
+        let mut calls = 0;
+        value.map_coords_slice(2, |coords| {
+            calls += 1;
+            assert_eq!(coords.len(), 10);
+            assert!(coords[7].is_nan());
+            coords.iter_mut().for_each(|c| *c += 0.5);
+        });
+        assert_eq!(calls, 1);
+
+        let mut expected = original;
+        expected.map_coords(|position| {
+            position.iter_mut().take(2).for_each(|c| *c += 0.5);
+        });
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn stale_bboxes_are_recomputed() {
+        let geometry = Geometry {
+            bbox: Some(vec![0., 0., 1., 1.]),
+            value: Value::LineString(vec![vec![0., 0.], vec![1., 1.]]),
+            foreign_members: None,
+        };
+        let feature = Feature {
+            bbox: Some(vec![0., 0., 1., 1.]),
+            geometry: Some(geometry),
+            id: None,
+            properties: None,
+            foreign_members: None,
+        };
+        let without_bbox = Feature {
+            bbox: None,
+            geometry: Some(Geometry::new(Value::Point(vec![5., 5.]))),
+            id: None,
+            properties: None,
+            foreign_members: None,
+        };
+        let mut collection = FeatureCollection {
+            bbox: Some(vec![0., 0., 5., 5.]),
+            features: vec![feature, without_bbox],
+            foreign_members: None,
+        };
+
+        collection.map_coords(translate);
+
+        assert_eq!(collection.bbox, Some(vec![1., 2., 6., 7.]));
+        let feature = &collection.features[0];
+        assert_eq!(feature.bbox, Some(vec![1., 2., 2., 3.]));
+        assert_eq!(
+            feature.geometry.as_ref().unwrap().bbox,
+            Some(vec![1., 2., 2., 3.])
+        );
+        assert_eq!(collection.features[1].bbox, None);
+    }
+
+    #[test]
+    fn try_map_coords_leaves_failed_features_untouched() {
+        let line = Feature::with_computed_bbox(Geometry::new(Value::LineString(vec![
+            vec![0., 0.],
+            vec![1., 1.],
+        ])));
+        let partly_invalid = Feature::with_computed_bbox(Geometry::new(Value::LineString(vec![
+            vec![0., 0.],
+            vec![-1., 1.],
+        ])));
+        let mut collection = FeatureCollection {
+            bbox: Some(vec![-1., 0., 1., 1.]),
+            features: vec![line, partly_invalid.clone(), Feature::with_null_geometry()],
+            foreign_members: None,
+        };
+
+        let failed = collection.try_map_coords(|position| {
+            if position[0] < 0. {
+                return Err(position[0]);
+            }
+            translate(position);
+            Ok(())
+        });
+
+        assert_eq!(failed, vec![(1, -1.)]);
+        assert_eq!(collection.features[0].bbox, Some(vec![1., 2., 2., 3.]));
+        assert_eq!(collection.features[1], partly_invalid);
+        assert_eq!(collection.bbox, Some(vec![-1., 0., 2., 3.]));
+    }
+}
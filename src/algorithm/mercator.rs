@@ -0,0 +1,175 @@
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use crate::{BboxDimension, Value};
+
+/// The radius of the sphere used by spherical ("Web") Mercator, in metres
+const EARTH_RADIUS: f64 = 6_378_137.0;
+
+/// The latitude at which spherical Mercator's square extent ends, about 85.0511°
+fn max_latitude() -> f64 {
+    (2.0 * PI.exp().atan() - FRAC_PI_2).to_degrees()
+}
+
+impl Value {
+    /// Project every position from WGS84 longitude / latitude (EPSG:4326) to spherical
+    /// Mercator metres (EPSG:3857), as used by web map tiles.
+    ///
+    /// Latitudes beyond about ±85.0511°, where the projection's square extent ends, are
+    /// clamped to that limit. Any coordinates after the first two are left untouched.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut value = Value::Point(vec![180.0, 85.0511287798066]);
+    /// value.to_web_mercator();
+    /// match value {
+    ///     Value::Point(position) => {
+    ///         assert_eq!(position[0], 20037508.342789244);
+    ///         assert!((position[1] - 20037508.342789244).abs() < 1e-6);
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn to_web_mercator(&mut self) {
+        let max_latitude = max_latitude();
+        for position in self.coords_iter_mut() {
+            if let [x, y, ..] = position.as_mut_slice() {
+                let latitude = y.clamp(-max_latitude, max_latitude).to_radians();
+                *x = EARTH_RADIUS * x.to_radians();
+                *y = EARTH_RADIUS * (FRAC_PI_4 + latitude / 2.0).tan().ln();
+            }
+        }
+    }
+
+    /// Unproject every position from spherical Mercator metres (EPSG:3857) to WGS84
+    /// longitude / latitude (EPSG:4326), the inverse of
+    /// [`to_web_mercator`](#method.to_web_mercator).
+    ///
+    /// Any coordinates after the first two are left untouched.
+    pub fn to_wgs84(&mut self) {
+        for position in self.coords_iter_mut() {
+            if let [x, y, ..] = position.as_mut_slice() {
+                *x = (*x / EARTH_RADIUS).to_degrees();
+                *y = (2.0 * (*y / EARTH_RADIUS).exp().atan() - FRAC_PI_2).to_degrees();
+            }
+        }
+    }
+
+    /// The `(x, y)` XYZ web map tiles at `zoom` which the geometry's bounding box touches,
+    /// reading positions as WGS84 longitude / latitude, in rows from north to south and west
+    /// to east within each row.
+    ///
+    /// This is a conservative cover: every tile the geometry touches is included, but so are
+    /// tiles which only the bounding box reaches, such as those between the arms of an L-shaped
+    /// line. Latitudes beyond the tiles' extent of about ±85.0511° are clamped to it. Returns an
+    /// empty `Vec` if there are no positions.
+    ///
+    /// # Panics
+    ///
+    /// If `zoom` is more than 32, where tile indices no longer fit a `u32`.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let line = Value::LineString(vec![vec![-10.0, 10.0], vec![10.0, -10.0]]);
+    /// assert_eq!(line.covering_tiles(0), vec![(0, 0)]);
+    /// assert_eq!(line.covering_tiles(1), vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+    /// ```
+    pub fn covering_tiles(&self, zoom: u8) -> Vec<(u32, u32)> {
+        assert!(zoom <= 32, "zoom must be at most 32");
+        let [west, south, east, north] = match self.bbox_calculated_with(BboxDimension::Force2D) {
+            Some(bbox) => [bbox[0], bbox[1], bbox[2], bbox[3]],
+            None => return Vec::new(),
+        };
+        let tiles = (1u64 << zoom) as f64;
+        let max_index = tiles - 1.0;
+        let max_latitude = max_latitude();
+        let tile_x = |longitude: f64| {
+            ((longitude + 180.0) / 360.0 * tiles)
+                .floor()
+                .clamp(0.0, max_index) as u32
+        };
+        let tile_y = |latitude: f64| {
+            let latitude = latitude.clamp(-max_latitude, max_latitude).to_radians();
+            let y = (1.0 - (FRAC_PI_4 + latitude / 2.0).tan().ln() / PI) / 2.0;
+            (y * tiles).floor().clamp(0.0, max_index) as u32
+        };
+        let (min_x, max_x) = (tile_x(west), tile_x(east));
+        (tile_y(north)..=tile_y(south))
+            .flat_map(|y| (min_x..=max_x).map(move |x| (x, y)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+
+    fn assert_close(actual: &Value, expected: &[[f64; 2]]) {
+        let positions = match actual {
+            Value::LineString(positions) => positions,
+            _ => unreachable!(),
+        };
+        assert_eq!(positions.len(), expected.len());
+        for (position, expected) in positions.iter().zip(expected) {
+            assert!((position[0] - expected[0]).abs() < 1e-6, "{:?}", position);
+            assert!((position[1] - expected[1]).abs() < 1e-6, "{:?}", position);
+        }
+    }
+
+    #[test]
+    fn web_mercator_round_trip() {
+        let original = Value::LineString(vec![
+            vec![-180.0, -85.0],
+            vec![0.0, 0.0, 12.0],
+            vec![13.404954, 52.520008],
+        ]);
+        let mut value = original.clone();
+
+        value.to_web_mercator();
+        assert_close(
+            &value,
+            &[
+                [-20037508.342789244, -19971868.88040857],
+                [0.0, 0.0],
+                [1492232.6533872557, 6894701.264846518],
+            ],
+        );
+        if let Value::LineString(positions) = &value {
+            assert_eq!(positions[1][2], 12.0);
+        }
+
+        value.to_wgs84();
+        assert_close(
+            &value,
+            &[[-180.0, -85.0], [0.0, 0.0], [13.404954, 52.520008]],
+        );
+    }
+
+    #[test]
+    fn covering_tiles() {
+        // Berlin's Brandenburg Gate at zoom 14 is tile 8800, 5373
+        let gate = Value::Point(vec![13.377704, 52.516275]);
+        assert_eq!(gate.covering_tiles(14), vec![(8800, 5373)]);
+
+        // The whole world, including the poles and the antimeridian's eastern edge
+        let world = Value::LineString(vec![vec![-180.0, -90.0], vec![180.0, 90.0]]);
+        assert_eq!(world.covering_tiles(1).len(), 4);
+        let corner = Value::Point(vec![180.0, -90.0]);
+        assert_eq!(corner.covering_tiles(32), vec![(u32::MAX, u32::MAX)]);
+
+        assert!(Value::GeometryCollection(vec![])
+            .covering_tiles(3)
+            .is_empty());
+    }
+
+    #[test]
+    fn poles_are_clamped() {
+        let mut value = Value::LineString(vec![vec![0.0, 90.0], vec![0.0, -90.0]]);
+        value.to_web_mercator();
+        assert_close(
+            &value,
+            &[[0.0, 20037508.342789244], [0.0, -20037508.342789244]],
+        );
+    }
+}
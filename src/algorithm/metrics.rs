@@ -0,0 +1,177 @@
+use super::area::ring_signed_area;
+use super::rotated_rect::convex_hull;
+use super::segments;
+use crate::{PolygonType, Position, Value};
+
+/// Size and shape measurements of the polygons of a `Value`, as computed by
+/// [`Value::shape_metrics`](enum.Value.html#method.shape_metrics)
+///
+/// Measurements are planar, in the units of the coordinates.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ShapeMetrics {
+    /// The area of the exterior rings, less that of the holes
+    pub area: f64,
+    /// The total length of every ring, including holes
+    pub perimeter: f64,
+    /// The area of the polygons' bounding box
+    pub bbox_area: f64,
+    /// The area of the convex hull of the polygons
+    pub hull_area: f64,
+    /// The Polsby–Popper score, `4π · area / perimeter²`: 1 for a circle, approaching 0 for
+    /// long or convoluted shapes. `None` if the perimeter is 0.
+    pub compactness: Option<f64>,
+}
+
+impl Value {
+    /// Measure the `Polygon`s and `MultiPolygon`s of this `Value`, for classifying shapes by
+    /// size and compactness
+    ///
+    /// Members of `GeometryCollection`s are measured together. Other geometries have no area,
+    /// so are ignored, and a value without polygons gives `ShapeMetrics::default()`: zeros, and
+    /// no compactness. Ring orientation doesn't matter; the first ring of each polygon is
+    /// taken as its exterior. Positions with fewer than two coordinates are skipped.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let square = Value::Polygon(vec![vec![
+    ///     vec![0., 0.],
+    ///     vec![2., 0.],
+    ///     vec![2., 2.],
+    ///     vec![0., 2.],
+    ///     vec![0., 0.],
+    /// ]]);
+    /// let metrics = square.shape_metrics();
+    /// assert_eq!(metrics.area, 4.);
+    /// assert_eq!(metrics.perimeter, 8.);
+    /// assert_eq!(metrics.hull_area, 4.);
+    /// assert_eq!(metrics.compactness, Some(std::f64::consts::PI / 4.));
+    /// ```
+    pub fn shape_metrics(&self) -> ShapeMetrics {
+        let mut polygons = Vec::new();
+        collect_polygons(self, &mut polygons);
+
+        let mut metrics = ShapeMetrics::default();
+        let mut points = Vec::new();
+        for rings in polygons {
+            for (i, ring) in rings.iter().enumerate() {
+                let area = ring_signed_area(ring).abs();
+                metrics.area += if i == 0 { area } else { -area };
+                let ring: Vec<&Position> =
+                    ring.iter().filter(|position| position.len() >= 2).collect();
+                metrics.perimeter += segments(&ring)
+                    .map(|(a, b)| (b[0] - a[0]).hypot(b[1] - a[1]))
+                    .sum::<f64>();
+                points.extend(
+                    ring.iter()
+                        .map(|position| [position[0], position[1]])
+                        .filter(|point| point.iter().all(|c| c.is_finite())),
+                );
+            }
+        }
+        if points.is_empty() {
+            return metrics;
+        }
+
+        let (mut min, mut max) = (points[0], points[0]);
+        for point in &points {
+            min = [min[0].min(point[0]), min[1].min(point[1])];
+            max = [max[0].max(point[0]), max[1].max(point[1])];
+        }
+        metrics.bbox_area = (max[0] - min[0]) * (max[1] - min[1]);
+
+        let hull: Vec<_> = convex_hull(points)
+            .iter()
+            .map(|point| point.to_vec())
+            .collect();
+        metrics.hull_area = ring_signed_area(&hull).abs();
+
+        if metrics.perimeter > 0. {
+            metrics.compactness =
+                Some(4. * std::f64::consts::PI * metrics.area / metrics.perimeter.powi(2));
+        }
+        metrics
+    }
+}
+
+fn collect_polygons<'a>(value: &'a Value, polygons: &mut Vec<&'a PolygonType>) {
+    match value {
+        Value::Polygon(rings) => polygons.push(rings),
+        Value::MultiPolygon(members) => polygons.extend(members),
+        Value::GeometryCollection(geometries) => geometries
+            .iter()
+            .for_each(|geometry| collect_polygons(&geometry.value, polygons)),
+        Value::Point(_)
+        | Value::MultiPoint(_)
+        | Value::LineString(_)
+        | Value::MultiLineString(_)
+        | Value::Unknown(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShapeMetrics;
+    use crate::{Geometry, Value};
+
+    #[test]
+    fn holes_and_concavity() {
+        // A 4 × 4 square missing a 2 × 2 corner, with a 1 × 1 hole
+        let value = Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![100., 100.])),
+            Geometry::new(Value::MultiPolygon(vec![vec![
+                vec![
+                    vec![0., 0.],
+                    vec![4., 0.],
+                    vec![4., 2.],
+                    vec![2., 2.],
+                    vec![2., 4.],
+                    vec![0., 4.],
+                    vec![0., 0.],
+                ],
+                vec![
+                    vec![0.5, 0.5],
+                    vec![0.5, 1.5],
+                    vec![1.5, 1.5],
+                    vec![1.5, 0.5],
+                    vec![0.5, 0.5],
+                ],
+            ]])),
+        ]);
+        let metrics = value.shape_metrics();
+        assert_eq!(metrics.area, 11.);
+        assert_eq!(metrics.perimeter, 20.);
+        assert_eq!(metrics.bbox_area, 16.);
+        assert_eq!(metrics.hull_area, 14.);
+        let compactness = metrics.compactness.unwrap();
+        assert!((compactness - 4. * std::f64::consts::PI * 11. / 400.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn non_areal_geometries() {
+        let line = Value::LineString(vec![vec![0., 0.], vec![1., 1.]]);
+        assert_eq!(line.shape_metrics(), ShapeMetrics::default());
+        assert_eq!(
+            Value::Polygon(vec![]).shape_metrics(),
+            ShapeMetrics::default()
+        );
+    }
+
+    #[test]
+    fn short_positions_are_skipped() {
+        let square = Value::Polygon(vec![vec![
+            vec![0., 0.],
+            vec![2., 0.],
+            vec![1.],
+            vec![2., 2.],
+            vec![0., 2.],
+            vec![0., 0.],
+        ]]);
+        let metrics = square.shape_metrics();
+        assert_eq!((metrics.area, metrics.perimeter), (4., 8.));
+        assert_eq!((metrics.bbox_area, metrics.hull_area), (4., 4.));
+
+        let short = Value::Polygon(vec![vec![vec![0.], vec![1.], vec![0.]]]);
+        assert_eq!(short.shape_metrics(), ShapeMetrics::default());
+    }
+}
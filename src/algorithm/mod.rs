@@ -0,0 +1,195 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Geometric operations on `Value`s.
+//!
+//! All operations treat the first two components of each `Position` as planar `x` / `y`
+//! coordinates unless documented otherwise.
+
+use crate::{Position, Value};
+
+mod affine;
+pub(crate) mod area;
+mod boundary;
+mod clamp;
+mod clip;
+mod closest_pair;
+mod collinear;
+pub use clamp::LongitudeMode;
+mod dimensions;
+mod distance;
+mod geodesic;
+pub use geodesic::{geodesic_bearing, geodesic_destination, geodesic_midpoint};
+mod holes;
+mod label;
+mod make_valid;
+pub use make_valid::RepairReport;
+mod map_coords;
+mod mercator;
+mod metrics;
+pub use metrics::ShapeMetrics;
+mod nearest;
+mod normalize;
+mod offset;
+mod precision;
+mod reverse;
+mod rotated_rect;
+mod segmentize;
+mod self_intersection;
+mod simplify;
+pub use simplify::SimplifyOptions;
+mod swap;
+mod topology_eq;
+mod triangulate;
+
+impl Value {
+    /// Iterate over every `Position` in this `Value`, recursing into `GeometryCollection`s
+    pub(crate) fn coords_iter(&self) -> Box<dyn Iterator<Item = &Position> + '_> {
+        match self {
+            Value::Point(position) => Box::new(std::iter::once(position)),
+            Value::MultiPoint(positions) | Value::LineString(positions) => {
+                Box::new(positions.iter())
+            }
+            Value::MultiLineString(lines) | Value::Polygon(lines) => {
+                Box::new(lines.iter().flatten())
+            }
+            Value::MultiPolygon(polygons) => Box::new(polygons.iter().flatten().flatten()),
+            Value::GeometryCollection(geometries) => Box::new(
+                geometries
+                    .iter()
+                    .flat_map(|geometry| geometry.value.coords_iter()),
+            ),
+            Value::Unknown(_) => Box::new(std::iter::empty()),
+        }
+    }
+
+    /// Iterate mutably over every `Position` in this `Value`, recursing into
+    /// `GeometryCollection`s
+    pub(crate) fn coords_iter_mut(&mut self) -> Box<dyn Iterator<Item = &mut Position> + '_> {
+        match self {
+            Value::Point(position) => Box::new(std::iter::once(position)),
+            Value::MultiPoint(positions) | Value::LineString(positions) => {
+                Box::new(positions.iter_mut())
+            }
+            Value::MultiLineString(lines) | Value::Polygon(lines) => {
+                Box::new(lines.iter_mut().flatten())
+            }
+            Value::MultiPolygon(polygons) => Box::new(polygons.iter_mut().flatten().flatten()),
+            Value::GeometryCollection(geometries) => Box::new(
+                geometries
+                    .iter_mut()
+                    .flat_map(|geometry| geometry.value.coords_iter_mut()),
+            ),
+            Value::Unknown(_) => Box::new(std::iter::empty()),
+        }
+    }
+
+    /// Iterate over copies of the `x` and `y` coordinates of every position, in order,
+    /// recursing into `GeometryCollection`s
+    ///
+    /// Positions with fewer than two coordinates are skipped.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let line = Value::LineString(vec![vec![1., 2., 10.], vec![3., 4., 20.]]);
+    /// let length: f64 = line
+    ///     .points_iter()
+    ///     .zip(line.points_iter().skip(1))
+    ///     .map(|((x1, y1), (x2, y2))| (x2 - x1).hypot(y2 - y1))
+    ///     .sum();
+    /// assert_eq!(length, 8f64.sqrt());
+    /// ```
+    pub fn points_iter(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.coords_iter()
+            .filter_map(|position| match position[..] {
+                [x, y, ..] => Some((x, y)),
+                _ => None,
+            })
+    }
+
+    /// Iterate over the linear parts of this `Value`: every line and ring, and every point as a
+    /// single-position part. Recurses into `GeometryCollection`s.
+    pub(crate) fn parts_iter(&self) -> Box<dyn Iterator<Item = &[Position]> + '_> {
+        match self {
+            Value::Point(position) => Box::new(std::iter::once(std::slice::from_ref(position))),
+            Value::MultiPoint(positions) => Box::new(positions.iter().map(std::slice::from_ref)),
+            Value::LineString(line) => Box::new(std::iter::once(&line[..])),
+            Value::MultiLineString(lines) | Value::Polygon(lines) => {
+                Box::new(lines.iter().map(|line| &line[..]))
+            }
+            Value::MultiPolygon(polygons) => {
+                Box::new(polygons.iter().flatten().map(|ring| &ring[..]))
+            }
+            Value::GeometryCollection(geometries) => Box::new(
+                geometries
+                    .iter()
+                    .flat_map(|geometry| geometry.value.parts_iter()),
+            ),
+            Value::Unknown(_) => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+/// Iterate over the consecutive segments of a line or ring
+pub(crate) fn segments<P>(line: &[P]) -> impl Iterator<Item = (&P, &P)> {
+    line.windows(2).map(|w| (&w[0], &w[1]))
+}
+
+/// The point of the segment `a`-`b` closest to `p`
+pub(crate) fn closest_point_on_segment(p: &[f64], a: &[f64], b: &[f64]) -> [f64; 2] {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let length_squared = dx * dx + dy * dy;
+    let t = if length_squared == 0. {
+        0.
+    } else {
+        (((p[0] - a[0]) * dx + (p[1] - a[1]) * dy) / length_squared).clamp(0., 1.)
+    };
+    [a[0] + t * dx, a[1] + t * dy]
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `c`.
+///
+/// Positive if the points are in counter-clockwise order, negative if clockwise,
+/// and zero if they are collinear.
+pub(crate) fn orientation(a: &[f64], b: &[f64], c: &[f64]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Whether `p`, known to be collinear with `a` and `b`, lies within their bounding box
+pub(crate) fn on_segment(a: &[f64], b: &[f64], p: &[f64]) -> bool {
+    p[0] >= a[0].min(b[0])
+        && p[0] <= a[0].max(b[0])
+        && p[1] >= a[1].min(b[1])
+        && p[1] <= a[1].max(b[1])
+}
+
+/// Whether the segments `a`-`b` and `c`-`d` share at least one point
+pub(crate) fn segments_intersect(a: &[f64], b: &[f64], c: &[f64], d: &[f64]) -> bool {
+    let o1 = orientation(a, b, c);
+    let o2 = orientation(a, b, d);
+    let o3 = orientation(c, d, a);
+    let o4 = orientation(c, d, b);
+
+    if ((o1 > 0. && o2 < 0.) || (o1 < 0. && o2 > 0.))
+        && ((o3 > 0. && o4 < 0.) || (o3 < 0. && o4 > 0.))
+    {
+        return true;
+    }
+
+    (o1 == 0. && on_segment(a, b, c))
+        || (o2 == 0. && on_segment(a, b, d))
+        || (o3 == 0. && on_segment(c, d, a))
+        || (o4 == 0. && on_segment(c, d, b))
+}
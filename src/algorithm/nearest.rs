@@ -0,0 +1,217 @@
+use super::{closest_point_on_segment, segments};
+use crate::{CoordPath, Position, Value};
+
+impl Value {
+    /// The point of this geometry's boundary closest to `x`, `y`, for snapping a location onto
+    /// it. Distances are planar.
+    ///
+    /// Lines and polygon rings are projected onto, so the result may lie between vertices. For
+    /// a `MultiPoint` it is the nearest of the points, and for a `Point` the point itself.
+    /// `GeometryCollection`s return the nearest point of any member. Positions with fewer than
+    /// two coordinates are skipped, and `None` is returned if there are no others.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let line = Value::LineString(vec![vec![0., 0.], vec![10., 0.]]);
+    /// assert_eq!(line.nearest_point(3., 4.), Some((3., 0.)));
+    /// ```
+    pub fn nearest_point(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let query = [x, y];
+        let mut nearest = None;
+        let mut best = f64::INFINITY;
+        let mut consider = |candidate: [f64; 2]| {
+            let distance = (candidate[0] - x).powi(2) + (candidate[1] - y).powi(2);
+            if nearest.is_none() || distance < best {
+                nearest = Some(candidate);
+                best = distance;
+            }
+        };
+        for part in self.parts_iter() {
+            let part: Vec<&Position> = part.iter().filter(|position| position.len() >= 2).collect();
+            match part[..] {
+                [] => {}
+                [position] => consider([position[0], position[1]]),
+                _ => segments(&part)
+                    .for_each(|(a, b)| consider(closest_point_on_segment(&query, a, b))),
+            }
+        }
+        nearest.map(|[x, y]| (x, y))
+    }
+
+    /// The stored vertex closest to `x`, `y`, along with its path within this geometry, for
+    /// snapping to and moving existing vertices. Distances are planar.
+    ///
+    /// Unlike [`nearest_point`](#method.nearest_point), only positions themselves are
+    /// considered, never points between them. `GeometryCollection`s are searched recursively.
+    /// The first of several equally close vertices is returned, and `None` if there are no
+    /// positions. See [`position_at_mut`](#method.position_at_mut) to edit the vertex.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let polygons = Value::MultiPolygon(vec![
+    ///     vec![vec![vec![0., 0.], vec![1., 0.], vec![0., 1.], vec![0., 0.]]],
+    ///     vec![vec![vec![5., 5.], vec![6., 5.], vec![5., 6.], vec![5., 5.]]],
+    /// ]);
+    /// assert_eq!(
+    ///     polygons.closest_vertex(5.9, 5.2),
+    ///     Some((vec![1, 0, 1], [6., 5.]))
+    /// );
+    /// ```
+    pub fn closest_vertex(&self, x: f64, y: f64) -> Option<(CoordPath, [f64; 2])> {
+        let mut closest = None;
+        let mut best = f64::INFINITY;
+        visit_positions(self, &mut Vec::new(), &mut |path, position| {
+            if position.len() < 2 {
+                return;
+            }
+            let distance = (position[0] - x).powi(2) + (position[1] - y).powi(2);
+            if closest.is_none() || distance < best {
+                closest = Some((path.clone(), [position[0], position[1]]));
+                best = distance;
+            }
+        });
+        closest
+    }
+
+    /// The position at `path`, as returned by [`closest_vertex`](#method.closest_vertex), or
+    /// `None` if there is no position there
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut line = Value::LineString(vec![vec![0., 0.], vec![10., 0.]]);
+    /// let (path, _) = line.closest_vertex(9., 1.).unwrap();
+    /// *line.position_at_mut(&path).unwrap() = vec![9., 1.];
+    /// assert_eq!(line, Value::LineString(vec![vec![0., 0.], vec![9., 1.]]));
+    /// ```
+    pub fn position_at_mut(&mut self, path: &[usize]) -> Option<&mut Position> {
+        match (self, path) {
+            (Value::Point(position), []) => Some(position),
+            (Value::MultiPoint(positions), [i]) | (Value::LineString(positions), [i]) => {
+                positions.get_mut(*i)
+            }
+            (Value::MultiLineString(lines), [i, j]) | (Value::Polygon(lines), [i, j]) => {
+                lines.get_mut(*i)?.get_mut(*j)
+            }
+            (Value::MultiPolygon(polygons), [i, j, k]) => {
+                polygons.get_mut(*i)?.get_mut(*j)?.get_mut(*k)
+            }
+            (Value::GeometryCollection(geometries), [i, rest @ ..]) => {
+                geometries.get_mut(*i)?.value.position_at_mut(rest)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Call `f` with every position of `value` and its path, which is appended to `path`
+fn visit_positions(value: &Value, path: &mut CoordPath, f: &mut dyn FnMut(&CoordPath, &Position)) {
+    fn visit_line(
+        line: &[Position],
+        path: &mut CoordPath,
+        f: &mut dyn FnMut(&CoordPath, &Position),
+    ) {
+        for (i, position) in line.iter().enumerate() {
+            path.push(i);
+            f(path, position);
+            path.pop();
+        }
+    }
+
+    match value {
+        Value::Point(position) => f(path, position),
+        Value::MultiPoint(positions) | Value::LineString(positions) => {
+            visit_line(positions, path, f)
+        }
+        Value::MultiLineString(lines) | Value::Polygon(lines) => {
+            for (i, line) in lines.iter().enumerate() {
+                path.push(i);
+                visit_line(line, path, f);
+                path.pop();
+            }
+        }
+        Value::MultiPolygon(polygons) => {
+            for (i, rings) in polygons.iter().enumerate() {
+                path.push(i);
+                for (j, ring) in rings.iter().enumerate() {
+                    path.push(j);
+                    visit_line(ring, path, f);
+                    path.pop();
+                }
+                path.pop();
+            }
+        }
+        Value::GeometryCollection(geometries) => {
+            for (i, geometry) in geometries.iter().enumerate() {
+                path.push(i);
+                visit_positions(&geometry.value, path, f);
+                path.pop();
+            }
+        }
+        Value::Unknown(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, Value};
+
+    #[test]
+    fn nearest_point() {
+        let square = vec![
+            vec![0., 0.],
+            vec![4., 0.],
+            vec![4., 4.],
+            vec![0., 4.],
+            vec![0., 0.],
+        ];
+        let polygon = Value::Polygon(vec![square]);
+        // Inside the polygon snaps to the nearest edge, not the interior
+        assert_eq!(polygon.nearest_point(3., 2.), Some((4., 2.)));
+        assert_eq!(polygon.nearest_point(-1., -1.), Some((0., 0.)));
+
+        let points = Value::MultiPoint(vec![vec![0., 0.], vec![5., 5.]]);
+        assert_eq!(points.nearest_point(4., 4.), Some((5., 5.)));
+
+        let collection = Value::GeometryCollection(vec![
+            Geometry::new(polygon),
+            Geometry::new(Value::Point(vec![10., 10.])),
+        ]);
+        assert_eq!(collection.nearest_point(9., 9.), Some((10., 10.)));
+        assert_eq!(
+            Value::GeometryCollection(vec![]).nearest_point(0., 0.),
+            None
+        );
+
+        let line = Value::LineString(vec![vec![0., 0.], vec![2.], vec![4., 0.]]);
+        assert_eq!(line.nearest_point(2., 1.), Some((2., 0.)));
+        assert_eq!(Value::Point(vec![1.]).nearest_point(0., 0.), None);
+    }
+
+    #[test]
+    fn closest_vertex_paths() {
+        let value = Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![10., 10.])),
+            Geometry::new(Value::Polygon(vec![
+                vec![vec![0., 0.], vec![4., 0.], vec![4., 4.], vec![0., 0.]],
+                vec![vec![1., 1.], vec![2., 1.], vec![2., 2.], vec![1., 1.]],
+            ])),
+        ]);
+        // The nearest edge is closer, but only vertices count
+        assert_eq!(
+            value.closest_vertex(2.1, 0.2),
+            Some((vec![1, 1, 1], [2., 1.]))
+        );
+        assert_eq!(value.closest_vertex(9., 9.), Some((vec![0], [10., 10.])));
+
+        let mut value = value;
+        assert_eq!(value.position_at_mut(&[1, 1, 1]), Some(&mut vec![2., 1.]));
+        assert_eq!(value.position_at_mut(&[1, 2, 0]), None);
+        assert_eq!(value.position_at_mut(&[0, 0]), None);
+        assert_eq!(value.position_at_mut(&[2]), None);
+
+        assert_eq!(Value::MultiPoint(vec![]).closest_vertex(0., 0.), None);
+    }
+}
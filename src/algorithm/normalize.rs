@@ -0,0 +1,163 @@
+use super::collinear::{remove_from_line, remove_from_ring};
+use crate::{Position, Value};
+
+impl Value {
+    /// Prepare this value for overlay operations by snapping it to a grid and removing the
+    /// vertices that snapping makes redundant, in place.
+    ///
+    /// The operations run in this order, on every line and ring:
+    ///
+    /// 1. The `x` and `y` of every position are rounded to the nearest multiple of
+    ///    `grid_size`. Any further coordinates are left as they are.
+    /// 2. Consecutive positions which now have the same `x` and `y` are merged, keeping the
+    ///    first. For rings this includes the closing position, which is set aside first.
+    /// 3. Vertices exactly collinear with their neighbours are removed. Lines keep their
+    ///    endpoints; rings are treated as cyclic.
+    /// 4. Rings are closed again with a copy of their first position.
+    ///
+    /// Returns `true` if any line was left with fewer than 2 positions, or any ring with fewer
+    /// than 3 distinct vertices, so that the caller can drop such geometries. These are kept,
+    /// as closed as they can be, rather than removed. `Point`s and `MultiPoint`s are only
+    /// snapped.
+    ///
+    /// # Panics
+    ///
+    /// If `grid_size` is not a positive, finite number.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut polygon = Value::Polygon(vec![vec![
+    ///     vec![0.1, 0.0],
+    ///     vec![1.0, 0.1],
+    ///     vec![2.1, -0.1],
+    ///     vec![2.0, 1.9],
+    ///     vec![2.1, 2.0],
+    /// ]]);
+    /// let degenerate = polygon.normalize_topology(1.0);
+    /// assert!(!degenerate);
+    /// assert_eq!(
+    ///     polygon,
+    ///     Value::Polygon(vec![vec![
+    ///         vec![0.0, 0.0],
+    ///         vec![2.0, 0.0],
+    ///         vec![2.0, 2.0],
+    ///         vec![0.0, 0.0],
+    ///     ]])
+    /// );
+    /// ```
+    pub fn normalize_topology(&mut self, grid_size: f64) -> bool {
+        assert!(
+            grid_size > 0. && grid_size.is_finite(),
+            "grid_size must be positive and finite"
+        );
+        for position in self.coords_iter_mut() {
+            for coordinate in position.iter_mut().take(2) {
+                *coordinate = (*coordinate / grid_size).round() * grid_size;
+            }
+        }
+        self.remove_snapped_vertices()
+    }
+
+    /// Steps 2 to 4 of `normalize_topology`, returning whether anything is degenerate
+    fn remove_snapped_vertices(&mut self) -> bool {
+        let mut degenerate = false;
+        match self {
+            Value::Point(_) | Value::MultiPoint(_) | Value::Unknown(_) => {}
+            Value::LineString(line) => degenerate = normalize_line(line),
+            Value::MultiLineString(lines) => {
+                for line in lines {
+                    degenerate |= normalize_line(line);
+                }
+            }
+            Value::Polygon(rings) => {
+                for ring in rings {
+                    degenerate |= normalize_ring(ring);
+                }
+            }
+            Value::MultiPolygon(polygons) => {
+                for ring in polygons.iter_mut().flatten() {
+                    degenerate |= normalize_ring(ring);
+                }
+            }
+            Value::GeometryCollection(geometries) => {
+                for geometry in geometries {
+                    degenerate |= geometry.value.remove_snapped_vertices();
+                }
+            }
+        }
+        degenerate
+    }
+}
+
+fn same_xy(a: &Position, b: &Position) -> bool {
+    a.iter().take(2).eq(b.iter().take(2))
+}
+
+fn normalize_line(line: &mut Vec<Position>) -> bool {
+    line.dedup_by(|b, a| same_xy(a, b));
+    remove_from_line(line, |area| area == 0.);
+    line.len() < 2
+}
+
+fn normalize_ring(ring: &mut Vec<Position>) -> bool {
+    if ring.is_empty() {
+        return true;
+    }
+    if ring.len() > 1 && same_xy(&ring[0], &ring[ring.len() - 1]) {
+        ring.pop();
+    }
+    ring.dedup_by(|b, a| same_xy(a, b));
+    if ring.len() > 1 && same_xy(&ring[0], &ring[ring.len() - 1]) {
+        ring.pop();
+    }
+    remove_from_ring(ring, 2, |area| area == 0.);
+    ring.push(ring[0].clone());
+    ring.len() < 4
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, Value};
+
+    #[test]
+    fn collapsed_parts_are_reported() {
+        let mut value = Value::GeometryCollection(vec![
+            Geometry::new(Value::MultiLineString(vec![
+                vec![vec![0., 0.], vec![0.2, 0.1, 5.], vec![3., 0.]],
+                vec![vec![0., 0.], vec![0.3, 0.3]],
+            ])),
+            Geometry::new(Value::Point(vec![0.4, 0.6])),
+        ]);
+        assert!(value.normalize_topology(1.));
+        assert_eq!(
+            value,
+            Value::GeometryCollection(vec![
+                Geometry::new(Value::MultiLineString(vec![
+                    vec![vec![0., 0.], vec![3., 0.]],
+                    vec![vec![0., 0.]],
+                ])),
+                Geometry::new(Value::Point(vec![0., 1.])),
+            ])
+        );
+
+        // A sliver thinner than the grid collapses onto a line
+        let mut sliver = Value::MultiPolygon(vec![vec![vec![
+            vec![0., 0.],
+            vec![10., 0.],
+            vec![5., 0.2],
+            vec![0., 0.],
+        ]]]);
+        assert!(sliver.normalize_topology(1.));
+        assert_eq!(
+            sliver,
+            Value::MultiPolygon(vec![vec![vec![vec![10., 0.], vec![5., 0.], vec![10., 0.]]]])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "grid_size")]
+    fn zero_grid_size() {
+        Value::Point(vec![0., 0.]).normalize_topology(0.);
+    }
+}
@@ -0,0 +1,196 @@
+use std::f64::consts::FRAC_PI_8;
+
+use crate::{Position, Value};
+
+/// The longest a mitred corner may reach from its vertex, as a multiple of the offset distance,
+/// before it is rounded instead
+const MITER_LIMIT: f64 = 2.;
+
+impl Value {
+    /// Offset a `LineString`, or each line of a `MultiLineString`, by `distance` to one side:
+    /// to the left of the direction of travel for a positive distance, and to the right for a
+    /// negative one.
+    ///
+    /// Each segment is moved along its normal. Where the offset side is the outside of a turn the
+    /// corner is mitred, or rounded if the miter would reach more than twice `distance` from the
+    /// vertex; on the inside of a turn the offset segments meet where they cross. The ends are
+    /// left square, and a closed line isn't joined at its start.
+    ///
+    /// Tight turns on the inside, with segments shorter than `distance`, loop back on themselves;
+    /// those self-intersections aren't removed. Repeated positions are skipped, and positions
+    /// made at a vertex keep any coordinates after its `x` and `y`.
+    ///
+    /// Lines without two distinct positions can't be offset, and are left out of a
+    /// `MultiLineString`. Returns `None` if no line can be, and for other variants.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let road = Value::LineString(vec![vec![0., 0.], vec![10., 0.], vec![10., 10.]]);
+    /// assert_eq!(
+    ///     road.offset_curve(-1.),
+    ///     Some(Value::LineString(vec![vec![0., -1.], vec![11., -1.], vec![11., 10.]]))
+    /// );
+    /// assert_eq!(
+    ///     road.offset_curve(1.),
+    ///     Some(Value::LineString(vec![vec![0., 1.], vec![9., 1.], vec![9., 10.]]))
+    /// );
+    /// ```
+    pub fn offset_curve(&self, distance: f64) -> Option<Value> {
+        match self {
+            Value::LineString(line) => offset_line(line, distance).map(Value::LineString),
+            Value::MultiLineString(lines) => {
+                let lines: Vec<_> = lines
+                    .iter()
+                    .filter_map(|line| offset_line(line, distance))
+                    .collect();
+                Some(lines)
+                    .filter(|lines| !lines.is_empty())
+                    .map(Value::MultiLineString)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn offset_line(line: &[Position], distance: f64) -> Option<Vec<Position>> {
+    let mut vertices: Vec<&Position> = Vec::with_capacity(line.len());
+    for position in line.iter().filter(|position| position.len() >= 2) {
+        if !matches!(vertices.last(), Some(last) if last[..2] == position[..2]) {
+            vertices.push(position);
+        }
+    }
+    if vertices.len() < 2 {
+        return None;
+    }
+
+    // The unit direction of the segment from `a` to `b`
+    let direction = |a: &Position, b: &Position| {
+        let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+        let length = dx.hypot(dy);
+        [dx / length, dy / length]
+    };
+    let at = |vertex: &Position, offset: [f64; 2]| {
+        let mut position = vec![vertex[0] + offset[0], vertex[1] + offset[1]];
+        position.extend_from_slice(&vertex[2..]);
+        position
+    };
+
+    let first = direction(vertices[0], vertices[1]);
+    let mut offset = vec![at(vertices[0], [-first[1] * distance, first[0] * distance])];
+    for window in vertices.windows(3) {
+        let (vertex, incoming, outgoing) = (
+            window[1],
+            direction(window[0], window[1]),
+            direction(window[1], window[2]),
+        );
+        let (n1, n2) = ([-incoming[1], incoming[0]], [-outgoing[1], outgoing[0]]);
+        let cos = n1[0] * n2[0] + n1[1] * n2[1];
+        let turn = incoming[0] * outgoing[1] - incoming[1] * outgoing[0];
+        let outside = turn * distance < 0. || (turn == 0. && cos < 0.);
+
+        if outside && cos < 2. / (MITER_LIMIT * MITER_LIMIT) - 1. {
+            // Round the corner, sweeping the normal around the outside of the vertex
+            let sweep = cos.clamp(-1., 1.).acos();
+            let steps = (sweep / FRAC_PI_8).ceil().max(1.);
+            let sign = -distance.signum();
+            for step in 0..=steps as usize {
+                let (sin, cos) = (sign * sweep * step as f64 / steps).sin_cos();
+                let normal = [n1[0] * cos - n1[1] * sin, n1[0] * sin + n1[1] * cos];
+                offset.push(at(vertex, [normal[0] * distance, normal[1] * distance]));
+            }
+        } else if 1. + cos > f64::EPSILON {
+            // Where the two offset segments' lines cross
+            let scale = distance / (1. + cos);
+            offset.push(at(
+                vertex,
+                [(n1[0] + n2[0]) * scale, (n1[1] + n2[1]) * scale],
+            ));
+        } else {
+            // Doubling back on the inside, so the offset lines never cross
+            offset.push(at(vertex, [n1[0] * distance, n1[1] * distance]));
+            offset.push(at(vertex, [n2[0] * distance, n2[1] * distance]));
+        }
+    }
+    let n = vertices.len();
+    let last = direction(vertices[n - 2], vertices[n - 1]);
+    offset.push(at(
+        vertices[n - 1],
+        [-last[1] * distance, last[0] * distance],
+    ));
+    Some(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+
+    fn offset(line: Vec<Vec<f64>>, distance: f64) -> Vec<Vec<f64>> {
+        match Value::LineString(line).offset_curve(distance) {
+            Some(Value::LineString(line)) => line,
+            other => panic!("expected a LineString, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn straight_lines() {
+        let line = vec![
+            vec![0., 0., 5.],
+            vec![5., 0., 6.],
+            vec![5., 0., 6.],
+            vec![10., 0., 7.],
+        ];
+        assert_eq!(
+            offset(line.clone(), 2.),
+            vec![vec![0., 2., 5.], vec![5., 2., 6.], vec![10., 2., 7.]]
+        );
+        assert_eq!(
+            offset(line, -2.),
+            vec![vec![0., -2., 5.], vec![5., -2., 6.], vec![10., -2., 7.]]
+        );
+    }
+
+    #[test]
+    fn sharp_outside_turns_are_rounded() {
+        // Nearly doubling back to the left, with the offset on the right, outside the turn
+        let line = vec![vec![0., 0.], vec![10., 0.], vec![0., 1.]];
+        let offset = offset(line, -1.);
+        let arc = &offset[1..offset.len() - 1];
+        assert!(arc.len() > 4);
+        for position in arc {
+            let radius = (position[0] - 10.).hypot(position[1]);
+            assert!((radius - 1.).abs() < 1e-12, "{:?}", position);
+        }
+        // The arc starts and ends on the offset segments, and bulges past the vertex
+        assert_eq!(arc[0], vec![10., -1.]);
+        assert!(arc.iter().any(|position| position[0] > 10.9));
+
+        // A complete reversal is rounded through a half turn
+        let reversal = vec![vec![0., 0.], vec![10., 0.], vec![0., 0.]];
+        let offset = super::offset_line(&reversal, 1.).unwrap();
+        assert_eq!(offset.first(), Some(&vec![0., 1.]));
+        assert!(offset
+            .iter()
+            .any(|position| (position[0] - 11.).abs() < 1e-12));
+        let last = offset.last().unwrap();
+        assert!(last[0].abs() < 1e-12 && (last[1] + 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn other_geometries() {
+        let lines = Value::MultiLineString(vec![
+            vec![vec![0., 0.], vec![0., 10.]],
+            vec![vec![1., 1.], vec![1., 1.]],
+        ]);
+        assert_eq!(
+            lines.offset_curve(1.),
+            Some(Value::MultiLineString(vec![vec![
+                vec![-1., 0.],
+                vec![-1., 10.]
+            ]]))
+        );
+        assert_eq!(Value::Point(vec![0., 0.]).offset_curve(1.), None);
+        assert_eq!(Value::LineString(vec![vec![1., 1.]]).offset_curve(1.), None);
+        assert_eq!(Value::MultiLineString(vec![]).offset_curve(1.), None);
+    }
+}
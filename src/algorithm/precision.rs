@@ -0,0 +1,64 @@
+use crate::Value;
+
+/// Digits beyond this are treated as `f64` representation noise
+const MAX_PRECISION: usize = 15;
+
+impl Value {
+    /// Return the largest number of fractional digits used by any coordinate, capped at 15.
+    ///
+    /// Each coordinate is measured by its shortest round-tripping decimal form, so `1.5` has one
+    /// digit and `2.0` has none. Values like `0.1 + 0.2` whose shortest form carries trailing
+    /// floating point noise report the cap rather than 17. Non-finite coordinates are ignored,
+    /// and a `Value` without positions returns `0`.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let line = Value::LineString(vec![vec![1.5, 2.0], vec![-0.125, 10.25]]);
+    /// assert_eq!(line.max_coordinate_precision(), 3);
+    /// ```
+    pub fn max_coordinate_precision(&self) -> usize {
+        self.coords_iter()
+            .flatten()
+            .map(|&coordinate| fractional_digits(coordinate))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+fn fractional_digits(coordinate: f64) -> usize {
+    if !coordinate.is_finite() {
+        return 0;
+    }
+    // `Display` for `f64` prints the shortest round-tripping form and never uses an exponent
+    let formatted = coordinate.to_string();
+    match formatted.find('.') {
+        Some(dot) => (formatted.len() - dot - 1).min(MAX_PRECISION),
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, Value};
+
+    #[test]
+    fn max_coordinate_precision() {
+        assert_eq!(Value::Point(vec![3.0, -7.0]).max_coordinate_precision(), 0);
+        assert_eq!(
+            Value::GeometryCollection(vec![]).max_coordinate_precision(),
+            0
+        );
+        assert_eq!(Value::Point(vec![1e-7, 0.0]).max_coordinate_precision(), 7);
+        assert_eq!(
+            Value::Point(vec![0.1 + 0.2, 0.0]).max_coordinate_precision(),
+            15
+        );
+
+        let collection = Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![1.25, 2.5])),
+            Geometry::new(Value::MultiPoint(vec![vec![f64::NAN, 0.123456]])),
+        ]);
+        assert_eq!(collection.max_coordinate_precision(), 6);
+    }
+}
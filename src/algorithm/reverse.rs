@@ -0,0 +1,62 @@
+use crate::Value;
+
+impl Value {
+    /// Reverse the order of the vertices of every line and ring, in place.
+    ///
+    /// This flips the direction of `LineString`s and the winding of polygon rings. A closed ring
+    /// stays closed, since its first and last positions just swap places. `Point` and
+    /// `MultiPoint` are left untouched, and `GeometryCollection` members are reversed
+    /// recursively. Reversing twice restores the original.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut line = Value::LineString(vec![vec![0., 0.], vec![1., 0.], vec![1., 1.]]);
+    /// line.reverse_coords();
+    /// assert_eq!(
+    ///     line,
+    ///     Value::LineString(vec![vec![1., 1.], vec![1., 0.], vec![0., 0.]])
+    /// );
+    /// ```
+    pub fn reverse_coords(&mut self) {
+        match self {
+            Value::Point(_) | Value::MultiPoint(_) | Value::Unknown(_) => {}
+            Value::LineString(line) => line.reverse(),
+            Value::MultiLineString(lines) | Value::Polygon(lines) => {
+                lines.iter_mut().for_each(|line| line.reverse())
+            }
+            Value::MultiPolygon(polygons) => polygons
+                .iter_mut()
+                .flatten()
+                .for_each(|ring| ring.reverse()),
+            Value::GeometryCollection(geometries) => geometries
+                .iter_mut()
+                .for_each(|geometry| geometry.value.reverse_coords()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+
+    #[test]
+    fn reverse_rings() {
+        let ring = vec![vec![0., 0.], vec![1., 0.], vec![0., 1.], vec![0., 0.]];
+        let original = Value::MultiPolygon(vec![vec![ring.clone()]]);
+        let mut value = original.clone();
+
+        value.reverse_coords();
+        assert_eq!(value.ring_signed_areas(), vec![-0.5]);
+        match &value {
+            Value::MultiPolygon(polygons) => {
+                let ring = &polygons[0][0];
+                assert_eq!(ring.first(), ring.last());
+            }
+            _ => unreachable!(),
+        }
+
+        value.reverse_coords();
+        assert_eq!(value, original);
+    }
+}
@@ -0,0 +1,149 @@
+use super::orientation;
+use crate::Value;
+
+impl Value {
+    /// Computes the smallest-area rectangle, at any rotation, which contains all of the value's
+    /// positions
+    ///
+    /// The rectangle is found with rotating calipers over the convex hull of the positions: one
+    /// of its sides always lies along an edge of the hull. It is returned as a 2D `Polygon` of
+    /// five positions, wound counter-clockwise and closed. Returns `None` if there are fewer than
+    /// three positions not all on one line, or any of them is not finite.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// // A diamond is its own minimum rectangle
+    /// let diamond = Value::MultiPoint(vec![vec![1., 0.], vec![2., 1.], vec![1., 2.], vec![0., 1.]]);
+    /// let rectangle = diamond.minimum_rotated_rectangle().unwrap();
+    /// assert!((rectangle.ring_signed_areas()[0] - 2.).abs() < 1e-12);
+    ///
+    /// assert_eq!(Value::LineString(vec![vec![0., 0.], vec![1., 1.]]).minimum_rotated_rectangle(), None);
+    /// ```
+    pub fn minimum_rotated_rectangle(&self) -> Option<Value> {
+        let mut points = Vec::new();
+        for position in self.coords_iter() {
+            match position.as_slice() {
+                [x, y, ..] if x.is_finite() && y.is_finite() => points.push([*x, *y]),
+                _ => return None,
+            }
+        }
+        let hull = convex_hull(points);
+        if hull.len() < 3 {
+            return None;
+        }
+
+        let mut best: Option<(f64, [[f64; 2]; 4])> = None;
+        for (i, a) in hull.iter().enumerate() {
+            let b = hull[(i + 1) % hull.len()];
+            let length = (b[0] - a[0]).hypot(b[1] - a[1]);
+            // The edge's direction, and the inward normal to it
+            let u = [(b[0] - a[0]) / length, (b[1] - a[1]) / length];
+            let v = [-u[1], u[0]];
+            let (mut min_u, mut max_u, mut max_v) = (0f64, 0f64, 0f64);
+            for p in &hull {
+                let (du, dv) = (p[0] - a[0], p[1] - a[1]);
+                let along = du * u[0] + dv * u[1];
+                min_u = min_u.min(along);
+                max_u = max_u.max(along);
+                max_v = max_v.max(du * v[0] + dv * v[1]);
+            }
+            let area = (max_u - min_u) * max_v;
+            if !matches!(best, Some((best_area, _)) if best_area <= area) {
+                let corner =
+                    |s: f64, t: f64| [a[0] + s * u[0] + t * v[0], a[1] + s * u[1] + t * v[1]];
+                best = Some((
+                    area,
+                    [
+                        corner(min_u, 0.),
+                        corner(max_u, 0.),
+                        corner(max_u, max_v),
+                        corner(min_u, max_v),
+                    ],
+                ));
+            }
+        }
+
+        let (_, corners) = best?;
+        let mut ring: Vec<Vec<f64>> = corners.iter().map(|corner| corner.to_vec()).collect();
+        ring.push(ring[0].clone());
+        Some(Value::Polygon(vec![ring]))
+    }
+}
+
+/// The convex hull of `points`, counter-clockwise and without collinear points, by Andrew's
+/// monotone chain algorithm
+pub(crate) fn convex_hull(mut points: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    // The last point of each chain starts the other
+    let mut hull = half_hull(points.iter());
+    hull.pop();
+    let mut upper = half_hull(points.iter().rev());
+    upper.pop();
+    hull.extend(upper);
+    hull
+}
+
+/// One chain of the monotone chain algorithm, turning left at every point
+fn half_hull<'a>(points: impl Iterator<Item = &'a [f64; 2]>) -> Vec<[f64; 2]> {
+    let mut chain: Vec<[f64; 2]> = Vec::new();
+    for p in points {
+        while chain.len() >= 2
+            && orientation(&chain[chain.len() - 2], &chain[chain.len() - 1], p) <= 0.
+        {
+            chain.pop();
+        }
+        chain.push(*p);
+    }
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+
+    #[test]
+    fn minimum_rotated_rectangle() {
+        // A rectangle 4 long and 1 wide, rotated by 30 degrees, with interior and collinear
+        // points
+        let (c, s) = (30f64.to_radians().cos(), 30f64.to_radians().sin());
+        let rotate = |x: f64, y: f64| vec![x * c - y * s, x * s + y * c];
+        let points = [
+            (0., 0.),
+            (2., 0.),
+            (4., 0.),
+            (4., 1.),
+            (0., 1.),
+            (1., 0.5),
+            (3., 0.2),
+        ];
+        let value = Value::MultiPoint(points.iter().map(|&(x, y)| rotate(x, y)).collect());
+        let rectangle = value.minimum_rotated_rectangle().unwrap();
+        assert!((rectangle.ring_signed_areas()[0] - 4.).abs() < 1e-9);
+        match &rectangle {
+            Value::Polygon(rings) => {
+                assert_eq!(rings.len(), 1);
+                assert_eq!(rings[0].len(), 5);
+                assert_eq!(rings[0].first(), rings[0].last());
+                for &(x, y) in &[(0., 0.), (4., 0.), (4., 1.), (0., 1.)] {
+                    let expected = rotate(x, y);
+                    assert!(rings[0]
+                        .iter()
+                        .any(|p| (p[0] - expected[0]).abs() < 1e-9
+                            && (p[1] - expected[1]).abs() < 1e-9));
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        let collinear = Value::MultiPoint(vec![vec![0., 0.], vec![1., 1.], vec![2., 2.]]);
+        assert_eq!(collinear.minimum_rotated_rectangle(), None);
+        assert_eq!(Value::Point(vec![1., 1.]).minimum_rotated_rectangle(), None);
+        let nan = Value::MultiPoint(vec![vec![0., 0.], vec![1., 0.], vec![f64::NAN, 1.]]);
+        assert_eq!(nan.minimum_rotated_rectangle(), None);
+    }
+}
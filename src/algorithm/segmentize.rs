@@ -0,0 +1,105 @@
+use crate::{Position, Value};
+
+impl Value {
+    /// Split every segment of each line and polygon ring into `segments_per_edge` equal parts, in
+    /// place, by inserting `segments_per_edge - 1` evenly spaced vertices into it.
+    ///
+    /// Inserted positions are interpolated in every coordinate the segment's endpoints share, so
+    /// z values are kept. `Point` and `MultiPoint` are left untouched, and `GeometryCollection`
+    /// members are segmentized recursively. A value of 1 leaves the geometry unchanged.
+    ///
+    /// # Panics
+    ///
+    /// If `segments_per_edge` is 0.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut line = Value::LineString(vec![vec![0., 0.], vec![3., 0.], vec![3., 3.]]);
+    /// line.segmentize(3);
+    /// assert_eq!(
+    ///     line,
+    ///     Value::LineString(vec![
+    ///         vec![0., 0.],
+    ///         vec![1., 0.],
+    ///         vec![2., 0.],
+    ///         vec![3., 0.],
+    ///         vec![3., 1.],
+    ///         vec![3., 2.],
+    ///         vec![3., 3.],
+    ///     ])
+    /// );
+    /// ```
+    pub fn segmentize(&mut self, segments_per_edge: usize) {
+        assert!(segments_per_edge > 0, "segments_per_edge must be positive");
+        if segments_per_edge == 1 {
+            return;
+        }
+        match self {
+            Value::Point(_) | Value::MultiPoint(_) | Value::Unknown(_) => {}
+            Value::LineString(line) => segmentize_line(line, segments_per_edge),
+            Value::MultiLineString(lines) | Value::Polygon(lines) => lines
+                .iter_mut()
+                .for_each(|line| segmentize_line(line, segments_per_edge)),
+            Value::MultiPolygon(polygons) => polygons
+                .iter_mut()
+                .flatten()
+                .for_each(|ring| segmentize_line(ring, segments_per_edge)),
+            Value::GeometryCollection(geometries) => geometries
+                .iter_mut()
+                .for_each(|geometry| geometry.value.segmentize(segments_per_edge)),
+        }
+    }
+}
+
+fn segmentize_line(line: &mut Vec<Position>, segments_per_edge: usize) {
+    if line.len() < 2 {
+        return;
+    }
+    let mut segmentized = Vec::with_capacity((line.len() - 1) * segments_per_edge + 1);
+    for (start, end) in super::segments(line) {
+        segmentized.push(start.clone());
+        for step in 1..segments_per_edge {
+            let t = step as f64 / segments_per_edge as f64;
+            segmentized.push(
+                start
+                    .iter()
+                    .zip(end)
+                    .map(|(a, b)| a + (b - a) * t)
+                    .collect(),
+            );
+        }
+    }
+    segmentized.extend(line.last().cloned());
+    *line = segmentized;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+
+    #[test]
+    fn segmentize_rings() {
+        let ring = vec![
+            vec![0., 0., 0.],
+            vec![2., 0., 4.],
+            vec![0., 2., 0.],
+            vec![0., 0., 0.],
+        ];
+        let mut value = Value::Polygon(vec![ring.clone()]);
+        value.segmentize(1);
+        assert_eq!(value, Value::Polygon(vec![ring]));
+
+        value.segmentize(2);
+        match &value {
+            Value::Polygon(rings) => {
+                assert_eq!(rings[0].len(), 7);
+                assert_eq!(rings[0][1], vec![1., 0., 2.]);
+                assert_eq!(rings[0][3], vec![1., 1., 2.]);
+                assert_eq!(rings[0].first(), rings[0].last());
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(value.ring_signed_areas(), vec![2.]);
+    }
+}
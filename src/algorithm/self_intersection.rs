@@ -0,0 +1,171 @@
+use crate::algorithm::{on_segment, orientation, segments, segments_intersect};
+use crate::{Position, Value};
+
+impl Value {
+    /// Returns `true` if any `LineString`, or any ring of a `Polygon`, crosses or touches itself.
+    ///
+    /// Each line and ring is checked on its own: rings of the same polygon touching each other
+    /// are not reported. The endpoint shared by two consecutive segments (and the closing vertex
+    /// of a ring) is not considered an intersection, but consecutive segments that double back
+    /// over each other are. `GeometryCollection` members are checked recursively; `Point` and
+    /// `MultiPoint` never self-intersect. Positions with fewer than two coordinates are skipped.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let bow_tie = Value::Polygon(vec![vec![
+    ///     vec![0., 0.],
+    ///     vec![1., 1.],
+    ///     vec![1., 0.],
+    ///     vec![0., 1.],
+    ///     vec![0., 0.],
+    /// ]]);
+    /// assert!(bow_tie.has_self_intersection());
+    ///
+    /// let square = Value::Polygon(vec![vec![
+    ///     vec![0., 0.],
+    ///     vec![1., 0.],
+    ///     vec![1., 1.],
+    ///     vec![0., 1.],
+    ///     vec![0., 0.],
+    /// ]]);
+    /// assert!(!square.has_self_intersection());
+    /// ```
+    pub fn has_self_intersection(&self) -> bool {
+        match self {
+            Value::Point(_) | Value::MultiPoint(_) | Value::Unknown(_) => false,
+            Value::LineString(line) => line_self_intersects(line),
+            Value::MultiLineString(lines) => lines.iter().any(|line| line_self_intersects(line)),
+            Value::Polygon(rings) => rings.iter().any(|ring| line_self_intersects(ring)),
+            Value::MultiPolygon(polygons) => polygons
+                .iter()
+                .flatten()
+                .any(|ring| line_self_intersects(ring)),
+            Value::GeometryCollection(geometries) => geometries
+                .iter()
+                .any(|geometry| geometry.value.has_self_intersection()),
+        }
+    }
+}
+
+fn line_self_intersects(line: &[Position]) -> bool {
+    // Zero-length segments would otherwise make their neighbours look like they overlap
+    let mut points: Vec<&Position> = Vec::with_capacity(line.len());
+    for position in line.iter().filter(|position| position.len() >= 2) {
+        if points.last().map(|last| &last[..2]) != Some(&position[..2]) {
+            points.push(position);
+        }
+    }
+    let closed = points.len() > 3 && points.first() == points.last();
+    let segments: Vec<_> = segments(&points).collect();
+
+    let n = segments.len();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let (a, b) = segments[i];
+            let (c, d) = segments[j];
+            let adjacent = j == i + 1;
+            let wraps = closed && i == 0 && j == n - 1;
+            if adjacent {
+                // `b == c`; they only intersect if `d` folds back onto `a`-`b`
+                if orientation(a, b, d) == 0. && on_segment(a, b, d) {
+                    return true;
+                }
+            } else if wraps {
+                // `a == d`; they only intersect if `c` folds back onto `a`-`b`
+                if orientation(a, b, c) == 0. && on_segment(a, b, c) {
+                    return true;
+                }
+            } else if segments_intersect(a, b, c, d) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, Value};
+
+    #[test]
+    fn crossing_line_string() {
+        let line = Value::LineString(vec![vec![0., 0.], vec![2., 2.], vec![2., 0.], vec![0., 2.]]);
+        assert!(line.has_self_intersection());
+    }
+
+    #[test]
+    fn simple_line_string() {
+        let line = Value::LineString(vec![vec![0., 0.], vec![1., 0.], vec![1., 1.], vec![2., 1.]]);
+        assert!(!line.has_self_intersection());
+    }
+
+    #[test]
+    fn repeated_vertex_is_not_an_intersection() {
+        let line = Value::LineString(vec![vec![0., 0.], vec![1., 0.], vec![1., 0.], vec![1., 1.]]);
+        assert!(!line.has_self_intersection());
+    }
+
+    #[test]
+    fn line_doubling_back() {
+        let line = Value::LineString(vec![vec![0., 0.], vec![2., 0.], vec![1., 0.]]);
+        assert!(line.has_self_intersection());
+    }
+
+    #[test]
+    fn ring_touching_itself() {
+        // The vertex at [1, 0] touches the first edge
+        let polygon = Value::Polygon(vec![vec![
+            vec![0., 0.],
+            vec![2., 0.],
+            vec![2., 2.],
+            vec![1., 0.],
+            vec![0., 2.],
+            vec![0., 0.],
+        ]]);
+        assert!(polygon.has_self_intersection());
+    }
+
+    #[test]
+    fn rings_are_checked_independently() {
+        let polygon = Value::Polygon(vec![
+            vec![
+                vec![0., 0.],
+                vec![4., 0.],
+                vec![4., 4.],
+                vec![0., 4.],
+                vec![0., 0.],
+            ],
+            vec![vec![0., 0.], vec![1., 1.], vec![1., 2.], vec![0., 0.]],
+        ]);
+        assert!(!polygon.has_self_intersection());
+    }
+
+    #[test]
+    fn geometry_collection_recurses() {
+        let collection = Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![0., 0.])),
+            Geometry::new(Value::MultiLineString(vec![vec![
+                vec![0., 0.],
+                vec![2., 2.],
+                vec![2., 0.],
+                vec![0., 2.],
+            ]])),
+        ]);
+        assert!(collection.has_self_intersection());
+    }
+
+    #[test]
+    fn short_positions_are_skipped() {
+        let line = Value::LineString(vec![vec![1.], vec![2.]]);
+        assert!(!line.has_self_intersection());
+        let line = Value::LineString(vec![
+            vec![0., 0.],
+            vec![2., 2.],
+            vec![],
+            vec![2., 0.],
+            vec![0., 2.],
+        ]);
+        assert!(line.has_self_intersection());
+    }
+}
@@ -0,0 +1,208 @@
+use super::closest_point_on_segment;
+use crate::{Position, Value};
+
+/// Options for [`Value::simplify_with`](enum.Value.html#method.simplify_with)
+///
+/// ```
+/// use geojson::{SimplifyOptions, Value};
+///
+/// let mut line = Value::LineString(vec![
+///     vec![0.0, 0.0],
+///     vec![1.0, 0.2],
+///     vec![2.0, -0.1],
+///     vec![3.0, 0.0],
+/// ]);
+/// line.simplify_with(&SimplifyOptions::new(1.0).min_points(3));
+/// assert_eq!(
+///     line,
+///     Value::LineString(vec![vec![0.0, 0.0], vec![1.0, 0.2], vec![3.0, 0.0]])
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimplifyOptions {
+    tolerance: f64,
+    min_points: usize,
+}
+
+impl SimplifyOptions {
+    /// Remove vertices closer than `tolerance` to the simplified shape, in the units of the
+    /// coordinates
+    pub fn new(tolerance: f64) -> Self {
+        SimplifyOptions {
+            tolerance,
+            min_points: 0,
+        }
+    }
+
+    /// Stop removing vertices from a line or ring once it is down to `min_points`, even if the
+    /// tolerance would remove more, so that small features keep their shape.
+    ///
+    /// Lines always keep at least their 2 endpoints, and rings at least 4 positions so that
+    /// they stay valid. Lines and rings which already have no more than this are left alone.
+    pub fn min_points(mut self, min_points: usize) -> Self {
+        self.min_points = min_points;
+        self
+    }
+}
+
+impl Value {
+    /// Simplify every line and ring with the Douglas–Peucker algorithm, in place.
+    ///
+    /// Vertices closer than `tolerance` to the simplified shape are removed, measured in the
+    /// plane in the units of the coordinates. Endpoints, and the closing position of rings, are
+    /// always kept. `Point` and `MultiPoint` are left untouched, as are lines and rings with a
+    /// position of fewer than two coordinates. See
+    /// [`simplify_with`](#method.simplify_with) for further options.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut line = Value::LineString(vec![vec![0.0, 0.0], vec![1.0, 0.1], vec![2.0, 0.0]]);
+    /// line.simplify(0.5);
+    /// assert_eq!(line, Value::LineString(vec![vec![0.0, 0.0], vec![2.0, 0.0]]));
+    /// ```
+    pub fn simplify(&mut self, tolerance: f64) {
+        self.simplify_with(&SimplifyOptions::new(tolerance))
+    }
+
+    /// Simplify every line and ring according to `options`, in place
+    pub fn simplify_with(&mut self, options: &SimplifyOptions) {
+        let simplify_line = |line: &mut Vec<Position>| simplify(line, options, 2);
+        let simplify_ring = |ring: &mut Vec<Position>| simplify(ring, options, 4);
+        match self {
+            Value::Point(_) | Value::MultiPoint(_) | Value::Unknown(_) => {}
+            Value::LineString(line) => simplify_line(line),
+            Value::MultiLineString(lines) => lines.iter_mut().for_each(simplify_line),
+            Value::Polygon(rings) => rings.iter_mut().for_each(simplify_ring),
+            Value::MultiPolygon(polygons) => polygons.iter_mut().flatten().for_each(simplify_ring),
+            Value::GeometryCollection(geometries) => geometries
+                .iter_mut()
+                .for_each(|geometry| geometry.value.simplify_with(options)),
+        }
+    }
+}
+
+/// Douglas–Peucker, splitting the segment with the farthest vertex first so that it can stop
+/// at a minimum number of points
+fn simplify(line: &mut Vec<Position>, options: &SimplifyOptions, floor: usize) {
+    let min_points = options.min_points.max(floor);
+    if line.len() <= min_points || line.iter().any(|position| position.len() < 2) {
+        return;
+    }
+    let mut keep = vec![false; line.len()];
+    keep[0] = true;
+    keep[line.len() - 1] = true;
+    let mut kept = 2;
+    // Unsplit segments, as (start, end, farthest vertex between them, its distance)
+    let mut segments: Vec<(usize, usize, usize, f64)> = Vec::new();
+    segments.extend(farthest(line, 0, line.len() - 1));
+    while let Some(index) =
+        (0..segments.len()).max_by(|&a, &b| segments[a].3.total_cmp(&segments[b].3))
+    {
+        let (start, end, vertex, distance) = segments[index];
+        if distance <= options.tolerance && kept >= min_points {
+            break;
+        }
+        segments.swap_remove(index);
+        keep[vertex] = true;
+        kept += 1;
+        segments.extend(farthest(line, start, vertex));
+        segments.extend(farthest(line, vertex, end));
+    }
+    let mut keep = keep.into_iter();
+    line.retain(|_| keep.next().unwrap_or(false));
+}
+
+/// The vertex strictly between `start` and `end` farthest from the segment joining them
+fn farthest(line: &[Position], start: usize, end: usize) -> Option<(usize, usize, usize, f64)> {
+    (start + 1..end)
+        .map(|i| (i, segment_distance(&line[i], &line[start], &line[end])))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(vertex, distance)| (start, end, vertex, distance))
+}
+
+fn segment_distance(p: &[f64], a: &[f64], b: &[f64]) -> f64 {
+    let [x, y] = closest_point_on_segment(p, a, b);
+    (p[0] - x).hypot(p[1] - y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SimplifyOptions;
+    use crate::Value;
+
+    #[test]
+    fn simplify_rings_keeps_them_valid() {
+        let ring = vec![
+            vec![0., 0.],
+            vec![10., 0.],
+            vec![10., 0.1],
+            vec![10., 10.],
+            vec![0., 10.],
+            vec![0., 0.],
+        ];
+        let mut polygon = Value::Polygon(vec![ring.clone()]);
+        polygon.simplify(0.5);
+        assert_eq!(
+            polygon,
+            Value::Polygon(vec![vec![
+                vec![0., 0.],
+                vec![10., 0.],
+                vec![10., 10.],
+                vec![0., 10.],
+                vec![0., 0.]
+            ]])
+        );
+
+        // A huge tolerance would collapse the ring, but rings keep at least 4 positions
+        let mut polygon = Value::Polygon(vec![ring]);
+        polygon.simplify_with(&SimplifyOptions::new(100.).min_points(1));
+        match polygon {
+            Value::Polygon(rings) => {
+                assert_eq!(rings[0].len(), 4);
+                assert_eq!(rings[0].first(), rings[0].last());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn min_points() {
+        let line: Vec<_> = (0..10).map(|i| vec![i as f64, (i % 2) as f64]).collect();
+        let mut simplified = Value::LineString(line.clone());
+        simplified.simplify(5.);
+        assert_eq!(
+            simplified,
+            Value::LineString(vec![line[0].clone(), line[9].clone()])
+        );
+
+        let mut floored = Value::MultiLineString(vec![line.clone(), line[..3].to_vec()]);
+        floored.simplify_with(&SimplifyOptions::new(5.).min_points(4));
+        match floored {
+            Value::MultiLineString(lines) => {
+                assert_eq!(lines[0].len(), 4);
+                assert_eq!(lines[1], line[..3].to_vec());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn short_positions_are_left_alone() {
+        let original = Value::Polygon(vec![vec![
+            vec![0., 0.],
+            vec![5., 0.1],
+            vec![10., 0.],
+            vec![10.],
+            vec![0., 10.],
+            vec![0., 0.],
+        ]]);
+        let mut value = original.clone();
+        value.simplify(1.);
+        assert_eq!(value, original);
+
+        let mut line = Value::LineString(vec![vec![0.], vec![1.], vec![2.]]);
+        line.simplify(1.);
+        assert_eq!(line, Value::LineString(vec![vec![0.], vec![1.], vec![2.]]));
+    }
+}
@@ -0,0 +1,86 @@
+use crate::Value;
+
+impl Value {
+    /// Swap the first two coordinates of every position, in place.
+    ///
+    /// This fixes data stored as `[latitude, longitude]` rather than the `[longitude, latitude]`
+    /// required by RFC 7946. Any further coordinates stay where they are, and positions with
+    /// fewer than two coordinates are left untouched. Swapping mirrors the geometry, so it
+    /// also reverses the winding of polygon rings.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut point = Value::Point(vec![51.5, -0.12, 10.0]);
+    /// point.swap_xy();
+    /// assert_eq!(point, Value::Point(vec![-0.12, 51.5, 10.0]));
+    /// ```
+    pub fn swap_xy(&mut self) {
+        for position in self.coords_iter_mut() {
+            if position.len() >= 2 {
+                position.swap(0, 1);
+            }
+        }
+    }
+
+    /// Guess whether this value's positions are `[latitude, longitude]` rather than
+    /// `[longitude, latitude]`.
+    ///
+    /// Returns `true` if some second coordinate lies outside `±90`, so it can't be a latitude,
+    /// while every first coordinate is within `±90`. Data whose longitudes are all within
+    /// `±90` can't be told apart this way and returns `false`.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let latlon = Value::LineString(vec![vec![37.77, -122.42], vec![40.71, -74.01]]);
+    /// assert!(latlon.looks_like_latlon());
+    ///
+    /// let ambiguous = Value::Point(vec![51.5, -0.12]);
+    /// assert!(!ambiguous.looks_like_latlon());
+    /// ```
+    pub fn looks_like_latlon(&self) -> bool {
+        let mut second_exceeds_latitude = false;
+        for position in self.coords_iter().filter(|position| position.len() >= 2) {
+            if position[0].abs() > 90. {
+                return false;
+            }
+            second_exceeds_latitude |= position[1].abs() > 90.;
+        }
+        second_exceeds_latitude
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, Value};
+
+    #[test]
+    fn swap_xy() {
+        let mut value = Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![1.])),
+            Geometry::new(Value::Polygon(vec![vec![
+                vec![45., 170.],
+                vec![46., 170.],
+                vec![46., 171.],
+                vec![45., 170.],
+            ]])),
+        ]);
+        assert!(value.looks_like_latlon());
+
+        value.swap_xy();
+        assert!(!value.looks_like_latlon());
+        assert_eq!(
+            value,
+            Value::GeometryCollection(vec![
+                Geometry::new(Value::Point(vec![1.])),
+                Geometry::new(Value::Polygon(vec![vec![
+                    vec![170., 45.],
+                    vec![170., 46.],
+                    vec![171., 46.],
+                    vec![170., 45.],
+                ]])),
+            ])
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use std::cmp::Ordering;
+
+use crate::algorithm::area::ring_signed_area;
+use crate::ring::is_closed_ring;
+use crate::{Position, Value};
+
+impl Value {
+    /// Whether this value and `other` describe the same shape, even if their rings start at
+    /// different vertices or wind in different directions
+    ///
+    /// The values must be the same variant, with the same number of parts in the same order,
+    /// and positions are compared exactly, including any z coordinates. Each polygon ring is
+    /// first put in a canonical form:
+    ///
+    /// * the closing position is dropped, if the ring is closed;
+    /// * the ring is reversed if it is clockwise, so that it winds counter-clockwise; a ring
+    ///   with no area is given whichever direction sorts first;
+    /// * the ring is rotated to start at its lowest position, comparing coordinate by
+    ///   coordinate, then by length, with ties between repeated positions broken by the rest
+    ///   of the ring.
+    ///
+    /// The order of rings within a polygon, of parts within multi-part values, and of members
+    /// within a `GeometryCollection` must match. Lines must run in the same direction; see
+    /// [`topologically_eq_ignoring_line_direction`](#method.topologically_eq_ignoring_line_direction).
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let a = Value::Polygon(vec![vec![
+    ///     vec![0., 0.], vec![1., 0.], vec![1., 1.], vec![0., 0.],
+    /// ]]);
+    /// let b = Value::Polygon(vec![vec![
+    ///     vec![1., 1.], vec![1., 0.], vec![0., 0.], vec![1., 1.],
+    /// ]]);
+    /// assert_ne!(a, b);
+    /// assert!(a.topologically_eq(&b));
+    /// ```
+    pub fn topologically_eq(&self, other: &Value) -> bool {
+        values_eq(self, other, false)
+    }
+
+    /// Like [`topologically_eq`](#method.topologically_eq), but lines are also equal to their
+    /// reverse
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let line = Value::LineString(vec![vec![0., 0.], vec![1., 1.]]);
+    /// let reversed = Value::LineString(vec![vec![1., 1.], vec![0., 0.]]);
+    /// assert!(!line.topologically_eq(&reversed));
+    /// assert!(line.topologically_eq_ignoring_line_direction(&reversed));
+    /// ```
+    pub fn topologically_eq_ignoring_line_direction(&self, other: &Value) -> bool {
+        values_eq(self, other, true)
+    }
+}
+
+fn values_eq(a: &Value, b: &Value, undirected: bool) -> bool {
+    let lines_eq = |a: &[Vec<Position>], b: &[Vec<Position>]| {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| line_eq(a, b, undirected))
+    };
+    match (a, b) {
+        (Value::LineString(a), Value::LineString(b)) => line_eq(a, b, undirected),
+        (Value::MultiLineString(a), Value::MultiLineString(b)) => lines_eq(a, b),
+        (Value::Polygon(a), Value::Polygon(b)) => polygon_eq(a, b),
+        (Value::MultiPolygon(a), Value::MultiPolygon(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| polygon_eq(a, b))
+        }
+        (Value::GeometryCollection(a), Value::GeometryCollection(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|(a, b)| values_eq(&a.value, &b.value, undirected))
+        }
+        (a, b) => a == b,
+    }
+}
+
+fn line_eq(a: &[Position], b: &[Position], undirected: bool) -> bool {
+    a == b || (undirected && a.len() == b.len() && a.iter().eq(b.iter().rev()))
+}
+
+fn polygon_eq(a: &[Vec<Position>], b: &[Vec<Position>]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| canonical_ring(a) == canonical_ring(b))
+}
+
+/// The positions of `ring` in the canonical form described by `Value::topologically_eq`
+fn canonical_ring(ring: &[Position]) -> Vec<&Position> {
+    let open = if is_closed_ring(ring) && ring.len() > 1 {
+        &ring[..ring.len() - 1]
+    } else {
+        ring
+    };
+    let area = if open.iter().all(|position| position.len() >= 2) {
+        ring_signed_area(open)
+    } else {
+        0.
+    };
+    let forward: Vec<&Position> = open.iter().collect();
+    let backward: Vec<&Position> = open.iter().rev().collect();
+    if area > 0. {
+        rotate_to_lowest(forward)
+    } else if area < 0. {
+        rotate_to_lowest(backward)
+    } else {
+        let (forward, backward) = (rotate_to_lowest(forward), rotate_to_lowest(backward));
+        match compare_sequences(&forward, &backward) {
+            Ordering::Greater => backward,
+            _ => forward,
+        }
+    }
+}
+
+/// Rotate the cycle `ring` to its lowest starting point
+fn rotate_to_lowest(ring: Vec<&Position>) -> Vec<&Position> {
+    let lowest = match ring.iter().copied().min_by(|a, b| compare_positions(a, b)) {
+        Some(lowest) => lowest,
+        None => return ring,
+    };
+    let rotated = |start: usize| -> Vec<&Position> {
+        ring[start..]
+            .iter()
+            .chain(&ring[..start])
+            .copied()
+            .collect()
+    };
+    (0..ring.len())
+        .filter(|&i| compare_positions(ring[i], lowest) == Ordering::Equal)
+        .map(rotated)
+        .min_by(|a, b| compare_sequences(a, b))
+        .unwrap_or(ring)
+}
+
+fn compare_positions(a: &[f64], b: &[f64]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| a.total_cmp(b))
+        .find(|&ordering| ordering != Ordering::Equal)
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+fn compare_sequences(a: &[&Position], b: &[&Position]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| compare_positions(a, b))
+        .find(|&ordering| ordering != Ordering::Equal)
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, Value};
+
+    fn ring(points: &[[f64; 2]]) -> Vec<Vec<f64>> {
+        points.iter().map(|p| p.to_vec()).collect()
+    }
+
+    #[test]
+    fn rings_are_canonicalized() {
+        let square = ring(&[[0., 0.], [2., 0.], [2., 2.], [0., 2.], [0., 0.]]);
+        let hole = ring(&[[1., 1.], [1., 1.5], [1.5, 1.5], [1., 1.]]);
+        let polygon = Value::Polygon(vec![square, hole]);
+
+        // Rotated, reversed and unclosed
+        let shifted = Value::Polygon(vec![
+            ring(&[[2., 2.], [2., 0.], [0., 0.], [0., 2.]]),
+            ring(&[[1.5, 1.5], [1., 1.], [1., 1.5], [1.5, 1.5]]),
+        ]);
+        assert!(polygon.topologically_eq(&shifted));
+        assert!(
+            Value::MultiPolygon(vec![polygon.as_polygon().unwrap().clone()]).topologically_eq(
+                &Value::MultiPolygon(vec![shifted.as_polygon().unwrap().clone()])
+            )
+        );
+
+        // A different shape with the same vertices
+        let bowtie = Value::Polygon(vec![
+            ring(&[[0., 0.], [2., 2.], [2., 0.], [0., 2.], [0., 0.]]),
+            ring(&[[1., 1.], [1., 1.5], [1.5, 1.5], [1., 1.]]),
+        ]);
+        assert!(!polygon.topologically_eq(&bowtie));
+        // Rings in a different order
+        let mut swapped = shifted.into_polygon().unwrap();
+        swapped.reverse();
+        assert!(!polygon.topologically_eq(&Value::Polygon(swapped)));
+    }
+
+    #[test]
+    fn degenerate_rings() {
+        // No area, and a repeated lowest position
+        let flat = Value::Polygon(vec![ring(&[
+            [0., 0.],
+            [1., 0.],
+            [0., 0.],
+            [2., 0.],
+            [0., 0.],
+        ])]);
+        let rotated = Value::Polygon(vec![ring(&[
+            [2., 0.],
+            [0., 0.],
+            [1., 0.],
+            [0., 0.],
+            [2., 0.],
+        ])]);
+        assert!(flat.topologically_eq(&rotated));
+        let different = Value::Polygon(vec![ring(&[
+            [0., 0.],
+            [1., 0.],
+            [2., 0.],
+            [0., 0.],
+            [0., 0.],
+        ])]);
+        assert!(!flat.topologically_eq(&different));
+        assert!(Value::Polygon(vec![vec![]]).topologically_eq(&Value::Polygon(vec![vec![]])));
+    }
+
+    #[test]
+    fn lines_and_collections() {
+        let line = Value::LineString(ring(&[[0., 0.], [1., 1.], [2., 0.]]));
+        let mut reversed = line.clone();
+        reversed.reverse_coords();
+        let collection = |value: &Value| {
+            Value::GeometryCollection(vec![
+                Geometry::new(Value::Point(vec![5., 5.])),
+                Geometry::new(value.clone()),
+            ])
+        };
+        assert!(!collection(&line).topologically_eq(&collection(&reversed)));
+        assert!(collection(&line).topologically_eq_ignoring_line_direction(&collection(&reversed)));
+        assert!(!line.topologically_eq(&Value::MultiLineString(vec![ring(&[
+            [0., 0.],
+            [1., 1.],
+            [2., 0.]
+        ])])));
+    }
+}
@@ -0,0 +1,307 @@
+use super::{on_segment, orientation};
+use crate::{Position, Value};
+
+/// A triangle, as the x / y coordinates of its corners in counter-clockwise order
+type Triangle = [[f64; 2]; 3];
+
+impl Value {
+    /// Split the value's polygons into triangles by ear clipping, for rendering
+    ///
+    /// Holes are joined to the exterior ring by a bridge to a visible vertex before clipping.
+    /// Rings are read as 2D and may be wound either way; every triangle is returned
+    /// counter-clockwise, and together they cover the polygon exactly. Degenerate rings and
+    /// repeated or collinear vertices add no triangles. Self-intersecting rings can't be
+    /// triangulated correctly, and give overlapping triangles or leave gaps.
+    /// `GeometryCollection` members' triangles are concatenated, and other types have none.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let square = Value::Polygon(vec![vec![
+    ///     vec![0., 0.],
+    ///     vec![1., 0.],
+    ///     vec![1., 1.],
+    ///     vec![0., 1.],
+    ///     vec![0., 0.],
+    /// ]]);
+    /// let triangles = square.triangulate();
+    /// assert_eq!(triangles.len(), 2);
+    /// assert!(Value::Point(vec![0., 0.]).triangulate().is_empty());
+    /// ```
+    pub fn triangulate(&self) -> Vec<[[f64; 2]; 3]> {
+        let mut triangles = vec![];
+        self.triangulate_into(&mut triangles);
+        triangles
+    }
+
+    fn triangulate_into(&self, triangles: &mut Vec<Triangle>) {
+        match self {
+            Value::Polygon(rings) => triangulate_polygon(rings, triangles),
+            Value::MultiPolygon(polygons) => polygons
+                .iter()
+                .for_each(|rings| triangulate_polygon(rings, triangles)),
+            Value::GeometryCollection(geometries) => geometries
+                .iter()
+                .for_each(|geometry| geometry.value.triangulate_into(triangles)),
+            Value::Point(_)
+            | Value::MultiPoint(_)
+            | Value::LineString(_)
+            | Value::MultiLineString(_)
+            | Value::Unknown(_) => {}
+        }
+    }
+}
+
+fn triangulate_polygon(rings: &[Vec<Position>], triangles: &mut Vec<Triangle>) {
+    let mut outer = match rings.first().and_then(|ring| ring_points(ring, true)) {
+        Some(outer) => outer,
+        None => return,
+    };
+    let mut holes: Vec<Vec<[f64; 2]>> = rings[1..]
+        .iter()
+        .filter_map(|ring| ring_points(ring, false))
+        .collect();
+    // Bridging the rightmost hole first keeps the bridges of the others from crossing it
+    holes.sort_by(|a, b| max_x(b).partial_cmp(&max_x(a)).unwrap());
+    for hole in holes {
+        outer = bridge(outer, hole);
+    }
+    clip_ears(outer, triangles);
+}
+
+/// The distinct vertices of `ring`, counter-clockwise if `exterior` and clockwise otherwise, or
+/// `None` if it has no area
+fn ring_points(ring: &[Position], exterior: bool) -> Option<Vec<[f64; 2]>> {
+    let mut points: Vec<[f64; 2]> = ring
+        .iter()
+        .filter(|position| position.len() >= 2)
+        .map(|position| [position[0], position[1]])
+        .collect();
+    points.dedup();
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    let area: f64 = (0..points.len())
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            a[0] * b[1] - b[0] * a[1]
+        })
+        .sum();
+    if points.len() < 3 || area == 0. || !area.is_finite() {
+        return None;
+    }
+    if (area > 0.) != exterior {
+        points.reverse();
+    }
+    Some(points)
+}
+
+fn max_x(points: &[[f64; 2]]) -> f64 {
+    points
+        .iter()
+        .map(|p| p[0])
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+/// Join `hole` into `outer` with a pair of coincident edges from the hole's rightmost vertex to
+/// a vertex of `outer` visible from it
+fn bridge(outer: Vec<[f64; 2]>, hole: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    let (m_index, m) = hole
+        .iter()
+        .copied()
+        .enumerate()
+        .fold(
+            (0, hole[0]),
+            |best, (i, p)| if p[0] > best.1[0] { (i, p) } else { best },
+        );
+
+    // The nearest crossing of a ray from `m` towards +x with an edge of `outer`
+    let mut nearest: Option<(f64, usize)> = None;
+    for i in 0..outer.len() {
+        let (a, b) = (outer[i], outer[(i + 1) % outer.len()]);
+        if a[1] == b[1] || m[1] < a[1].min(b[1]) || m[1] > a[1].max(b[1]) {
+            continue;
+        }
+        let x = a[0] + (m[1] - a[1]) * (b[0] - a[0]) / (b[1] - a[1]);
+        if x >= m[0] && !matches!(nearest, Some((best, _)) if best <= x) {
+            nearest = Some((x, i));
+        }
+    }
+    let (x, edge) = match nearest {
+        Some(nearest) => nearest,
+        None => return outer,
+    };
+    let crossing = [x, m[1]];
+    let (a, b) = (edge, (edge + 1) % outer.len());
+    let mut p_index = if outer[a] == crossing || (outer[b] != crossing && outer[a][0] > outer[b][0])
+    {
+        a
+    } else {
+        b
+    };
+
+    // Any vertex inside the triangle of `m`, the crossing and the candidate could block the
+    // view of it; the one closest in angle to the ray is visible
+    if outer[p_index] != crossing {
+        let p = outer[p_index];
+        let mut best_tan = f64::INFINITY;
+        for (i, q) in outer.iter().enumerate() {
+            let inside = [[m, crossing, p], [m, p, crossing]].iter().any(|t| {
+                orientation(&t[0], &t[1], q) >= 0.
+                    && orientation(&t[1], &t[2], q) >= 0.
+                    && orientation(&t[2], &t[0], q) >= 0.
+            });
+            if *q != p && *q != m && q[0] > m[0] && inside {
+                let tan = (q[1] - m[1]).abs() / (q[0] - m[0]);
+                if tan < best_tan {
+                    best_tan = tan;
+                    p_index = i;
+                }
+            }
+        }
+    }
+
+    let mut bridged = Vec::with_capacity(outer.len() + hole.len() + 2);
+    bridged.extend_from_slice(&outer[..=p_index]);
+    bridged.extend_from_slice(&hole[m_index..]);
+    bridged.extend_from_slice(&hole[..=m_index]);
+    bridged.extend_from_slice(&outer[p_index..]);
+    bridged
+}
+
+/// Triangulate a counter-clockwise ring which may touch itself along bridges
+fn clip_ears(points: Vec<[f64; 2]>, triangles: &mut Vec<Triangle>) {
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    while remaining.len() >= 3 {
+        let n = remaining.len();
+        let mut clipped = false;
+        for i in 0..n {
+            let (prev, cur, next) = (
+                points[remaining[(i + n - 1) % n]],
+                points[remaining[i]],
+                points[remaining[(i + 1) % n]],
+            );
+            let turn = orientation(&prev, &cur, &next);
+            if turn == 0. && on_segment(&prev, &next, &cur) {
+                // A vertex in the middle of a straight edge adds nothing
+                remaining.remove(i);
+                clipped = true;
+                break;
+            }
+            if turn > 0. && !contains_other_vertex(&points, &remaining, [prev, cur, next]) {
+                triangles.push([prev, cur, next]);
+                remaining.remove(i);
+                clipped = true;
+                break;
+            }
+        }
+        if !clipped {
+            // Only possible for self-intersecting input
+            break;
+        }
+    }
+}
+
+/// Whether any of the `remaining` points other than the triangle's corners lies inside or on it
+fn contains_other_vertex(points: &[[f64; 2]], remaining: &[usize], triangle: Triangle) -> bool {
+    let [a, b, c] = triangle;
+    remaining.iter().map(|&i| points[i]).any(|p| {
+        p != a
+            && p != b
+            && p != c
+            && orientation(&a, &b, &p) >= 0.
+            && orientation(&b, &c, &p) >= 0.
+            && orientation(&c, &a, &p) >= 0.
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Triangle;
+    use crate::algorithm::orientation;
+    use crate::{Geometry, Value};
+
+    fn area(triangles: &[Triangle]) -> f64 {
+        triangles
+            .iter()
+            .map(|[a, b, c]| {
+                let twice = orientation(a, b, c);
+                assert!(twice > 0., "{:?} isn't counter-clockwise", [a, b, c]);
+                twice / 2.
+            })
+            .sum()
+    }
+
+    fn ring(points: &[(f64, f64)]) -> Vec<Vec<f64>> {
+        let mut ring: Vec<Vec<f64>> = points.iter().map(|&(x, y)| vec![x, y]).collect();
+        ring.push(ring[0].clone());
+        ring
+    }
+
+    #[test]
+    fn triangulate_concave_and_holes() {
+        // An L shape, wound clockwise, with a repeated and a collinear vertex
+        let l_shape = ring(&[
+            (0., 0.),
+            (0., 2.),
+            (1., 2.),
+            (1., 1.),
+            (1., 1.),
+            (2., 1.),
+            (2., 0.),
+            (1., 0.),
+        ]);
+        let triangles = Value::Polygon(vec![l_shape]).triangulate();
+        assert_eq!(triangles.len(), 4);
+        assert_eq!(area(&triangles), 3.);
+
+        let holes = Value::MultiPolygon(vec![vec![
+            ring(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.)]),
+            ring(&[(2., 2.), (2., 4.), (4., 4.), (4., 2.)]),
+            ring(&[(6., 6.), (8., 6.), (8., 8.), (6., 8.)]),
+            ring(&[(6., 2.), (8., 2.), (7., 4.)]),
+        ]]);
+        let triangles = holes.triangulate();
+        assert!((area(&triangles) - (100. - 4. - 4. - 2.)).abs() < 1e-9);
+
+        let collection = Value::GeometryCollection(vec![
+            Geometry::new(holes),
+            Geometry::new(Value::LineString(vec![vec![0., 0.], vec![1., 1.]])),
+            Geometry::new(Value::Polygon(vec![ring(&[(0., 0.), (1., 1.), (2., 2.)])])),
+        ]);
+        assert_eq!(collection.triangulate(), triangles);
+    }
+
+    #[test]
+    fn triangles_cover_country_polygons() {
+        let geojson_str = include_str!("../../tests/fixtures/countries.geojson");
+        let collection: crate::FeatureCollection = serde_json::from_str(geojson_str).unwrap();
+        for feature in &collection.features {
+            let value = &feature.geometry.as_ref().unwrap().value;
+            let polygons = match value {
+                Value::Polygon(rings) => vec![rings.clone()],
+                Value::MultiPolygon(polygons) => polygons.clone(),
+                _ => continue,
+            };
+            // Antarctica's rings cross themselves where they wrap around the pole
+            if value.has_self_intersection() {
+                continue;
+            }
+            let expected: f64 = polygons
+                .into_iter()
+                .map(|rings| {
+                    let areas = Value::Polygon(rings).ring_signed_areas();
+                    areas[0].abs() - areas[1..].iter().map(|a| a.abs()).sum::<f64>()
+                })
+                .sum();
+            let actual = area(&value.triangulate());
+            assert!(
+                (actual - expected).abs() <= expected * 1e-9,
+                "{:?}: {} != {}",
+                feature.property("name"),
+                actual,
+                expected
+            );
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use tokio::io::AsyncRead;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tokio_util::io::SyncIoBridge;
+
+use crate::errors::Error;
+use crate::{Feature, FeatureReader};
+
+/// How many parsed features may wait for the stream's consumer before parsing pauses
+const BUFFERED_FEATURES: usize = 16;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+impl FeatureReader<SyncIoBridge<Box<dyn AsyncRead + Send + Unpin>>> {
+    /// Stream the features of a `FeatureCollection` from an asynchronous reader
+    ///
+    /// The features are parsed as for [`from_reader`](#method.from_reader), on a blocking
+    /// thread of the current Tokio runtime, which reads from `reader` as the stream is consumed.
+    /// At most a few features are buffered ahead of the consumer, so memory use stays bounded.
+    /// Dropping the stream stops the parsing.
+    ///
+    /// # Panics
+    ///
+    /// If called outside a Tokio runtime.
+    ///
+    /// ```
+    /// use geojson::FeatureReader;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let geojson_str = r#"{"type": "FeatureCollection", "features": [
+    ///     {"type": "Feature", "geometry": null, "properties": {"name": "a"}},
+    ///     {"type": "Feature", "geometry": null, "properties": {"name": "b"}}
+    /// ]}"#;
+    ///
+    /// let mut features = FeatureReader::from_async_reader(geojson_str.as_bytes());
+    /// let mut count = 0;
+    /// while let Some(feature) = features.next().await {
+    ///     assert!(feature.unwrap().contains_property("name"));
+    ///     count += 1;
+    /// }
+    /// assert_eq!(count, 2);
+    /// # }
+    /// ```
+    pub fn from_async_reader<R>(reader: R) -> impl Stream<Item = Result<Feature, Error>>
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        let (sender, receiver) = mpsc::channel(BUFFERED_FEATURES);
+        let reader: Box<dyn AsyncRead + Send + Unpin> = Box::new(reader);
+        tokio::task::spawn_blocking(move || {
+            for feature in FeatureReader::from_reader(SyncIoBridge::new(reader)) {
+                if sender.blocking_send(feature).is_err() {
+                    break;
+                }
+            }
+        });
+        ReceiverStream::new(receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, FeatureReader};
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn matches_sync_reader() {
+        let geojson_str = include_str!("../tests/fixtures/countries.geojson");
+        let features: Vec<_> = FeatureReader::from_async_reader(geojson_str.as_bytes())
+            .collect::<Result<_, _>>()
+            .await
+            .unwrap();
+        let expected: Vec<_> = FeatureReader::from_reader(geojson_str.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(features, expected);
+
+        let mut empty = FeatureReader::from_async_reader(&b""[..]);
+        assert!(matches!(empty.next().await, Some(Err(Error::EmptyInput))));
+        assert!(empty.next().await.is_none());
+    }
+}
@@ -0,0 +1,902 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Bbox, Feature, FeatureCollection, Geometry, Position, Value};
+use std::iter::{FromIterator, Sum};
+
+/// Returns the 2D extent of `bbox` as `[min x, min y, max x, max y]`, if it is a valid
+/// 4- or 6-element [bounding box](https://tools.ietf.org/html/rfc7946#section-5)
+pub(crate) fn extent_2d(bbox: &[f64]) -> Option<[f64; 4]> {
+    match bbox.len() {
+        4 | 6 => {
+            let dims = bbox.len() / 2;
+            Some([bbox[0], bbox[1], bbox[dims], bbox[dims + 1]])
+        }
+        _ => None,
+    }
+}
+
+/// Methods for updating a [`Bbox`](type.Bbox.html) in place
+///
+/// `Bbox` is an alias for `Vec<f64>`, so these are provided as an extension trait. An empty box
+/// takes the dimensionality of whatever is first added to it; a 4-element box stays 2D, and a
+/// box of any length other than 0, 4 or 6 is left unchanged.
+///
+/// ```
+/// use geojson::{Bbox, BboxExt};
+///
+/// let mut bbox = Bbox::new();
+/// bbox.expand_to_include_position(1.0, 2.0, None);
+/// bbox.expand_to_include_position(-1.0, 5.0, None);
+/// assert_eq!(bbox, vec![-1.0, 2.0, 1.0, 5.0]);
+///
+/// bbox.expand_to_include_bbox(&vec![0.0, 0.0, -10.0, 3.0, 3.0, 10.0]);
+/// assert_eq!(bbox, vec![-1.0, 0.0, 3.0, 5.0]);
+/// ```
+pub trait BboxExt {
+    /// Grow the box to contain the position `x`, `y` and, for a 3D box, `z`
+    fn expand_to_include_position(&mut self, x: f64, y: f64, z: Option<f64>);
+
+    /// Grow the box to contain `other`, which may be a 4- or 6-element box
+    fn expand_to_include_bbox(&mut self, other: &[f64]);
+
+    /// The 2D extent of the box as a closed, counter-clockwise `Polygon`
+    ///
+    /// A box whose west edge is east of its east edge crosses the antimeridian, and becomes a
+    /// `MultiPolygon` of the two parts either side of it. A box of any length other than 4 or 6
+    /// gives an empty `Polygon`.
+    ///
+    /// ```
+    /// use geojson::{BboxExt, Value};
+    ///
+    /// assert_eq!(
+    ///     vec![0., 0., 2., 1.].to_polygon(),
+    ///     Value::Polygon(vec![vec![
+    ///         vec![0., 0.],
+    ///         vec![2., 0.],
+    ///         vec![2., 1.],
+    ///         vec![0., 1.],
+    ///         vec![0., 0.],
+    ///     ]])
+    /// );
+    /// assert!(matches!(vec![170., 0., -170., 1.].to_polygon(), Value::MultiPolygon(_)));
+    /// ```
+    fn to_polygon(&self) -> Value;
+}
+
+/// The closed, counter-clockwise ring around a 2D extent
+fn extent_ring([min_x, min_y, max_x, max_y]: [f64; 4]) -> Vec<Position> {
+    vec![
+        vec![min_x, min_y],
+        vec![max_x, min_y],
+        vec![max_x, max_y],
+        vec![min_x, max_y],
+        vec![min_x, min_y],
+    ]
+}
+
+impl BboxExt for Bbox {
+    fn expand_to_include_position(&mut self, x: f64, y: f64, z: Option<f64>) {
+        match (self.len(), z) {
+            (0, None) => *self = vec![x, y, x, y],
+            (0, Some(z)) => *self = vec![x, y, z, x, y, z],
+            (4, _) | (6, _) => {
+                let dims = self.len() / 2;
+                let coordinates = [Some(x), Some(y), z];
+                for (i, coordinate) in coordinates.iter().take(dims).enumerate() {
+                    if let Some(coordinate) = *coordinate {
+                        self[i] = self[i].min(coordinate);
+                        self[dims + i] = self[dims + i].max(coordinate);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn expand_to_include_bbox(&mut self, other: &[f64]) {
+        match *other {
+            [min_x, min_y, max_x, max_y] => {
+                self.expand_to_include_position(min_x, min_y, None);
+                self.expand_to_include_position(max_x, max_y, None);
+            }
+            [min_x, min_y, min_z, max_x, max_y, max_z] => {
+                self.expand_to_include_position(min_x, min_y, Some(min_z));
+                self.expand_to_include_position(max_x, max_y, Some(max_z));
+            }
+            _ => {}
+        }
+    }
+
+    fn to_polygon(&self) -> Value {
+        match extent_2d(self) {
+            None => Value::Polygon(vec![]),
+            Some([west, south, east, north]) if west > east => Value::MultiPolygon(vec![
+                vec![extent_ring([west, south, 180., north])],
+                vec![extent_ring([-180., south, east, north])],
+            ]),
+            Some(extent) => Value::Polygon(vec![extent_ring(extent)]),
+        }
+    }
+}
+
+/// Computes the smallest 2D bounding box containing all of `boxes`.
+///
+/// Both 4- and 6-element boxes are accepted, but the result always has the 4-element
+/// `[min x, min y, max x, max y]` form. Boxes of any other length are ignored. Returns `None` if
+/// there are no valid boxes.
+///
+/// ```
+/// use geojson::bbox_union;
+///
+/// let boxes = vec![vec![0., 0., 1., 1.], vec![-1., 0.5, 0., 2., 2., 10.]];
+/// assert_eq!(bbox_union(boxes), Some(vec![-1., 0., 2., 2.]));
+/// assert_eq!(bbox_union(vec![]), None);
+/// ```
+pub fn bbox_union(boxes: impl IntoIterator<Item = Bbox>) -> Option<Bbox> {
+    boxes
+        .into_iter()
+        .filter_map(|bbox| extent_2d(&bbox))
+        .fold(None, |union: Option<[f64; 4]>, extent| {
+            Some(match union {
+                None => extent,
+                Some(union) => [
+                    union[0].min(extent[0]),
+                    union[1].min(extent[1]),
+                    union[2].max(extent[2]),
+                    union[3].max(extent[3]),
+                ],
+            })
+        })
+        .map(|union| union.to_vec())
+}
+
+/// Unions bounding boxes as they are collected or extended into it
+///
+/// Boxes are combined with [`BboxExt::expand_to_include_bbox`](trait.BboxExt.html), so the
+/// result takes the dimensionality of the first box and boxes of invalid length are ignored.
+///
+/// ```
+/// use geojson::{BboxAccumulator, Geometry, Value};
+///
+/// let geometries = vec![
+///     Geometry::new(Value::Point(vec![1., 2.])),
+///     Geometry::new(Value::Point(vec![-3., 4.])),
+/// ];
+/// let extent: BboxAccumulator = geometries
+///     .iter()
+///     .filter_map(|geometry| geometry.bbox_calculated())
+///     .collect();
+/// assert_eq!(extent.into_bbox(), Some(vec![-3., 2., 1., 4.]));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BboxAccumulator {
+    bbox: Bbox,
+}
+
+impl BboxAccumulator {
+    /// An accumulator with no boxes added yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `bbox` to the union
+    pub fn add(&mut self, bbox: &[f64]) {
+        self.bbox.expand_to_include_bbox(bbox);
+    }
+
+    /// The union of every box added so far, or `None` if there were no valid boxes
+    pub fn bbox(&self) -> Option<&Bbox> {
+        Some(&self.bbox).filter(|bbox| !bbox.is_empty())
+    }
+
+    /// Consume the accumulator, returning the union as for [`bbox`](#method.bbox)
+    pub fn into_bbox(self) -> Option<Bbox> {
+        Some(self.bbox).filter(|bbox| !bbox.is_empty())
+    }
+}
+
+impl Extend<Bbox> for BboxAccumulator {
+    fn extend<I: IntoIterator<Item = Bbox>>(&mut self, boxes: I) {
+        for bbox in boxes {
+            self.add(&bbox);
+        }
+    }
+}
+
+impl FromIterator<Bbox> for BboxAccumulator {
+    fn from_iter<I: IntoIterator<Item = Bbox>>(boxes: I) -> Self {
+        let mut accumulator = Self::new();
+        accumulator.extend(boxes);
+        accumulator
+    }
+}
+
+impl Sum<Bbox> for BboxAccumulator {
+    fn sum<I: Iterator<Item = Bbox>>(boxes: I) -> Self {
+        boxes.collect()
+    }
+}
+
+/// Adds [`collect_bbox`](#tymethod.collect_bbox) to iterators of bounding boxes
+///
+/// ```
+/// use geojson::{Bbox, BboxIteratorExt};
+///
+/// let boxes = vec![vec![0., 0., 1., 1.], vec![2., -1., 3., 0.5]];
+/// assert_eq!(boxes.into_iter().collect_bbox(), Some(vec![0., -1., 3., 1.]));
+/// assert_eq!(Vec::<Bbox>::new().into_iter().collect_bbox(), None);
+/// ```
+pub trait BboxIteratorExt: Iterator<Item = Bbox> + Sized {
+    /// The union of every box, or `None` if there were no valid boxes
+    fn collect_bbox(self) -> Option<Bbox> {
+        self.collect::<BboxAccumulator>().into_bbox()
+    }
+}
+
+impl<I: Iterator<Item = Bbox>> BboxIteratorExt for I {}
+
+/// Computes the 2D bounding box `[min x, min y, max x, max y]` of `positions`.
+///
+/// Returns `None` if there are no positions.
+///
+/// ```
+/// use geojson::bbox_of_positions;
+///
+/// let positions = vec![[1., 5.], [-2., 3.], [4., -1.]];
+/// assert_eq!(bbox_of_positions(positions), Some(vec![-2., -1., 4., 5.]));
+/// ```
+pub fn bbox_of_positions(positions: impl IntoIterator<Item = [f64; 2]>) -> Option<Bbox> {
+    bbox_union(positions.into_iter().map(|[x, y]| vec![x, y, x, y]))
+}
+
+/// The form of a calculated bounding box
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BboxDimension {
+    /// A 6-element 3D box if every position has a z coordinate, and a 4-element 2D box otherwise
+    #[default]
+    Auto,
+    /// Always a 4-element 2D box, ignoring any z coordinates
+    Force2D,
+    /// Always a 6-element 3D box. Its z range covers the positions which have a z coordinate,
+    /// and is `0.0` to `0.0` if none do.
+    Force3D,
+}
+
+/// Computes the bounding box of `positions` in the form given by `dimension`
+fn bbox_of<'a>(
+    positions: impl Iterator<Item = &'a Position>,
+    dimension: BboxDimension,
+) -> Option<Bbox> {
+    let mut extent = Extent::default();
+    positions.for_each(|position| extent.add(position));
+    extent.bbox(dimension)
+}
+
+/// The running extent of a sequence of positions, for calculating a bounding box in the same
+/// pass as other work
+pub(crate) struct Extent {
+    min: [f64; 3],
+    max: [f64; 3],
+    dims: usize,
+    empty: bool,
+}
+
+impl Default for Extent {
+    fn default() -> Self {
+        Extent {
+            min: [f64::INFINITY; 3],
+            max: [f64::NEG_INFINITY; 3],
+            dims: 3,
+            empty: true,
+        }
+    }
+}
+
+impl Extent {
+    pub(crate) fn add(&mut self, position: &Position) {
+        self.dims = self.dims.min(position.len());
+        for (i, &coordinate) in position.iter().take(3).enumerate() {
+            self.min[i] = self.min[i].min(coordinate);
+            self.max[i] = self.max[i].max(coordinate);
+        }
+        self.empty = false;
+    }
+
+    /// The bounding box of the positions added so far, or `None` if there are none or any of
+    /// them has fewer than two coordinates
+    pub(crate) fn bbox(&self, dimension: BboxDimension) -> Option<Bbox> {
+        let (min, max) = (self.min, self.max);
+        if self.empty || self.dims < 2 {
+            return None;
+        }
+        match (dimension, self.dims) {
+            (BboxDimension::Force2D, _) | (BboxDimension::Auto, 2) => {
+                Some(vec![min[0], min[1], max[0], max[1]])
+            }
+            (BboxDimension::Force3D, _) | (BboxDimension::Auto, _) => {
+                let (min_z, max_z) = if min[2].is_finite() {
+                    (min[2], max[2])
+                } else {
+                    (0., 0.)
+                };
+                Some(vec![min[0], min[1], min_z, max[0], max[1], max_z])
+            }
+        }
+    }
+}
+
+impl Value {
+    /// Computes the bounding box of all of the value's positions.
+    ///
+    /// The result has the 6-element form if every position has a z coordinate, and the 4-element
+    /// form otherwise. Returns `None` if there are no positions.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let value = Value::LineString(vec![vec![1.0, 2.0], vec![-1.0, 4.0]]);
+    /// assert_eq!(value.bbox_calculated(), Some(vec![-1.0, 2.0, 1.0, 4.0]));
+    /// ```
+    pub fn bbox_calculated(&self) -> Option<Bbox> {
+        self.bbox_calculated_with(BboxDimension::Auto)
+    }
+
+    /// Computes the bounding box of all of the value's positions, in the form given by
+    /// `dimension`.
+    ///
+    /// ```
+    /// use geojson::{BboxDimension, Value};
+    ///
+    /// let value = Value::LineString(vec![vec![1.0, 2.0, 10.0], vec![-1.0, 4.0, 20.0]]);
+    /// assert_eq!(
+    ///     value.bbox_calculated_with(BboxDimension::Force2D),
+    ///     Some(vec![-1.0, 2.0, 1.0, 4.0])
+    /// );
+    /// ```
+    pub fn bbox_calculated_with(&self, dimension: BboxDimension) -> Option<Bbox> {
+        bbox_of(self.coords_iter(), dimension)
+    }
+
+    /// The 2D bounding box of the value's positions as a closed `Polygon`, or `None` if there
+    /// are no positions
+    ///
+    /// See [`BboxExt::to_polygon`](trait.BboxExt.html#tymethod.to_polygon).
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let line = Value::LineString(vec![vec![1., 2.], vec![3., 0.]]);
+    /// assert_eq!(
+    ///     line.bbox_polygon(),
+    ///     Some(Value::Polygon(vec![vec![
+    ///         vec![1., 0.],
+    ///         vec![3., 0.],
+    ///         vec![3., 2.],
+    ///         vec![1., 2.],
+    ///         vec![1., 0.],
+    ///     ]]))
+    /// );
+    /// ```
+    pub fn bbox_polygon(&self) -> Option<Value> {
+        self.bbox_calculated_with(BboxDimension::Force2D)
+            .map(|bbox| bbox.to_polygon())
+    }
+}
+
+impl Geometry {
+    /// Returns a geometry of `value` with its `bbox` set to the calculated bounding box
+    ///
+    /// See [`Value::bbox_calculated`](enum.Value.html#method.bbox_calculated).
+    ///
+    /// ```
+    /// use geojson::{Geometry, Value};
+    ///
+    /// let geometry = Geometry::with_bbox(Value::LineString(vec![vec![0., 1.], vec![2., -1.]]));
+    /// assert_eq!(geometry.bbox, Some(vec![0., -1., 2., 1.]));
+    /// ```
+    pub fn with_bbox(value: Value) -> Self {
+        let bbox = value.bbox_calculated();
+        Geometry {
+            bbox,
+            value,
+            foreign_members: None,
+        }
+    }
+
+    /// Computes the bounding box of the geometry's `value`, ignoring its current `bbox`.
+    ///
+    /// See [`Value::bbox_calculated`](enum.Value.html#method.bbox_calculated).
+    pub fn bbox_calculated(&self) -> Option<Bbox> {
+        self.value.bbox_calculated()
+    }
+
+    /// Computes the bounding box of the geometry's `value` in the form given by `dimension`
+    pub fn bbox_calculated_with(&self, dimension: BboxDimension) -> Option<Bbox> {
+        self.value.bbox_calculated_with(dimension)
+    }
+}
+
+impl Feature {
+    /// Computes the bounding box of the feature's geometry, ignoring any current `bbox`.
+    /// Returns `None` if the geometry is `null` or has no positions.
+    ///
+    /// See [`Value::bbox_calculated`](enum.Value.html#method.bbox_calculated).
+    ///
+    /// ```
+    /// use geojson::{Feature, Geometry, Value};
+    ///
+    /// let mut feature = Feature::with_null_geometry();
+    /// assert_eq!(feature.bbox_calculated(), None);
+    ///
+    /// feature.geometry = Some(Geometry::new(Value::Point(vec![1.0, 2.0, 3.0])));
+    /// assert_eq!(feature.bbox_calculated(), Some(vec![1.0, 2.0, 3.0, 1.0, 2.0, 3.0]));
+    /// ```
+    pub fn bbox_calculated(&self) -> Option<Bbox> {
+        self.bbox_calculated_with(BboxDimension::Auto)
+    }
+
+    /// Computes the bounding box of the feature's geometry in the form given by `dimension`
+    pub fn bbox_calculated_with(&self, dimension: BboxDimension) -> Option<Bbox> {
+        self.geometry.as_ref()?.bbox_calculated_with(dimension)
+    }
+
+    /// Returns a feature of `geometry`, without an `id` or properties, with its `bbox` set to
+    /// the calculated bounding box of the geometry
+    ///
+    /// See [`Value::bbox_calculated`](enum.Value.html#method.bbox_calculated). The geometry is
+    /// stored unchanged.
+    pub fn with_computed_bbox(geometry: Geometry) -> Self {
+        Feature {
+            bbox: geometry.bbox_calculated(),
+            geometry: Some(geometry),
+            id: None,
+            properties: None,
+            foreign_members: None,
+        }
+    }
+
+    /// Replace the feature's geometry, keeping its `bbox` in step
+    ///
+    /// A feature with a `bbox` gets one calculated from the new geometry, in 2D if the old one
+    /// was, or none if the new geometry is `None` or has no positions. A feature without a
+    /// `bbox` is left without one. The new geometry's own `bbox` is kept as given.
+    ///
+    /// ```
+    /// use geojson::{Feature, Geometry, Value};
+    ///
+    /// let mut feature = Feature::with_computed_bbox(Geometry::new(Value::Point(vec![1., 2.])));
+    /// feature.set_geometry(Some(Geometry::new(Value::Point(vec![3., 4.]))));
+    /// assert_eq!(feature.bbox, Some(vec![3., 4., 3., 4.]));
+    ///
+    /// feature.set_geometry(None);
+    /// assert_eq!(feature.bbox, None);
+    /// ```
+    pub fn set_geometry(&mut self, geometry: Option<Geometry>) {
+        if let Some(bbox) = &self.bbox {
+            let dimension = match bbox.len() {
+                4 => BboxDimension::Force2D,
+                _ => BboxDimension::Auto,
+            };
+            self.bbox = geometry
+                .as_ref()
+                .and_then(|geometry| geometry.bbox_calculated_with(dimension));
+        }
+        self.geometry = geometry;
+    }
+
+    /// Remove the `bbox` of the feature, its geometry, and any members of a
+    /// `GeometryCollection` geometry, so that none is left stale by later edits
+    pub fn clear_bbox(&mut self) {
+        fn clear(geometry: &mut Geometry) {
+            geometry.bbox = None;
+            geometry.value.geometries_mut().iter_mut().for_each(clear);
+        }
+        self.bbox = None;
+        if let Some(geometry) = &mut self.geometry {
+            clear(geometry);
+        }
+    }
+}
+
+impl FeatureCollection {
+    /// Append `feature` to the collection.
+    ///
+    /// If the collection has a `bbox`, it is grown to include the feature's `bbox`, or the
+    /// calculated bounding box of its geometry if it has none.
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection, Geometry, Value};
+    ///
+    /// let mut collection = FeatureCollection {
+    ///     bbox: Some(vec![0.0, 0.0, 1.0, 1.0]),
+    ///     features: vec![],
+    ///     foreign_members: None,
+    /// };
+    /// collection.push(Feature {
+    ///     bbox: None,
+    ///     geometry: Some(Geometry::new(Value::Point(vec![2.0, -1.0]))),
+    ///     id: None,
+    ///     properties: None,
+    ///     foreign_members: None,
+    /// });
+    /// assert_eq!(collection.bbox, Some(vec![0.0, -1.0, 2.0, 1.0]));
+    /// ```
+    pub fn push(&mut self, feature: Feature) {
+        if let Some(bbox) = self.bbox.as_mut() {
+            let extent = match &feature.bbox {
+                Some(feature_bbox) => Some(feature_bbox.clone()),
+                None => feature
+                    .geometry
+                    .as_ref()
+                    .and_then(Geometry::bbox_calculated),
+            };
+            if let Some(extent) = extent {
+                bbox.expand_to_include_bbox(&extent);
+            }
+        }
+        self.features.push(feature);
+    }
+
+    /// Computes the bounding box of every feature's geometry, ignoring any `bbox` the collection
+    /// or its features currently have.
+    ///
+    /// See [`Value::bbox_calculated`](enum.Value.html#method.bbox_calculated).
+    pub fn bbox_calculated(&self) -> Option<Bbox> {
+        self.bbox_calculated_with(BboxDimension::Auto)
+    }
+
+    /// Computes the bounding box of every feature's geometry in the form given by `dimension`
+    pub fn bbox_calculated_with(&self, dimension: BboxDimension) -> Option<Bbox> {
+        bbox_of(self.positions(), dimension)
+    }
+
+    /// Replace a stale `bbox` with one calculated from the features' geometries, e.g. after
+    /// editing their coordinates
+    ///
+    /// Nothing is done if the collection has no `bbox`. A 4-element box is recalculated in 2D,
+    /// and any other as by [`bbox_calculated`](#method.bbox_calculated). If the features have
+    /// no positions the `bbox` is removed.
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection, Geometry, Value};
+    ///
+    /// let feature = Feature::with_computed_bbox(Geometry::new(Value::Point(vec![1., 2., 3.])));
+    /// let mut collection = FeatureCollection::from(vec![feature]);
+    /// collection.bbox = Some(vec![0., 0., 1., 1.]);
+    /// assert!(!collection.bbox_is_consistent());
+    ///
+    /// collection.recompute_bbox();
+    /// assert_eq!(collection.bbox, Some(vec![1., 2., 1., 2.]));
+    /// assert!(collection.bbox_is_consistent());
+    /// ```
+    pub fn recompute_bbox(&mut self) {
+        if let Some(bbox) = &self.bbox {
+            let dimension = match bbox.len() {
+                4 => BboxDimension::Force2D,
+                _ => BboxDimension::Auto,
+            };
+            self.bbox = self.bbox_calculated_with(dimension);
+        }
+    }
+
+    /// Whether the collection's `bbox`, if it has one, contains every position of the features'
+    /// geometries, for catching boxes left stale by edits
+    ///
+    /// The box needn't be the smallest that would do. A 6-element box must also contain the
+    /// z coordinate of every position which has one. A box whose minimum longitude exceeds its
+    /// maximum is taken to cross the antimeridian, as RFC 7946 § 5.2 describes. Boxes of any
+    /// other length than 4 or 6 are never consistent, and a collection without a `bbox` always
+    /// is.
+    pub fn bbox_is_consistent(&self) -> bool {
+        let bbox = match &self.bbox {
+            Some(bbox) => bbox,
+            None => return true,
+        };
+        let [west, south, east, north] = match extent_2d(bbox) {
+            Some(extent) => extent,
+            None => return false,
+        };
+        let z_range = match bbox[..] {
+            [_, _, min_z, _, _, max_z] => Some((min_z, max_z)),
+            _ => None,
+        };
+        self.positions().all(|position| {
+            let (x, y) = match position[..] {
+                [x, y, ..] => (x, y),
+                _ => return true,
+            };
+            let x_inside = if west <= east {
+                (west..=east).contains(&x)
+            } else {
+                x >= west || x <= east
+            };
+            let z_inside = match (z_range, position.get(2)) {
+                (Some((min_z, max_z)), Some(z)) => (min_z..=max_z).contains(z),
+                _ => true,
+            };
+            x_inside && (south..=north).contains(&y) && z_inside
+        })
+    }
+
+    fn positions(&self) -> impl Iterator<Item = &Position> {
+        self.features
+            .iter()
+            .filter_map(|feature| feature.geometry.as_ref())
+            .flat_map(|geometry| geometry.value.coords_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bbox_of_positions, bbox_union, BboxAccumulator, BboxDimension, BboxExt, BboxIteratorExt,
+    };
+    use crate::{Bbox, Feature, FeatureCollection, Geometry, Value};
+
+    #[test]
+    fn union_of_nothing() {
+        assert_eq!(bbox_union(vec![]), None);
+        assert_eq!(bbox_of_positions(vec![]), None);
+    }
+
+    #[test]
+    fn accumulate_boxes() {
+        let mut accumulator: BboxAccumulator = vec![vec![0., 0., 0., 1., 1., 1.]].into_iter().sum();
+        assert_eq!(accumulator.bbox(), Some(&vec![0., 0., 0., 1., 1., 1.]));
+        accumulator.extend(vec![vec![1., 2.], vec![-1., -1., 0.5, 0.5]]);
+        assert_eq!(
+            accumulator.into_bbox(),
+            Some(vec![-1., -1., 0., 1., 1., 1.])
+        );
+
+        assert_eq!(BboxAccumulator::new().bbox(), None);
+        assert_eq!(vec![vec![1., 2., 3.]].into_iter().collect_bbox(), None);
+    }
+
+    #[test]
+    fn constructors_with_bbox() {
+        let value = Value::MultiPoint(vec![vec![0., 0., 1.], vec![2., 3., -1.]]);
+        let geometry = Geometry::with_bbox(value.clone());
+        assert_eq!(geometry.bbox, Some(vec![0., 0., -1., 2., 3., 1.]));
+
+        let feature = Feature::with_computed_bbox(Geometry::new(value));
+        assert_eq!(feature.bbox, geometry.bbox);
+        assert_eq!(feature.geometry.unwrap().bbox, None);
+        assert_eq!(
+            Feature::with_computed_bbox(Geometry::new(Value::GeometryCollection(vec![]))).bbox,
+            None
+        );
+    }
+
+    #[test]
+    fn union_ignores_invalid_boxes() {
+        assert_eq!(bbox_union(vec![vec![1., 2., 3.]]), None);
+        assert_eq!(
+            bbox_union(vec![vec![1., 2., 3.], vec![0., 0., 1., 1.]]),
+            Some(vec![0., 0., 1., 1.])
+        );
+    }
+
+    #[test]
+    fn union_of_3d_boxes_is_2d() {
+        assert_eq!(
+            bbox_union(vec![vec![0., 0., -5., 1., 1., 5.]]),
+            Some(vec![0., 0., 1., 1.])
+        );
+    }
+
+    #[test]
+    fn single_position() {
+        assert_eq!(
+            bbox_of_positions(vec![[1., 2.]]),
+            Some(vec![1., 2., 1., 2.])
+        );
+    }
+
+    #[test]
+    fn bbox_calculated_dimensions() {
+        let value = Value::MultiPoint(vec![vec![0., 1., 2.], vec![3., -4., 5.]]);
+        assert_eq!(value.bbox_calculated(), Some(vec![0., -4., 2., 3., 1., 5.]));
+
+        let mixed = Value::MultiPoint(vec![vec![0., 1., 2.], vec![3., -4.]]);
+        assert_eq!(mixed.bbox_calculated(), Some(vec![0., -4., 3., 1.]));
+
+        assert_eq!(Value::MultiPoint(vec![]).bbox_calculated(), None);
+        assert_eq!(Value::Point(vec![1.]).bbox_calculated(), None);
+    }
+
+    #[test]
+    fn feature_bbox_calculated() {
+        let mut feature = Feature::with_null_geometry();
+        feature.bbox = Some(vec![0., 0., 9., 9.]);
+        assert_eq!(feature.bbox_calculated(), None);
+
+        feature.geometry = Some(Geometry::new(Value::LineString(vec![])));
+        assert_eq!(feature.bbox_calculated(), None);
+
+        feature.geometry = Some(Geometry::new(Value::LineString(vec![
+            vec![1., 2., 3.],
+            vec![-1., 0., 5.],
+        ])));
+        assert_eq!(
+            feature.bbox_calculated(),
+            Some(vec![-1., 0., 3., 1., 2., 5.])
+        );
+        assert_eq!(
+            feature.bbox_calculated_with(BboxDimension::Force2D),
+            Some(vec![-1., 0., 1., 2.])
+        );
+    }
+
+    #[test]
+    fn feature_collection_bbox_calculated() {
+        let feature = |value: Option<Value>| Feature {
+            bbox: None,
+            geometry: value.map(Geometry::new),
+            id: None,
+            properties: None,
+            foreign_members: None,
+        };
+        let collection = FeatureCollection {
+            bbox: None,
+            features: vec![
+                feature(Some(Value::Point(vec![1., 1.]))),
+                feature(None),
+                feature(Some(Value::Point(vec![-1., 3.]))),
+            ],
+            foreign_members: None,
+        };
+        assert_eq!(collection.bbox_calculated(), Some(vec![-1., 1., 1., 3.]));
+    }
+
+    #[test]
+    fn expand_3d_bbox() {
+        let mut bbox = Bbox::new();
+        bbox.expand_to_include_position(1., 1., Some(5.));
+        bbox.expand_to_include_position(2., 0., None);
+        assert_eq!(bbox, vec![1., 0., 5., 2., 1., 5.]);
+
+        bbox.expand_to_include_bbox(&[0., 0., -1., 1., 1., 1.]);
+        assert_eq!(bbox, vec![0., 0., -1., 2., 1., 5.]);
+
+        // Malformed boxes are left alone, and ignored when added
+        let mut malformed = vec![1.];
+        malformed.expand_to_include_bbox(&bbox);
+        assert_eq!(malformed, vec![1.]);
+        bbox.expand_to_include_bbox(&[100.]);
+        assert_eq!(bbox, vec![0., 0., -1., 2., 1., 5.]);
+    }
+
+    #[test]
+    fn push_without_bbox() {
+        let mut collection = FeatureCollection::from(vec![]);
+        collection.push(Feature {
+            bbox: Some(vec![0., 0., 1., 1.]),
+            geometry: None,
+            id: None,
+            properties: None,
+            foreign_members: None,
+        });
+        assert_eq!(collection.bbox, None);
+        assert_eq!(collection.features.len(), 1);
+    }
+
+    #[test]
+    fn bbox_calculated_with_dimension() {
+        let mixed = Value::MultiPoint(vec![vec![0., 1., 5.], vec![3., -4.]]);
+        assert_eq!(
+            mixed.bbox_calculated_with(BboxDimension::Auto),
+            Some(vec![0., -4., 3., 1.])
+        );
+        assert_eq!(
+            mixed.bbox_calculated_with(BboxDimension::Force3D),
+            Some(vec![0., -4., 5., 3., 1., 5.])
+        );
+
+        let flat = Value::Point(vec![1., 2.]);
+        assert_eq!(
+            flat.bbox_calculated_with(BboxDimension::Force3D),
+            Some(vec![1., 2., 0., 1., 2., 0.])
+        );
+        assert_eq!(
+            Value::Point(vec![1.]).bbox_calculated_with(BboxDimension::Force2D),
+            None
+        );
+    }
+
+    #[test]
+    fn bbox_consistency() {
+        let feature = |x: f64, z: f64| {
+            Feature::with_computed_bbox(Geometry::new(Value::Point(vec![x, 0., z])))
+        };
+        let mut collection = FeatureCollection::from(vec![feature(170., 5.), feature(-175., 10.)]);
+        assert!(collection.bbox_is_consistent());
+
+        collection.bbox = Some(vec![160., -1., -170., 1.]);
+        assert!(collection.bbox_is_consistent());
+        collection.bbox = Some(vec![-180., -1., 0., 5., 180., 1., 10.]);
+        assert!(!collection.bbox_is_consistent());
+        collection.bbox = Some(vec![-180., -1., 0., 180., 1., 9.]);
+        assert!(!collection.bbox_is_consistent());
+
+        collection.recompute_bbox();
+        assert_eq!(collection.bbox, Some(vec![-175., 0., 5., 170., 0., 10.]));
+        assert!(collection.bbox_is_consistent());
+
+        collection.features.clear();
+        collection.recompute_bbox();
+        assert_eq!(collection.bbox, None);
+        collection.recompute_bbox();
+        assert_eq!(collection.bbox, None);
+    }
+
+    #[test]
+    fn set_geometry_keeps_bbox_form() {
+        let mut feature = Feature::with_null_geometry();
+        feature.set_geometry(Some(Geometry::new(Value::Point(vec![1., 2., 3.]))));
+        assert_eq!(feature.bbox, None);
+
+        feature.bbox = Some(vec![0., 0., 1., 1.]);
+        let line = Value::LineString(vec![vec![1., 2., 3.], vec![4., 5., 6.]]);
+        feature.set_geometry(Some(Geometry::new(line.clone())));
+        assert_eq!(feature.bbox, Some(vec![1., 2., 4., 5.]));
+
+        feature.bbox = Some(vec![0.; 6]);
+        let mut geometry =
+            Geometry::new(Value::GeometryCollection(vec![Geometry::with_bbox(line)]));
+        geometry.bbox = Some(vec![0.; 4]);
+        feature.set_geometry(Some(geometry));
+        assert_eq!(feature.bbox, Some(vec![1., 2., 3., 4., 5., 6.]));
+        assert_eq!(feature.geometry.as_ref().unwrap().bbox, Some(vec![0.; 4]));
+
+        feature.clear_bbox();
+        let geometry = feature.geometry.as_ref().unwrap();
+        assert_eq!(feature.bbox, None);
+        assert_eq!(geometry.bbox, None);
+        assert_eq!(geometry.value.geometries()[0].bbox, None);
+    }
+
+    #[test]
+    fn bbox_to_polygon() {
+        let ring = |[min_x, min_y, max_x, max_y]: [f64; 4]| {
+            vec![
+                vec![min_x, min_y],
+                vec![max_x, min_y],
+                vec![max_x, max_y],
+                vec![min_x, max_y],
+                vec![min_x, min_y],
+            ]
+        };
+        assert_eq!(
+            vec![0., 1., 5., 2., 3., 9.].to_polygon(),
+            Value::Polygon(vec![ring([0., 1., 2., 3.])])
+        );
+        assert_eq!(
+            vec![170., -5., -160., 5.].to_polygon(),
+            Value::MultiPolygon(vec![
+                vec![ring([170., -5., 180., 5.])],
+                vec![ring([-180., -5., -160., 5.])],
+            ])
+        );
+        assert_eq!(vec![1., 2.].to_polygon(), Value::Polygon(vec![]));
+
+        let points = Value::MultiPoint(vec![vec![3., 4., 1.], vec![-1., 0., 2.]]);
+        assert_eq!(
+            points.bbox_polygon(),
+            Some(Value::Polygon(vec![ring([-1., 0., 3., 4.])]))
+        );
+        assert_eq!(Value::GeometryCollection(vec![]).bbox_polygon(), None);
+    }
+}
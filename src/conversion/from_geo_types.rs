@@ -1,13 +1,11 @@
-use crate::geo_types;
-
 use crate::geometry;
 
-use crate::{LineStringType, PointType, PolygonType};
+use crate::{Bbox, LineStringType, PointType, PolygonType};
 use num_traits::Float;
 use std::convert::From;
 
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
-impl<'a, T> From<&'a geo_types::Point<T>> for geometry::Value
+impl<T> From<&geo_types::Point<T>> for geometry::Value
 where
     T: Float,
 {
@@ -19,7 +17,7 @@ where
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
-impl<'a, T> From<&'a geo_types::MultiPoint<T>> for geometry::Value
+impl<T> From<&geo_types::MultiPoint<T>> for geometry::Value
 where
     T: Float,
 {
@@ -35,7 +33,7 @@ where
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
-impl<'a, T> From<&'a geo_types::LineString<T>> for geometry::Value
+impl<T> From<&geo_types::LineString<T>> for geometry::Value
 where
     T: Float,
 {
@@ -47,7 +45,7 @@ where
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
-impl<'a, T> From<&'a geo_types::Line<T>> for geometry::Value
+impl<T> From<&geo_types::Line<T>> for geometry::Value
 where
     T: Float,
 {
@@ -59,7 +57,7 @@ where
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
-impl<'a, T> From<&'a geo_types::Triangle<T>> for geometry::Value
+impl<T> From<&geo_types::Triangle<T>> for geometry::Value
 where
     T: Float,
 {
@@ -71,7 +69,7 @@ where
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
-impl<'a, T> From<&'a geo_types::Rect<T>> for geometry::Value
+impl<T> From<&geo_types::Rect<T>> for geometry::Value
 where
     T: Float,
 {
@@ -83,7 +81,7 @@ where
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
-impl<'a, T> From<&'a geo_types::MultiLineString<T>> for geometry::Value
+impl<T> From<&geo_types::MultiLineString<T>> for geometry::Value
 where
     T: Float,
 {
@@ -95,7 +93,7 @@ where
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
-impl<'a, T> From<&'a geo_types::Polygon<T>> for geometry::Value
+impl<T> From<&geo_types::Polygon<T>> for geometry::Value
 where
     T: Float,
 {
@@ -107,7 +105,7 @@ where
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
-impl<'a, T> From<&'a geo_types::MultiPolygon<T>> for geometry::Value
+impl<T> From<&geo_types::MultiPolygon<T>> for geometry::Value
 where
     T: Float,
 {
@@ -119,7 +117,7 @@ where
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
-impl<'a, T> From<&'a geo_types::GeometryCollection<T>> for geometry::Value
+impl<T> From<&geo_types::GeometryCollection<T>> for geometry::Value
 where
     T: Float,
 {
@@ -160,6 +158,28 @@ where
     }
 }
 
+/// Create a 2D `Bbox` of `[min x, min y, max x, max y]` from a `geo_types::Rect`
+///
+/// ```
+/// use geojson::bbox_from_rect;
+///
+/// let rect = geo_types::Rect::new((3.0, 4.0), (1.0, 2.0));
+/// assert_eq!(bbox_from_rect(&rect), vec![1.0, 2.0, 3.0, 4.0]);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
+pub fn bbox_from_rect<T>(rect: &geo_types::Rect<T>) -> Bbox
+where
+    T: Float,
+{
+    let (min, max) = (rect.min(), rect.max());
+    vec![
+        min.x.to_f64().unwrap(),
+        min.y.to_f64().unwrap(),
+        max.x.to_f64().unwrap(),
+        max.y.to_f64().unwrap(),
+    ]
+}
+
 fn create_point_type<T>(point: &geo_types::Point<T>) -> PointType
 where
     T: Float,
@@ -231,7 +251,7 @@ where
         polygon
             .interiors()
             .iter()
-            .map(|line_string| create_line_string_type(&line_string)),
+            .map(|line_string| create_line_string_type(line_string)),
     );
 
     coords
@@ -244,14 +264,13 @@ where
     multi_polygon
         .0
         .iter()
-        .map(|polygon| create_polygon_type(&polygon))
+        .map(|polygon| create_polygon_type(polygon))
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{Geometry, Value};
-    use geo_types;
     use geo_types::{
         Coordinate, GeometryCollection, Line, LineString, MultiLineString, MultiPoint,
         MultiPolygon, Point, Polygon, Rect, Triangle,
@@ -338,9 +357,9 @@ mod tests {
 
     #[test]
     fn geo_triangle_conversion_test() {
-        let c1 = Coordinate { x: 0., y: 0. };
-        let c2 = Coordinate { x: 10., y: 20. };
-        let c3 = Coordinate { x: 20., y: -10. };
+        let c1: Coordinate<f64> = Coordinate { x: 0., y: 0. };
+        let c2: Coordinate<f64> = Coordinate { x: 10., y: 20. };
+        let c3: Coordinate<f64> = Coordinate { x: 20., y: -10. };
 
         let triangle = Triangle(c1, c2, c3);
 
@@ -348,14 +367,14 @@ mod tests {
 
         // Geo-types Polygon construction introduces an extra vertex: let's check it!
         if let Value::Polygon(c) = geojson_polygon {
-            assert_almost_eq!(c1.x as f64, c[0][0][0], 1e-6);
-            assert_almost_eq!(c1.y as f64, c[0][0][1], 1e-6);
-            assert_almost_eq!(c2.x as f64, c[0][1][0], 1e-6);
-            assert_almost_eq!(c2.y as f64, c[0][1][1], 1e-6);
-            assert_almost_eq!(c3.x as f64, c[0][2][0], 1e-6);
-            assert_almost_eq!(c3.y as f64, c[0][2][1], 1e-6);
-            assert_almost_eq!(c1.x as f64, c[0][3][0], 1e-6);
-            assert_almost_eq!(c1.y as f64, c[0][3][1], 1e-6);
+            assert_almost_eq!(c1.x, c[0][0][0], 1e-6);
+            assert_almost_eq!(c1.y, c[0][0][1], 1e-6);
+            assert_almost_eq!(c2.x, c[0][1][0], 1e-6);
+            assert_almost_eq!(c2.y, c[0][1][1], 1e-6);
+            assert_almost_eq!(c3.x, c[0][2][0], 1e-6);
+            assert_almost_eq!(c3.y, c[0][2][1], 1e-6);
+            assert_almost_eq!(c1.x, c[0][3][0], 1e-6);
+            assert_almost_eq!(c1.y, c[0][3][1], 1e-6);
         } else {
             panic!("Not valid geometry {:?}", geojson_polygon);
         }
@@ -363,8 +382,8 @@ mod tests {
 
     #[test]
     fn geo_rect_conversion_test() {
-        let c1 = Coordinate { x: 0., y: 0. };
-        let c2 = Coordinate { x: 10., y: 20. };
+        let c1: Coordinate<f64> = Coordinate { x: 0., y: 0. };
+        let c2: Coordinate<f64> = Coordinate { x: 10., y: 20. };
 
         let rect = Rect::new(c1, c2);
 
@@ -372,16 +391,16 @@ mod tests {
 
         // Geo-types Polygon construction introduces an extra vertex: let's check it!
         if let Value::Polygon(c) = geojson_polygon {
-            assert_almost_eq!(c1.x as f64, c[0][0][0], 1e-6);
-            assert_almost_eq!(c1.y as f64, c[0][0][1], 1e-6);
-            assert_almost_eq!(c1.x as f64, c[0][1][0], 1e-6);
-            assert_almost_eq!(c2.y as f64, c[0][1][1], 1e-6);
-            assert_almost_eq!(c2.x as f64, c[0][2][0], 1e-6);
-            assert_almost_eq!(c2.y as f64, c[0][2][1], 1e-6);
-            assert_almost_eq!(c2.x as f64, c[0][3][0], 1e-6);
-            assert_almost_eq!(c1.y as f64, c[0][3][1], 1e-6);
-            assert_almost_eq!(c1.x as f64, c[0][4][0], 1e-6);
-            assert_almost_eq!(c1.y as f64, c[0][4][1], 1e-6);
+            assert_almost_eq!(c1.x, c[0][0][0], 1e-6);
+            assert_almost_eq!(c1.y, c[0][0][1], 1e-6);
+            assert_almost_eq!(c1.x, c[0][1][0], 1e-6);
+            assert_almost_eq!(c2.y, c[0][1][1], 1e-6);
+            assert_almost_eq!(c2.x, c[0][2][0], 1e-6);
+            assert_almost_eq!(c2.y, c[0][2][1], 1e-6);
+            assert_almost_eq!(c2.x, c[0][3][0], 1e-6);
+            assert_almost_eq!(c1.y, c[0][3][1], 1e-6);
+            assert_almost_eq!(c1.x, c[0][4][0], 1e-6);
+            assert_almost_eq!(c1.y, c[0][4][1], 1e-6);
         } else {
             panic!("Not valid geometry {:?}", geojson_polygon);
         }
@@ -515,6 +534,7 @@ mod tests {
                 Value::Polygon(..) => "Polygon",
                 Value::MultiPolygon(..) => "MultiPolygon",
                 Value::GeometryCollection(..) => "GeometryCollection",
+                Value::Unknown(..) => "Unknown",
             };
 
             assert_eq!(3, geometries.len());
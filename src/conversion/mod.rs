@@ -12,14 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::geo_types;
-use crate::geo_types::{
+use crate::geojson::GeoJson;
+use crate::geojson::GeoJson::{Feature, FeatureCollection, Geometry};
+use geo_types::{
     Geometry as GtGeometry, GeometryCollection, LineString as GtLineString,
     MultiLineString as GtMultiLineString, MultiPoint as GtMultiPoint,
     MultiPolygon as GtMultiPolygon, Point as GtPoint, Polygon as GtPolygon,
 };
-use crate::geojson::GeoJson;
-use crate::geojson::GeoJson::{Feature, FeatureCollection, Geometry};
 
 use crate::geometry::Geometry as GjGeometry;
 use crate::Error as GJError;
@@ -73,29 +72,32 @@ macro_rules! assert_almost_eq {
 pub(crate) mod from_geo_types;
 pub(crate) mod to_geo_types;
 
+pub use from_geo_types::bbox_from_rect;
+pub use to_geo_types::{rect_from_bbox, GeoTypesConversion, ZHandling};
+
 // Process top-level `GeoJSON` items, returning a geo_types::GeometryCollection or an Error
 fn process_geojson<T>(gj: &GeoJson) -> Result<geo_types::GeometryCollection<T>, GJError>
 where
     T: Float,
 {
-    match &*gj {
+    match gj {
         FeatureCollection(collection) => Ok(GeometryCollection(
             collection
                 .features
                 .iter()
                 // Only pass on non-empty geometries
                 .filter_map(|feature| feature.geometry.as_ref())
-                .map(|geometry| process_geometry(&geometry))
+                .map(|geometry| process_geometry(geometry))
                 .collect::<Result<_, _>>()?,
         )),
         Feature(feature) => {
             if let Some(geometry) = &feature.geometry {
-                Ok(GeometryCollection(vec![process_geometry(&geometry)?]))
+                Ok(GeometryCollection(vec![process_geometry(geometry)?]))
             } else {
                 Ok(GeometryCollection(vec![]))
             }
         }
-        Geometry(geometry) => Ok(GeometryCollection(vec![process_geometry(&geometry)?])),
+        Geometry(geometry) => Ok(GeometryCollection(vec![process_geometry(geometry)?])),
     }
 }
 
@@ -122,11 +124,12 @@ where
         Value::GeometryCollection(gc) => {
             let gc = GtGeometry::GeometryCollection(GeometryCollection(
                 gc.iter()
-                    .map(|geom| process_geometry(&geom))
+                    .map(|geom| process_geometry(geom))
                     .collect::<Result<Vec<geo_types::Geometry<T>>, GJError>>()?,
             ));
             Ok(gc)
         }
+        Value::Unknown(_) => Err(GJError::InvalidGeometryConversion(geometry.value.clone())),
     }
 }
 
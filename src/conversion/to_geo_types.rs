@@ -1,5 +1,3 @@
-use crate::geo_types;
-
 use crate::geometry;
 
 use crate::Error as GJError;
@@ -37,7 +35,7 @@ where
             geometry::Value::MultiPoint(multi_point_type) => Ok(geo_types::MultiPoint(
                 multi_point_type
                     .iter()
-                    .map(|point_type| create_geo_point(&point_type))
+                    .map(|point_type| create_geo_point(point_type))
                     .collect(),
             )),
             _ => Err(GJError::InvalidGeometryConversion(value)),
@@ -149,7 +147,7 @@ where
                 Ok(geo_types::Geometry::MultiPoint(geo_types::MultiPoint(
                     multi_point_type
                         .iter()
-                        .map(|point_type| create_geo_point(&point_type))
+                        .map(|point_type| create_geo_point(point_type))
                         .collect(),
                 )))
             }
@@ -193,7 +191,7 @@ where
 
     fn try_from(val: Feature) -> Result<geo_types::Geometry<T>, Self::Error> {
         match val.geometry {
-            None => Err(GJError::FeatureHasNoGeometry(val)),
+            None => Err(GJError::FeatureHasNoGeometry(Box::new(val))),
             Some(geom) => geom.try_into(),
         }
     }
@@ -229,6 +227,105 @@ where
     }
 }
 
+/// Create a `geo_types::Rect` from a 2D `Bbox`
+///
+/// `Rect` has no third dimension, so 3D (6 element) boxes, like any box without exactly 4
+/// elements, are rejected with `Error::BboxUnexpectedLength`.
+///
+/// ```
+/// use geojson::rect_from_bbox;
+///
+/// let rect: geo_types::Rect<f64> = rect_from_bbox(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+/// assert_eq!(rect, geo_types::Rect::new((1.0, 2.0), (3.0, 4.0)));
+///
+/// assert!(rect_from_bbox::<f64>(&[1.0, 2.0, 0.0, 3.0, 4.0, 10.0]).is_err());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
+pub fn rect_from_bbox<T>(bbox: &[f64]) -> Result<geo_types::Rect<T>, GJError>
+where
+    T: Float,
+{
+    match *bbox {
+        [min_x, min_y, max_x, max_y] => Ok(geo_types::Rect::new(
+            (T::from(min_x).unwrap(), T::from(min_y).unwrap()),
+            (T::from(max_x).unwrap(), T::from(max_y).unwrap()),
+        )),
+        _ => Err(GJError::BboxUnexpectedLength {
+            expected: "4",
+            actual: bbox.len(),
+        }),
+    }
+}
+
+/// What a [`GeoTypesConversion`](struct.GeoTypesConversion.html) does with coordinates after
+/// `x` and `y`, which `geo_types` has no room for
+#[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ZHandling {
+    /// Leave them out, as the `TryFrom` conversions do
+    #[default]
+    DropZ,
+    /// Fail with `Error::UnexpectedZCoordinate` at the first position which has any
+    ErrorOnZ,
+}
+
+/// Options for converting a `Value` to a `geo_types::Geometry`
+///
+/// The `TryFrom` conversions silently drop any z coordinates. For data expected to be 2D,
+/// [`ZHandling::ErrorOnZ`](enum.ZHandling.html#variant.ErrorOnZ) makes that loss an error
+/// instead.
+///
+/// ```
+/// use geojson::{GeoTypesConversion, Value, ZHandling};
+///
+/// let point = Value::Point(vec![1.0, 2.0, 30.0]);
+/// let dropped: geo_types::Geometry<f64> = GeoTypesConversion::new().convert(&point).unwrap();
+/// assert_eq!(dropped, geo_types::Geometry::Point(geo_types::Point::new(1.0, 2.0)));
+///
+/// let strict = GeoTypesConversion::new().on_z(ZHandling::ErrorOnZ);
+/// assert!(strict.convert::<f64>(&point).is_err());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GeoTypesConversion {
+    on_z: ZHandling,
+}
+
+impl GeoTypesConversion {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle z coordinates as `on_z` says
+    pub fn on_z(mut self, on_z: ZHandling) -> Self {
+        self.on_z = on_z;
+        self
+    }
+
+    /// Convert `value`, recursing into `GeometryCollection`s
+    pub fn convert<T>(&self, value: &geometry::Value) -> Result<geo_types::Geometry<T>, GJError>
+    where
+        T: Float,
+    {
+        if self.on_z == ZHandling::ErrorOnZ {
+            if let Some(position) = value.coords_iter().find(|position| position.len() > 2) {
+                return Err(GJError::UnexpectedZCoordinate(position.clone()));
+            }
+        }
+        match value {
+            geometry::Value::GeometryCollection(geometries) => Ok(
+                geo_types::Geometry::GeometryCollection(geo_types::GeometryCollection(
+                    geometries
+                        .iter()
+                        .map(|geometry| self.convert(&geometry.value))
+                        .collect::<Result<_, _>>()?,
+                )),
+            ),
+            _ => value.clone().try_into(),
+        }
+    }
+}
+
 fn create_geo_coordinate<T>(point_type: &PointType) -> geo_types::Coordinate<T>
 where
     T: Float,
@@ -270,7 +367,7 @@ where
     geo_types::MultiLineString(
         multi_line_type
             .iter()
-            .map(|point_type| create_geo_line_string(&point_type))
+            .map(|point_type| create_geo_line_string(point_type))
             .collect(),
     )
 }
@@ -280,7 +377,7 @@ where
     T: Float,
 {
     let exterior = polygon_type
-        .get(0)
+        .first()
         .map(|e| create_geo_line_string(e))
         .unwrap_or_else(|| create_geo_line_string(&vec![]));
 
@@ -303,7 +400,7 @@ where
     geo_types::MultiPolygon(
         multi_polygon_type
             .iter()
-            .map(|polygon_type| create_geo_polygon(&polygon_type))
+            .map(|polygon_type| create_geo_polygon(polygon_type))
             .collect(),
     )
 }
@@ -311,7 +408,6 @@ where
 #[cfg(test)]
 mod tests {
     use crate::{Geometry, Value};
-    use geo_types;
 
     use std::convert::TryInto;
 
@@ -363,13 +459,13 @@ mod tests {
         let geo_multi_line_string: geo_types::MultiLineString<f64> =
             geojson_multi_line_string.try_into().unwrap();
 
-        let ref geo_line_string1 = geo_multi_line_string.0[0];
+        let geo_line_string1 = &geo_multi_line_string.0[0];
         assert_almost_eq!(geo_line_string1.0[0].x, coord1[0], 1e-6);
         assert_almost_eq!(geo_line_string1.0[0].y, coord1[1], 1e-6);
         assert_almost_eq!(geo_line_string1.0[1].x, coord2[0], 1e-6);
         assert_almost_eq!(geo_line_string1.0[1].y, coord2[1], 1e-6);
 
-        let ref geo_line_string2 = geo_multi_line_string.0[1];
+        let geo_line_string2 = &geo_multi_line_string.0[1];
         assert_almost_eq!(geo_line_string2.0[0].x, coord2[0], 1e-6);
         assert_almost_eq!(geo_line_string2.0[0].y, coord2[1], 1e-6);
         assert_almost_eq!(geo_line_string2.0[1].x, coord3[0], 1e-6);
@@ -402,7 +498,7 @@ mod tests {
         let geojson_polygon = Value::Polygon(geojson_multi_line_string_type1);
         let geo_polygon: geo_types::Polygon<f64> = geojson_polygon.try_into().unwrap();
 
-        let ref geo_line_string1 = geo_polygon.exterior();
+        let geo_line_string1 = geo_polygon.exterior();
         assert_almost_eq!(geo_line_string1.0[0].x, coord1[0], 1e-6);
         assert_almost_eq!(geo_line_string1.0[0].y, coord1[1], 1e-6);
         assert_almost_eq!(geo_line_string1.0[1].x, coord2[0], 1e-6);
@@ -412,7 +508,7 @@ mod tests {
         assert_almost_eq!(geo_line_string1.0[3].x, coord1[0], 1e-6);
         assert_almost_eq!(geo_line_string1.0[3].y, coord1[1], 1e-6);
 
-        let ref geo_line_string2 = geo_polygon.interiors()[0];
+        let geo_line_string2 = &geo_polygon.interiors()[0];
         assert_almost_eq!(geo_line_string2.0[0].x, coord4[0], 1e-6);
         assert_almost_eq!(geo_line_string2.0[0].y, coord4[1], 1e-6);
         assert_almost_eq!(geo_line_string2.0[1].x, coord5[0], 1e-6);
@@ -446,7 +542,7 @@ mod tests {
         let geojson_polygon = Value::Polygon(geojson_multi_line_string_type1);
         let geo_polygon: geo_types::Polygon<f64> = geojson_polygon.try_into().unwrap();
 
-        let ref geo_line_string1 = geo_polygon.exterior();
+        let geo_line_string1 = geo_polygon.exterior();
         assert_almost_eq!(geo_line_string1.0[0].x, coord1[0], 1e-6);
         assert_almost_eq!(geo_line_string1.0[0].y, coord1[1], 1e-6);
         assert_almost_eq!(geo_line_string1.0[1].x, coord2[0], 1e-6);
@@ -488,7 +584,7 @@ mod tests {
         let geo_multi_polygon: geo_types::MultiPolygon<f64> =
             geojson_multi_polygon.try_into().unwrap();
 
-        let ref geo_line_string1 = geo_multi_polygon.0[0].exterior();
+        let geo_line_string1 = &geo_multi_polygon.0[0].exterior();
         assert_almost_eq!(geo_line_string1.0[0].x, coord1[0], 1e-6);
         assert_almost_eq!(geo_line_string1.0[0].y, coord1[1], 1e-6);
         assert_almost_eq!(geo_line_string1.0[1].x, coord2[0], 1e-6);
@@ -498,7 +594,7 @@ mod tests {
         assert_almost_eq!(geo_line_string1.0[3].x, coord1[0], 1e-6);
         assert_almost_eq!(geo_line_string1.0[3].y, coord1[1], 1e-6);
 
-        let ref geo_line_string2 = geo_multi_polygon.0[1].exterior();
+        let geo_line_string2 = &geo_multi_polygon.0[1].exterior();
         assert_almost_eq!(geo_line_string2.0[0].x, coord4[0], 1e-6);
         assert_almost_eq!(geo_line_string2.0[0].y, coord4[1], 1e-6);
         assert_almost_eq!(geo_line_string2.0[1].x, coord5[0], 1e-6);
@@ -561,4 +657,35 @@ mod tests {
         assert_almost_eq!(geo_point.x(), coords[0], 1e-6);
         assert_almost_eq!(geo_point.y(), coords[1], 1e-6);
     }
+
+    #[test]
+    fn geo_types_conversion_of_z() {
+        use crate::{Error, GeoTypesConversion, ZHandling};
+
+        let collection = Value::GeometryCollection(vec![
+            Geometry::from(Value::Point(vec![1.0, 2.0])),
+            Geometry::from(Value::LineString(vec![
+                vec![0.0, 0.0, 5.0],
+                vec![1.0, 1.0, 6.0],
+            ])),
+        ]);
+        let converted: geo_types::Geometry<f64> =
+            GeoTypesConversion::new().convert(&collection).unwrap();
+        assert_eq!(
+            converted,
+            geo_types::Geometry::GeometryCollection(geo_types::GeometryCollection(vec![
+                geo_types::Point::new(1.0, 2.0).into(),
+                geo_types::LineString::from(vec![(0.0, 0.0), (1.0, 1.0)]).into(),
+            ]))
+        );
+
+        let strict = GeoTypesConversion::new().on_z(ZHandling::ErrorOnZ);
+        match strict.convert::<f64>(&collection) {
+            Err(Error::UnexpectedZCoordinate(position)) => {
+                assert_eq!(position, vec![0.0, 0.0, 5.0])
+            }
+            other => panic!("expected a z coordinate error, got {:?}", other),
+        }
+        assert!(strict.convert::<f64>(&Value::Point(vec![1.0, 2.0])).is_ok());
+    }
 }
@@ -0,0 +1,928 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Direct `serde` deserialization, without an intermediate `serde_json::Value` tree
+
+use std::fmt;
+
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+
+use crate::errors::Error;
+use crate::json::{Deserialize, Deserializer, JsonObject, JsonValue};
+use crate::{util, Feature, FeatureCollection, GeoJson, Geometry, ParseOptions, Position, Value};
+
+/// `coordinates` whose nesting depth is not yet known, because the `type` member follows them
+enum Coordinates {
+    Position(Position),
+    Array(Vec<Coordinates>),
+}
+
+impl From<Coordinates> for JsonValue {
+    fn from(coordinates: Coordinates) -> JsonValue {
+        match coordinates {
+            Coordinates::Position(position) => JsonValue::from(position),
+            Coordinates::Array(items) => {
+                JsonValue::Array(items.into_iter().map(JsonValue::from).collect())
+            }
+        }
+    }
+}
+
+/// A single element of a `coordinates` array: either a number or a nested array
+enum CoordinatesItem {
+    Number(f64),
+    Nested(Coordinates),
+}
+
+impl<'de> Deserialize<'de> for CoordinatesItem {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ItemVisitor;
+
+        impl<'de> Visitor<'de> for ItemVisitor {
+            type Value = CoordinatesItem;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a number or an array of coordinates")
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+                Ok(CoordinatesItem::Number(v))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(CoordinatesItem::Number(v as f64))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(CoordinatesItem::Number(v as f64))
+            }
+
+            fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                visit_coordinates(seq).map(CoordinatesItem::Nested)
+            }
+
+            // With serde_json's `arbitrary_precision` feature, numbers arrive as a map holding
+            // their text, which `serde_json::Number` knows how to read
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let number =
+                    serde_json::Number::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                number
+                    .as_f64()
+                    .map(CoordinatesItem::Number)
+                    .ok_or_else(|| de::Error::custom(Error::ExpectedF64Value))
+            }
+        }
+
+        deserializer.deserialize_any(ItemVisitor)
+    }
+}
+
+fn visit_coordinates<'de, A>(mut seq: A) -> Result<Coordinates, A::Error>
+where
+    A: SeqAccess<'de>,
+{
+    let mut numbers = Vec::new();
+    let mut nested = Vec::new();
+    while let Some(item) = seq.next_element()? {
+        match item {
+            CoordinatesItem::Number(n) if nested.is_empty() => numbers.push(n),
+            CoordinatesItem::Nested(c) if numbers.is_empty() => nested.push(c),
+            _ => {
+                return Err(de::Error::custom(
+                    "Encountered a mix of numbers and arrays within 'coordinates'",
+                ))
+            }
+        }
+    }
+    if nested.is_empty() {
+        Ok(Coordinates::Position(numbers))
+    } else {
+        Ok(Coordinates::Array(nested))
+    }
+}
+
+impl<'de> Deserialize<'de> for Coordinates {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match CoordinatesItem::deserialize(deserializer)? {
+            CoordinatesItem::Nested(coordinates) => Ok(coordinates),
+            CoordinatesItem::Number(_) => Err(de::Error::custom(Error::ExpectedArrayValue(
+                "Number".to_string(),
+            ))),
+        }
+    }
+}
+
+impl Coordinates {
+    fn into_position<E: de::Error>(self) -> Result<Position, E> {
+        match self {
+            Coordinates::Position(position) => Ok(position),
+            // An empty array is parsed as `Array`, but is equally an empty position
+            Coordinates::Array(ref items) if items.is_empty() => Ok(vec![]),
+            Coordinates::Array(_) => Err(E::custom(Error::ExpectedF64Value)),
+        }
+    }
+
+    fn into_array<E: de::Error>(self) -> Result<Vec<Coordinates>, E> {
+        match self {
+            Coordinates::Array(items) => Ok(items),
+            Coordinates::Position(ref numbers) if numbers.is_empty() => Ok(vec![]),
+            Coordinates::Position(_) => {
+                Err(E::custom(Error::ExpectedArrayValue("Number".to_string())))
+            }
+        }
+    }
+
+    fn into_1d<E: de::Error>(self) -> Result<Vec<Position>, E> {
+        self.into_array()?
+            .into_iter()
+            .map(Coordinates::into_position)
+            .collect()
+    }
+
+    fn into_2d<E: de::Error>(self) -> Result<Vec<Vec<Position>>, E> {
+        self.into_array()?
+            .into_iter()
+            .map(Coordinates::into_1d)
+            .collect()
+    }
+
+    fn into_3d<E: de::Error>(self) -> Result<Vec<Vec<Vec<Position>>>, E> {
+        self.into_array()?
+            .into_iter()
+            .map(Coordinates::into_2d)
+            .collect()
+    }
+
+    fn into_value<E: de::Error>(self, type_: &str) -> Result<Value, E> {
+        Ok(match type_ {
+            "Point" => Value::Point(self.into_position()?),
+            "MultiPoint" => Value::MultiPoint(self.into_1d()?),
+            "LineString" => Value::LineString(self.into_1d()?),
+            "MultiLineString" => Value::MultiLineString(self.into_2d()?),
+            "Polygon" => Value::Polygon(self.into_2d()?),
+            "MultiPolygon" => Value::MultiPolygon(self.into_3d()?),
+            _ => return Err(E::custom(Error::GeometryUnknownType(type_.to_string()))),
+        })
+    }
+}
+
+/// Deserializes the `coordinates` of a geometry whose `type` is already known, straight into
+/// the matching `Value` variant
+struct TypedCoordinates<'a>(&'a str);
+
+impl<'de, 'a> DeserializeSeed<'de> for TypedCoordinates<'a> {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match self.0 {
+            "Point" => Value::Point(Deserialize::deserialize(deserializer)?),
+            "MultiPoint" => Value::MultiPoint(Deserialize::deserialize(deserializer)?),
+            "LineString" => Value::LineString(Deserialize::deserialize(deserializer)?),
+            "MultiLineString" => Value::MultiLineString(Deserialize::deserialize(deserializer)?),
+            "Polygon" => Value::Polygon(Deserialize::deserialize(deserializer)?),
+            "MultiPolygon" => Value::MultiPolygon(Deserialize::deserialize(deserializer)?),
+            type_ => {
+                return Err(de::Error::custom(Error::GeometryUnknownType(
+                    type_.to_string(),
+                )))
+            }
+        })
+    }
+}
+
+/// Deserializes the `geometries` of a `GeometryCollection`, each of which may hold
+/// `remaining` more levels of nested collections
+struct Geometries {
+    remaining: usize,
+}
+
+impl<'de> DeserializeSeed<'de> for Geometries {
+    type Value = Vec<Geometry>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Geometries {
+    type Value = Vec<Geometry>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array of GeoJSON geometry objects")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut geometries = Vec::new();
+        while let Some(members) = seq.next_element_seed(ObjectSeed {
+            kind: Kind::Geometry,
+            remaining: self.remaining,
+        })? {
+            geometries.push(members.into_geometry()?);
+        }
+        Ok(geometries)
+    }
+}
+
+/// The members of a geometry object which make up its `Value`
+#[derive(Default)]
+struct ValueMembers {
+    type_: Option<String>,
+    value: Option<Value>,
+    coordinates: Option<Coordinates>,
+    /// The `coordinates` of a `GeometryCollection`, kept as they are to become a foreign member
+    raw_coordinates: Option<JsonValue>,
+    geometries: Option<Vec<Geometry>>,
+}
+
+impl ValueMembers {
+    /// Consume the value of `key` if it is one of the `Value` members; returns `false` otherwise
+    ///
+    /// `remaining` is how many levels of `GeometryCollection` may still be nested, as for
+    /// `ParseOptions::max_depth`.
+    fn next_value<'de, A>(
+        &mut self,
+        key: &str,
+        map: &mut A,
+        remaining: usize,
+    ) -> Result<bool, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        match key {
+            "type" => self.type_ = Some(map.next_value()?),
+            "coordinates" => match self.type_.as_deref() {
+                Some("GeometryCollection") => self.raw_coordinates = Some(map.next_value()?),
+                Some(type_) => self.value = Some(map.next_value_seed(TypedCoordinates(type_))?),
+                None => self.coordinates = Some(map.next_value()?),
+            },
+            "geometries" => {
+                if remaining == 0 {
+                    return Err(de::Error::custom(Error::MaxDepthExceeded));
+                }
+                self.geometries = Some(map.next_value_seed(Geometries {
+                    remaining: remaining - 1,
+                })?)
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Like `next_value`, for a `coordinates` or `geometries` member which was buffered because
+    /// it came before the object's `type`
+    fn set_json(
+        &mut self,
+        key: &str,
+        json: JsonValue,
+        remaining: usize,
+    ) -> Result<(), serde_json::Error> {
+        match key {
+            "coordinates" => match self.type_.as_deref() {
+                Some("GeometryCollection") => self.raw_coordinates = Some(json),
+                Some(type_) => self.value = Some(TypedCoordinates(type_).deserialize(json)?),
+                None => self.coordinates = Some(Coordinates::deserialize(json)?),
+            },
+            _ => {
+                if remaining == 0 {
+                    return Err(de::Error::custom(Error::MaxDepthExceeded));
+                }
+                self.geometries = Some(
+                    Geometries {
+                        remaining: remaining - 1,
+                    }
+                    .deserialize(json)?,
+                )
+            }
+        }
+        Ok(())
+    }
+
+    fn finish<E: de::Error>(self) -> Result<Value, E> {
+        self.finish_with_foreign_members(&mut JsonObject::new())
+    }
+
+    /// Like `finish`, but moves a `coordinates` or `geometries` member which doesn't belong to
+    /// the geometry's type into `foreign_members`
+    fn finish_with_foreign_members<E: de::Error>(
+        self,
+        foreign_members: &mut JsonObject,
+    ) -> Result<Value, E> {
+        let type_ = self
+            .type_
+            .ok_or_else(|| E::custom(Error::ExpectedProperty("type".to_string())))?;
+        util::check_geometry_member(
+            &type_,
+            self.value.is_some() || self.coordinates.is_some() || self.raw_coordinates.is_some(),
+            self.geometries.is_some(),
+        )
+        .map_err(E::custom)?;
+        if type_ == "GeometryCollection" {
+            if let Some(coordinates) = self.coordinates {
+                foreign_members.insert("coordinates".to_string(), coordinates.into());
+            }
+            if let Some(coordinates) = self.raw_coordinates {
+                foreign_members.insert("coordinates".to_string(), coordinates);
+            }
+            return self
+                .geometries
+                .map(Value::GeometryCollection)
+                .ok_or_else(|| E::custom(Error::ExpectedProperty("geometries".to_string())));
+        }
+        if let Some(geometries) = self.geometries {
+            foreign_members.insert("geometries".to_string(), to_json_value(&geometries)?);
+        }
+        match (self.value, self.coordinates) {
+            (Some(value), _) => Ok(value),
+            (None, Some(coordinates)) => coordinates.into_value(&type_),
+            (None, None) => match type_.as_str() {
+                "Point" | "MultiPoint" | "LineString" | "MultiLineString" | "Polygon"
+                | "MultiPolygon" => Err(E::custom(Error::ExpectedProperty(
+                    "coordinates".to_string(),
+                ))),
+                _ => Err(E::custom(Error::GeometryUnknownType(type_))),
+            },
+        }
+    }
+
+    /// Move any `Value` members into `foreign_members`, for objects which aren't geometries
+    fn into_foreign_members<E: de::Error>(self, foreign_members: &mut JsonObject) -> Result<(), E> {
+        if let Some(value) = self.value {
+            foreign_members.insert("coordinates".to_string(), to_json_value(&value)?);
+        }
+        if let Some(coordinates) = self.coordinates {
+            foreign_members.insert("coordinates".to_string(), coordinates.into());
+        }
+        if let Some(coordinates) = self.raw_coordinates {
+            foreign_members.insert("coordinates".to_string(), coordinates);
+        }
+        if let Some(geometries) = self.geometries {
+            foreign_members.insert("geometries".to_string(), to_json_value(&geometries)?);
+        }
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ValueVisitor;
+
+        impl<'de> Visitor<'de> for ValueVisitor {
+            type Value = Value;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a GeoJSON geometry object")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut members = ValueMembers::default();
+                let remaining = ParseOptions::default().max_depth;
+                while let Some(key) = map.next_key::<String>()? {
+                    if !members.next_value(&key, &mut map, remaining)? {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+                members.finish()
+            }
+        }
+
+        deserializer.deserialize_map(ValueVisitor)
+    }
+}
+
+fn to_json_value<T: serde::Serialize, E: de::Error>(value: &T) -> Result<JsonValue, E> {
+    serde_json::to_value(value).map_err(E::custom)
+}
+
+/// The kind of GeoJSON object being deserialized, which decides how its members are read
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Kind {
+    Geometry,
+    Feature,
+    FeatureCollection,
+    /// Any of the above, as decided by the object's `type`
+    Any,
+}
+
+impl Kind {
+    fn of_type(type_: &str) -> Kind {
+        match type_ {
+            "Feature" => Kind::Feature,
+            "FeatureCollection" => Kind::FeatureCollection,
+            _ => Kind::Geometry,
+        }
+    }
+}
+
+/// All the members of a GeoJSON object.
+///
+/// Members which are cheap to keep as JSON are parsed when the object is finished; `coordinates`,
+/// `geometries`, `geometry` and `features` are deserialized straight into their final types,
+/// unless they come before the `type` of an object of any kind. Those are kept as JSON until the
+/// `type` says whether they belong to the object or are foreign members.
+pub(crate) struct ObjectMembers {
+    value: ValueMembers,
+    bbox: Option<JsonValue>,
+    geometry: Option<Option<Geometry>>,
+    properties: Option<JsonValue>,
+    id: Option<JsonValue>,
+    features: Option<Vec<Feature>>,
+    foreign_members: JsonObject,
+}
+
+impl ObjectMembers {
+    /// Deserialize the members of an object of `kind`, applying the default
+    /// `ParseOptions::max_depth`
+    pub(crate) fn deserialize<'de, D>(deserializer: D, kind: Kind) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ObjectSeed {
+            kind,
+            remaining: ParseOptions::default().max_depth,
+        }
+        .deserialize(deserializer)
+    }
+
+    fn take_type<E: de::Error>(&mut self) -> Result<String, E> {
+        self.value
+            .type_
+            .take()
+            .ok_or_else(|| E::custom(Error::ExpectedProperty("type".to_string())))
+    }
+
+    fn bbox<E: de::Error>(&mut self) -> Result<Option<crate::Bbox>, E> {
+        self.bbox
+            .take()
+            .map(|bbox| util::check_bbox(util::expect_bbox(bbox)?, &ParseOptions::default()))
+            .transpose()
+            .map_err(E::custom)
+    }
+
+    fn foreign_members(foreign_members: JsonObject) -> Option<JsonObject> {
+        if foreign_members.is_empty() {
+            None
+        } else {
+            Some(foreign_members)
+        }
+    }
+
+    pub(crate) fn into_geometry<E: de::Error>(mut self) -> Result<Geometry, E> {
+        let bbox = self.bbox()?;
+        let mut foreign_members = self.foreign_members;
+        let value = self
+            .value
+            .finish_with_foreign_members(&mut foreign_members)?;
+        if let Some(geometry) = self.geometry {
+            foreign_members.insert("geometry".to_string(), to_json_value(&geometry)?);
+        }
+        if let Some(properties) = self.properties {
+            foreign_members.insert("properties".to_string(), properties);
+        }
+        if let Some(id) = self.id {
+            foreign_members.insert("id".to_string(), id);
+        }
+        if let Some(features) = self.features {
+            foreign_members.insert("features".to_string(), to_json_value(&features)?);
+        }
+        Ok(Geometry {
+            bbox,
+            value,
+            foreign_members: Self::foreign_members(foreign_members),
+        })
+    }
+
+    pub(crate) fn into_feature<E: de::Error>(mut self) -> Result<Feature, E> {
+        let type_ = self.take_type()?;
+        if type_ != "Feature" {
+            return Err(E::custom(Error::NotAFeature(type_)));
+        }
+        util::check_feature_member(
+            &type_,
+            self.geometry.is_some(),
+            self.features.is_some() || self.foreign_members.contains_key("features"),
+        )
+        .map_err(E::custom)?;
+        let bbox = self.bbox()?;
+        let geometry = self
+            .geometry
+            .ok_or_else(|| E::custom(Error::ExpectedProperty("geometry".to_string())))?;
+        let properties = self
+            .properties
+            .ok_or_else(|| E::custom(Error::ExpectedProperty("properties".to_string())))
+            .and_then(|properties| util::expect_properties(properties).map_err(E::custom))?;
+        let id = self
+            .id
+            .map(util::expect_id)
+            .transpose()
+            .map_err(E::custom)?
+            .flatten();
+        let mut foreign_members = self.foreign_members;
+        self.value.into_foreign_members(&mut foreign_members)?;
+        if let Some(features) = self.features {
+            foreign_members.insert("features".to_string(), to_json_value(&features)?);
+        }
+        Ok(Feature {
+            bbox,
+            geometry,
+            id,
+            properties,
+            foreign_members: Self::foreign_members(foreign_members),
+        })
+    }
+
+    pub(crate) fn into_feature_collection<E: de::Error>(mut self) -> Result<FeatureCollection, E> {
+        let type_ = self.take_type()?;
+        if type_ != "FeatureCollection" {
+            return Err(E::custom(Error::ExpectedType {
+                expected: "FeatureCollection".to_owned(),
+                actual: type_,
+            }));
+        }
+        util::check_feature_member(
+            &type_,
+            self.geometry.is_some() || self.foreign_members.contains_key("geometry"),
+            self.features.is_some(),
+        )
+        .map_err(E::custom)?;
+        let bbox = self.bbox()?;
+        let features = self
+            .features
+            .ok_or_else(|| E::custom(Error::ExpectedProperty("features".to_string())))?;
+        let mut foreign_members = self.foreign_members;
+        self.value.into_foreign_members(&mut foreign_members)?;
+        if let Some(geometry) = self.geometry {
+            foreign_members.insert("geometry".to_string(), to_json_value(&geometry)?);
+        }
+        if let Some(properties) = self.properties {
+            foreign_members.insert("properties".to_string(), properties);
+        }
+        if let Some(id) = self.id {
+            foreign_members.insert("id".to_string(), id);
+        }
+        Ok(FeatureCollection {
+            bbox,
+            features,
+            foreign_members: Self::foreign_members(foreign_members),
+        })
+    }
+
+    pub(crate) fn into_geojson<E: de::Error>(self) -> Result<GeoJson, E> {
+        let kind = match self.value.type_.as_deref() {
+            Some(
+                "Point" | "MultiPoint" | "LineString" | "MultiLineString" | "Polygon"
+                | "MultiPolygon" | "GeometryCollection",
+            ) => Kind::Geometry,
+            Some(type_ @ "Feature") | Some(type_ @ "FeatureCollection") => Kind::of_type(type_),
+            Some(_) => return Err(E::custom(Error::EmptyType)),
+            None => return Err(E::custom(Error::GeometryUnknownType("type".to_owned()))),
+        };
+        match kind {
+            Kind::Feature => self.into_feature().map(GeoJson::Feature),
+            Kind::FeatureCollection => self
+                .into_feature_collection()
+                .map(GeoJson::FeatureCollection),
+            _ => self.into_geometry().map(GeoJson::Geometry),
+        }
+    }
+}
+
+/// Deserializes the members of an object of `kind`, within which `remaining` more levels of
+/// `GeometryCollection` may be nested
+#[derive(Clone, Copy)]
+struct ObjectSeed {
+    kind: Kind,
+    remaining: usize,
+}
+
+impl<'de> DeserializeSeed<'de> for ObjectSeed {
+    type Value = ObjectMembers;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ObjectSeed {
+    type Value = ObjectMembers;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(match self.kind {
+            Kind::Geometry => "a GeoJSON geometry object",
+            Kind::Feature => "a GeoJSON feature object",
+            Kind::FeatureCollection => "a GeoJSON feature collection object",
+            Kind::Any => "a GeoJSON object",
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut members = ObjectMembers {
+            value: ValueMembers::default(),
+            bbox: None,
+            geometry: None,
+            properties: None,
+            id: None,
+            features: None,
+            foreign_members: JsonObject::new(),
+        };
+        // Members which only some kinds of object have, read before the `type` of an object of
+        // any kind
+        let mut pending = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let kind = match (self.kind, &members.value.type_) {
+                (Kind::Any, Some(type_)) => Kind::of_type(type_),
+                (kind, _) => kind,
+            };
+            let is_geometry = kind == Kind::Geometry;
+            let is_feature = kind == Kind::Feature || kind == Kind::Any;
+            let is_feature_collection = kind == Kind::FeatureCollection;
+            match key.as_str() {
+                "type" => members.value.type_ = Some(map.next_value()?),
+                "bbox" => members.bbox = Some(map.next_value()?),
+                "coordinates" | "geometries" | "geometry" | "features" if kind == Kind::Any => {
+                    let value: JsonValue = map.next_value()?;
+                    pending.push((key, value));
+                }
+                "coordinates" | "geometries" if is_geometry => {
+                    members.value.next_value(&key, &mut map, self.remaining)?;
+                }
+                "geometry" if is_feature => members.geometry = Some(map.next_value()?),
+                "properties" if is_feature => members.properties = Some(map.next_value()?),
+                "id" if is_feature => members.id = Some(map.next_value()?),
+                "features" if is_feature_collection => members.features = Some(map.next_value()?),
+                _ => {
+                    let value = map.next_value()?;
+                    members.foreign_members.insert(key, value);
+                }
+            }
+        }
+
+        let kind = members
+            .value
+            .type_
+            .as_deref()
+            .map_or(Kind::Any, Kind::of_type);
+        for (key, json) in pending {
+            match (key.as_str(), kind) {
+                ("coordinates", Kind::Geometry) | ("geometries", Kind::Geometry) => members
+                    .value
+                    .set_json(&key, json, self.remaining)
+                    .map_err(de::Error::custom)?,
+                ("geometry", Kind::Feature) => {
+                    members.geometry =
+                        Some(Deserialize::deserialize(json).map_err(de::Error::custom)?)
+                }
+                ("features", Kind::FeatureCollection) => {
+                    members.features =
+                        Some(Deserialize::deserialize(json).map_err(de::Error::custom)?)
+                }
+                _ => {
+                    members.foreign_members.insert(key, json);
+                }
+            }
+        }
+        Ok(members)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Feature, FeatureCollection, GeoJson, Geometry, Value};
+    use serde_json::json;
+
+    #[test]
+    fn coordinates_before_type() {
+        let value: Value = serde_json::from_str(
+            r#"{"coordinates": [[[0, 0], [1, 0], [1, 1], [0, 0]]], "type": "Polygon"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            Value::Polygon(vec![vec![
+                vec![0., 0.],
+                vec![1., 0.],
+                vec![1., 1.],
+                vec![0., 0.]
+            ]])
+        );
+    }
+
+    #[test]
+    fn coordinates_after_type() {
+        let value: Value =
+            serde_json::from_str(r#"{"type": "MultiPoint", "coordinates": [[0, 0], [1.5, 2]]}"#)
+                .unwrap();
+        assert_eq!(value, Value::MultiPoint(vec![vec![0., 0.], vec![1.5, 2.]]));
+    }
+
+    #[test]
+    fn geometry_collection() {
+        let value: Value = serde_json::from_str(
+            r#"{"type": "GeometryCollection", "geometries": [{"type": "Point", "coordinates": [1, 2]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            Value::GeometryCollection(vec![Geometry::new(Value::Point(vec![1., 2.]))])
+        );
+    }
+
+    #[test]
+    fn wrong_nesting_depth() {
+        assert!(
+            serde_json::from_str::<Value>(r#"{"type": "Point", "coordinates": [[1, 2]]}"#).is_err()
+        );
+        assert!(
+            serde_json::from_str::<Value>(r#"{"coordinates": [[1, 2]], "type": "Point"}"#).is_err()
+        );
+        assert!(
+            serde_json::from_str::<Value>(r#"{"coordinates": [1, [2]], "type": "Point"}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn missing_members() {
+        assert!(serde_json::from_str::<Value>(r#"{"type": "Point"}"#).is_err());
+        assert!(serde_json::from_str::<Value>(r#"{"coordinates": [1, 2]}"#).is_err());
+        assert!(
+            serde_json::from_str::<Value>(r#"{"type": "Circle", "coordinates": [1, 2]}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn matches_json_value_parsing() {
+        for geojson_str in &[
+            include_str!("../tests/fixtures/countries.geojson"),
+            include_str!("../tests/fixtures/geometry_collection.geojson"),
+            include_str!("../tests/fixtures/canonical/good-featurecollection-extensions.geojson"),
+            include_str!("../tests/fixtures/canonical/good-feature-with-id.geojson"),
+            include_str!("../tests/fixtures/canonical/nullgeometry.geojson"),
+        ] {
+            let direct: GeoJson = serde_json::from_str(geojson_str).unwrap();
+            let via_json_value: GeoJson = geojson_str.parse().unwrap();
+            assert_eq!(direct, via_json_value);
+        }
+    }
+
+    #[test]
+    fn feature_collection_in_serialized_order() {
+        // `features` precedes `type`, as this crate emits it
+        let geojson: GeoJson = serde_json::from_str(
+            r#"{"bbox": [0, 0, 1, 1], "features": [{"geometry": null, "properties": null, "type": "Feature"}], "type": "FeatureCollection"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            geojson,
+            GeoJson::FeatureCollection(FeatureCollection {
+                bbox: Some(vec![0., 0., 1., 1.]),
+                features: vec![Feature {
+                    bbox: None,
+                    geometry: None,
+                    id: None,
+                    properties: None,
+                    foreign_members: None,
+                }],
+                foreign_members: None,
+            })
+        );
+    }
+
+    #[test]
+    fn members_of_other_types_are_foreign() {
+        let geometry: Geometry = serde_json::from_str(
+            r#"{"properties": {"a": 1}, "type": "Point", "coordinates": [1, 2]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            geometry.foreign_members,
+            json!({"properties": {"a": 1}}).as_object().cloned()
+        );
+
+        let geojson: GeoJson = serde_json::from_str(
+            r#"{"coordinates": [1, 2], "geometry": null, "properties": null, "type": "Feature"}"#,
+        )
+        .unwrap();
+        let feature = match geojson {
+            GeoJson::Feature(feature) => feature,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            feature.foreign_members,
+            json!({"coordinates": [1, 2]}).as_object().cloned()
+        );
+
+        // Members which come before the `type` aren't read as the wrong kind of member
+        let geojson_str = r#"{"coordinates": "x", "geometries": 1, "type": "Feature", "geometry": null, "properties": {}}"#;
+        let direct: GeoJson = serde_json::from_str(geojson_str).unwrap();
+        assert_eq!(direct, geojson_str.parse::<GeoJson>().unwrap());
+        let geojson_str = r#"{"geometry": 1, "features": [2], "type": "GeometryCollection", "coordinates": [3], "geometries": []}"#;
+        let direct: GeoJson = serde_json::from_str(geojson_str).unwrap();
+        assert_eq!(direct, geojson_str.parse::<GeoJson>().unwrap());
+        match direct {
+            GeoJson::Geometry(geometry) => assert_eq!(
+                geometry.foreign_members,
+                json!({"geometry": 1, "features": [2], "coordinates": [3]})
+                    .as_object()
+                    .cloned()
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn max_depth() {
+        let nested = |depth: usize| {
+            let mut geojson_str = r#"{"type": "Point", "coordinates": [1, 2]}"#.to_string();
+            for _ in 0..depth {
+                geojson_str = format!(
+                    r#"{{"type": "GeometryCollection", "geometries": [{}]}}"#,
+                    geojson_str
+                );
+            }
+            geojson_str
+        };
+        let limit = crate::ParseOptions::default().max_depth;
+        assert!(nested(limit).parse::<GeoJson>().is_ok());
+        assert!(serde_json::from_str::<GeoJson>(&nested(limit)).is_ok());
+        assert!(serde_json::from_str::<Value>(&nested(limit)).is_ok());
+        for geojson_str in &[nested(limit + 1), nested(40)] {
+            assert!(geojson_str.parse::<GeoJson>().is_err());
+            assert!(serde_json::from_str::<GeoJson>(geojson_str)
+                .unwrap_err()
+                .to_string()
+                .contains("nested too deeply"));
+            assert!(serde_json::from_str::<Geometry>(geojson_str).is_err());
+            assert!(serde_json::from_str::<Value>(geojson_str).is_err());
+        }
+
+        // Collections buffered before the `type` are limited too
+        let geojson_str = format!(
+            r#"{{"geometries": [{}], "type": "GeometryCollection"}}"#,
+            nested(limit)
+        );
+        assert!(serde_json::from_str::<GeoJson>(&geojson_str).is_err());
+    }
+
+    #[test]
+    fn invalid_members() {
+        assert!(serde_json::from_str::<Feature>(
+            r#"{"type": "Feature", "geometry": 3.14, "properties": null}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<Feature>(
+            r#"{"type": "Feature", "geometry": null, "properties": null, "id": true}"#
+        )
+        .is_err());
+        assert!(
+            serde_json::from_str::<Feature>(r#"{"type": "Feature", "geometry": null}"#).is_err()
+        );
+        assert!(serde_json::from_str::<FeatureCollection>(
+            r#"{"type": "Feature", "features": []}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<GeoJson>(r#"{"type": "Unknown"}"#).is_err());
+    }
+}
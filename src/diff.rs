@@ -0,0 +1,295 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::feature::Id;
+use crate::json::{JsonObject, JsonValue};
+use crate::ser::content_hash;
+use crate::{Feature, FeatureCollection};
+
+/// The differences between two versions of a `FeatureCollection`, matched by feature id
+///
+/// Returned by [`FeatureCollection::diff`](struct.FeatureCollection.html#method.diff).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CollectionDiff {
+    /// Ids of features only in the new collection, in its order
+    pub added: Vec<Id>,
+    /// Ids of features only in the old collection, in its order
+    pub removed: Vec<Id>,
+    /// Ids of features in both collections whose geometry or properties differ, in the old
+    /// collection's order
+    pub changed: Vec<Id>,
+    /// Indices of the old collection's features which couldn't be matched: those without an
+    /// id, and any after the first with the same id
+    pub unmatchable_old: Vec<usize>,
+    /// Indices of the new collection's features which couldn't be matched, as for
+    /// `unmatchable_old`
+    pub unmatchable_new: Vec<usize>,
+}
+
+impl CollectionDiff {
+    /// Whether no feature was added, removed or changed, and every feature could be matched
+    pub fn is_empty(&self) -> bool {
+        *self == CollectionDiff::default()
+    }
+}
+
+/// The differences between the properties of two versions of a `Feature`
+///
+/// Returned by [`Feature::property_diff`](struct.Feature.html#method.property_diff).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PropertyDiff {
+    /// Properties only in the new feature, with their values, in its order
+    pub added: Vec<(String, JsonValue)>,
+    /// Properties only in the old feature, with their values, in its order
+    pub removed: Vec<(String, JsonValue)>,
+    /// Properties in both features whose values differ, with the old and then the new value,
+    /// in the old feature's order
+    pub changed: Vec<(String, JsonValue, JsonValue)>,
+}
+
+impl PropertyDiff {
+    /// Whether no property was added, removed or changed
+    pub fn is_empty(&self) -> bool {
+        *self == PropertyDiff::default()
+    }
+}
+
+impl Feature {
+    /// Compare this feature's properties with those of a newer version of it
+    ///
+    /// A feature whose `properties` are `None` is compared as if they were empty.
+    ///
+    /// ```
+    /// use geojson::Feature;
+    /// use serde_json::json;
+    ///
+    /// let mut old = Feature::with_null_geometry();
+    /// old.set_property("name", "Ash");
+    /// old.set_property("height", 12);
+    /// let mut new = old.clone();
+    /// new.set_property("height", 14);
+    /// new.remove_property("name");
+    ///
+    /// let diff = old.property_diff(&new);
+    /// assert_eq!(diff.removed, vec![("name".to_string(), json!("Ash"))]);
+    /// assert_eq!(diff.changed, vec![("height".to_string(), json!(12), json!(14))]);
+    /// assert!(diff.added.is_empty());
+    /// ```
+    pub fn property_diff(&self, other: &Feature) -> PropertyDiff {
+        let empty = JsonObject::new();
+        let old = self.properties.as_ref().unwrap_or(&empty);
+        let new = other.properties.as_ref().unwrap_or(&empty);
+
+        let mut diff = PropertyDiff::default();
+        for (key, old_value) in old {
+            match new.get(key) {
+                None => diff.removed.push((key.clone(), old_value.clone())),
+                Some(new_value) if new_value != old_value => {
+                    diff.changed
+                        .push((key.clone(), old_value.clone(), new_value.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        for (key, new_value) in new {
+            if !old.contains_key(key) {
+                diff.added.push((key.clone(), new_value.clone()));
+            }
+        }
+        diff
+    }
+}
+
+impl FeatureCollection {
+    /// Compare this collection with a newer version of it, matching features by their `id`
+    ///
+    /// Matched features are compared by a hash of their geometry and properties, which
+    /// ignores any `bbox`, foreign members and the order of object members, and treats `null`
+    /// properties as equal to empty ones.
+    ///
+    /// ```
+    /// use geojson::feature::Id;
+    /// use geojson::{Feature, FeatureCollection};
+    ///
+    /// let feature = |id: &str, name: &str| {
+    ///     let mut feature = Feature::with_null_geometry();
+    ///     feature.id = Some(Id::String(id.to_string()));
+    ///     feature.set_property("name", name);
+    ///     feature
+    /// };
+    /// let old = FeatureCollection::from(vec![feature("a", "Ash"), feature("b", "Birch")]);
+    /// let new = FeatureCollection::from(vec![feature("b", "Beech"), feature("c", "Cedar")]);
+    ///
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.added, vec![Id::String("c".to_string())]);
+    /// assert_eq!(diff.removed, vec![Id::String("a".to_string())]);
+    /// assert_eq!(diff.changed, vec![Id::String("b".to_string())]);
+    /// ```
+    pub fn diff(&self, other: &FeatureCollection) -> CollectionDiff {
+        let mut diff = CollectionDiff::default();
+        let old = index_by_id(self, &mut diff.unmatchable_old);
+        let new = index_by_id(other, &mut diff.unmatchable_new);
+
+        for (id, old_feature) in ids_in_order(&old) {
+            match new.get(id) {
+                None => diff.removed.push(id.clone()),
+                Some(&(_, new_feature)) => {
+                    if content(old_feature) != content(new_feature) {
+                        diff.changed.push(id.clone());
+                    }
+                }
+            }
+        }
+        for (id, _) in ids_in_order(&new) {
+            if !old.contains_key(id) {
+                diff.added.push(id.clone());
+            }
+        }
+        diff
+    }
+}
+
+/// Map each id to the index and feature of its first occurrence, recording the indices of
+/// features which can't be matched in `unmatchable`
+fn index_by_id<'a>(
+    collection: &'a FeatureCollection,
+    unmatchable: &mut Vec<usize>,
+) -> HashMap<&'a Id, (usize, &'a Feature)> {
+    let mut index = HashMap::new();
+    for (i, feature) in collection.features.iter().enumerate() {
+        match &feature.id {
+            Some(id) if !index.contains_key(id) => {
+                index.insert(id, (i, feature));
+            }
+            _ => unmatchable.push(i),
+        }
+    }
+    index
+}
+
+fn ids_in_order<'a>(index: &HashMap<&'a Id, (usize, &'a Feature)>) -> Vec<(&'a Id, &'a Feature)> {
+    let mut ids: Vec<_> = index
+        .iter()
+        .map(|(&id, &(i, feature))| (i, id, feature))
+        .collect();
+    ids.sort_unstable_by_key(|&(i, _, _)| i);
+    ids.into_iter()
+        .map(|(_, id, feature)| (id, feature))
+        .collect()
+}
+
+/// A hash of the parts of `feature` which `diff` compares
+fn content(feature: &Feature) -> u64 {
+    let mut geometry = serde_json::to_value(feature.geometry.as_ref().map(|g| &g.value))
+        .unwrap_or(JsonValue::Null);
+    remove_bboxes(&mut geometry);
+    let properties = feature.properties.clone().unwrap_or_default();
+    content_hash(&JsonValue::Array(vec![
+        geometry,
+        JsonValue::Object(properties),
+    ]))
+}
+
+/// Remove the `bbox` of the members of a serialized `GeometryCollection`, recursively
+fn remove_bboxes(geometry: &mut JsonValue) {
+    if let Some(JsonValue::Array(geometries)) = geometry.get_mut("geometries") {
+        for member in geometries {
+            if let JsonValue::Object(object) = member {
+                object.remove("bbox");
+            }
+            remove_bboxes(member);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CollectionDiff, PropertyDiff};
+    use crate::feature::Id;
+    use crate::{Feature, FeatureCollection, Geometry, Value};
+
+    fn feature(id: Option<u64>, value: Value) -> Feature {
+        let mut feature = Feature::with_computed_bbox(Geometry::new(value));
+        feature.id = id.map(|id| Id::Number(id.into()));
+        feature
+    }
+
+    #[test]
+    fn bboxes_and_property_order_are_ignored() {
+        let point = Value::Point(vec![1., 2.]);
+        let mut old = feature(Some(1), point.clone());
+        old.properties = None;
+        let mut new = feature(Some(1), point);
+        new.bbox = None;
+        new.properties = Some(Default::default());
+
+        let old = FeatureCollection::from(vec![old]);
+        let new = FeatureCollection::from(vec![new]);
+        assert!(old.diff(&new).is_empty());
+        assert!(old.diff(&old).is_empty());
+
+        let mut moved = new.clone();
+        moved.map_coords(|position| position[0] += 1.);
+        assert_eq!(old.diff(&moved).changed, vec![Id::Number(1.into())]);
+    }
+
+    #[test]
+    fn features_without_unique_ids_are_unmatchable() {
+        let point = || Value::Point(vec![0., 0.]);
+        let old = FeatureCollection::from(vec![
+            feature(None, point()),
+            feature(Some(1), point()),
+            feature(Some(1), Value::Point(vec![5., 5.])),
+        ]);
+        let new = FeatureCollection::from(vec![feature(Some(2), point()), feature(None, point())]);
+
+        assert_eq!(
+            old.diff(&new),
+            CollectionDiff {
+                added: vec![Id::Number(2.into())],
+                removed: vec![Id::Number(1.into())],
+                changed: vec![],
+                unmatchable_old: vec![0, 2],
+                unmatchable_new: vec![1],
+            }
+        );
+    }
+
+    #[test]
+    fn property_diff() {
+        let mut old = Feature::with_null_geometry();
+        old.properties = None;
+        let mut new = Feature::with_null_geometry();
+        assert!(old.property_diff(&new).is_empty());
+
+        new.set_property("a", 1);
+        old.set_property("b", 2);
+        old.set_property("c", 3);
+        new.set_property("c", 3.5);
+        assert_eq!(
+            old.property_diff(&new),
+            PropertyDiff {
+                added: vec![("a".to_string(), 1.into())],
+                removed: vec![("b".to_string(), 2.into())],
+                changed: vec![("c".to_string(), 3.into(), 3.5.into())],
+            }
+        );
+        assert_eq!(
+            new.property_diff(&old).added,
+            vec![("b".to_string(), 2.into())]
+        );
+    }
+}
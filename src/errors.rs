@@ -9,8 +9,19 @@ use thiserror::Error;
 pub enum Error {
     #[error("Encountered non-array value for a 'bbox' object: `{0}`")]
     BboxExpectedArray(Value),
+    /// A `bbox` didn't have the number of elements required: 4 or 6 for a parsed one, or
+    /// whatever else the operation needs. `expected` describes the lengths accepted, e.g.
+    /// `"4 or 6"`.
+    #[error("Expected a 'bbox' with {expected} elements, but it has {actual}")]
+    BboxUnexpectedLength {
+        expected: &'static str,
+        actual: usize,
+    },
     #[error("Encountered non-numeric value within 'bbox' array")]
     BboxExpectedNumericValues(Value),
+    /// The input was empty or contained only whitespace
+    #[error("Expected GeoJSON, but the input was empty")]
+    EmptyInput,
     #[error("Encountered a non-object type for GeoJSON: `{0}`")]
     GeoJsonExpectedObject(Value),
     /// This was previously `GeoJsonUnknownType`, but has been split for clarity
@@ -19,24 +30,77 @@ pub enum Error {
     /// This was previously `GeoJsonUnknownType`, but has been split for clarity
     #[error("Expected a Feature mapping, but got a `{0}`")]
     NotAFeature(String),
+    /// A position had a z coordinate, converting with `ZHandling::ErrorOnZ`
+    #[error("Encountered the position `{0:?}`, whose z coordinate a Geo type can't keep")]
+    UnexpectedZCoordinate(Vec<f64>),
     #[error("Encountered a mismatch when converting to a Geo type: `{0}`")]
     InvalidGeometryConversion(GValue),
     #[error(
         "Attempted to a convert a feature without a geometry into a geo_types::Geometry: `{0}`"
     )]
-    FeatureHasNoGeometry(Feature),
+    FeatureHasNoGeometry(Box<Feature>),
     #[error("Encountered an unknown 'geometry' object type: `{0}`")]
     GeometryUnknownType(String),
+    /// `GeometryCollection`s were nested more deeply than `ParseOptions::max_depth` allows
+    #[error("Encountered 'GeometryCollection' objects nested too deeply")]
+    MaxDepthExceeded,
+    /// A geometry has no Esri JSON equivalent
+    #[error("Esri JSON can't represent the geometry `{0}`")]
+    EsriUnsupportedGeometry(GValue),
+    /// An Esri JSON geometry had none of the members identifying its type, or a position with
+    /// fewer than two coordinates
+    #[error("Encountered an unrecognized Esri JSON geometry: `{0}`")]
+    EsriUnrecognizedGeometry(Value),
+    /// An object had the member used by another type in place of the one its type needs:
+    /// `geometries` on a `Point`, say, `coordinates` on a `GeometryCollection`, `features` on a
+    /// `Feature`, or `geometry` on a `FeatureCollection`
+    #[error("A `{type_}` object needs a `{expected}` member, but has `{found}` instead")]
+    UnexpectedMember {
+        type_: String,
+        found: String,
+        expected: String,
+    },
+    /// Reading input or writing output failed
+    #[error("Encountered an IO error: {0}")]
+    Io(std::io::Error),
     #[error("Encountered malformed JSON: {0}")]
     MalformedJson(serde_json::error::Error),
+    /// The `features` member of a `FeatureCollection` wasn't an array
+    #[error(
+        "Encountered a non-array type for the 'features' member of a 'FeatureCollection': `{0}`"
+    )]
+    FeaturesExpectedArray(Value),
     #[error("Encountered neither object type nor null type for 'properties' object: `{0}`")]
     PropertiesExpectedObjectOrNull(Value),
     #[error("Encountered neither object type nor null type for 'geometry' field on 'feature' object: `{0}`")]
     FeatureInvalidGeometryValue(Value),
+    #[deprecated(note = "invalid feature ids are now reported as `Error::InvalidFeatureId`")]
     #[error(
         "Encountered neither number type nor string type for 'id' field on 'feature' object: `{0}`"
     )]
     FeatureInvalidIdentifierType(Value),
+    /// The `id` of a `Feature` was neither a string, a number, nor `null`
+    #[error("Encountered a `{found}` for the 'id' field on a 'feature' object, but expected a string or number")]
+    InvalidFeatureId { found: String },
+    /// An integer coordinate can't be represented exactly as an `f64`, with
+    /// `ParseOptions::reject_inexact_coordinates`
+    #[error("Coordinate `{0}` can't be represented exactly as an f64")]
+    CoordinatePrecisionLoss(Value),
+    /// Positions of the same geometry have different numbers of coordinates, or not the number
+    /// given to `ParseOptions::expected_dimension`
+    #[error("Expected positions with {expected} coordinates, but the one at `{path}` has {found}")]
+    MixedDimensions {
+        expected: usize,
+        found: usize,
+        path: String,
+    },
+    /// A key was in both property maps given to `Feature::merge_properties`, with different
+    /// values, under `MergePolicy::Error`
+    #[error("Encountered conflicting values for the property `{key}`")]
+    PropertyConflict { key: String },
+    /// A `Value` wasn't the geometry type it was expected to be
+    #[error("Expected a `{expected}` geometry, found `{found}`")]
+    UnexpectedGeometryType { expected: String, found: String },
     #[error("Expected GeoJSON type `{expected}`, found `{actual}`")]
     ExpectedType { expected: String, actual: String },
     #[error("Expected a String value, but got a `{0}`")]
@@ -50,3 +114,38 @@ pub enum Error {
     #[error("Expected an owned Object, but got `{0}`")]
     ExpectedObjectValue(Value),
 }
+
+/// Converts to an `io::Error`, so that `?` works in functions returning `io::Result`.
+///
+/// `Error::Io` gives back the underlying `io::Error`, and every other variant becomes an
+/// `ErrorKind::InvalidData` error carrying the original as its source.
+impl From<Error> for std::io::Error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Io(error) => error,
+            other => std::io::Error::new(std::io::ErrorKind::InvalidData, other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use std::io;
+
+    #[test]
+    fn into_io_error() {
+        fn parse(s: &str) -> io::Result<crate::GeoJson> {
+            Ok(s.parse::<crate::GeoJson>()?)
+        }
+        let error = parse("[]").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "Expected an owned Object, but got `[]`");
+
+        let not_found = io::Error::new(io::ErrorKind::NotFound, "gone");
+        assert_eq!(
+            io::Error::from(Error::Io(not_found)).kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
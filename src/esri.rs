@@ -0,0 +1,516 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion between GeoJSON and the Esri JSON dialect used by ArcGIS
+
+use serde_json::json;
+
+use crate::algorithm::area::ring_signed_area;
+use crate::json::{JsonObject, JsonValue};
+use crate::{util, Error, Feature, Geometry, Position, Value};
+
+/// GeoJSON coordinates are always WGS 84 longitude / latitude
+const WGS84_WKID: u32 = 4326;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "esri")))]
+impl Feature {
+    /// Convert this feature into an Esri JSON feature, as used by the ArcGIS REST API.
+    ///
+    /// Properties become `attributes`, and the geometry maps to an Esri geometry in WGS 84:
+    /// `Point` to `x` / `y`, `MultiPoint` to `points`, `LineString` and `MultiLineString` to
+    /// `paths`, and `Polygon` and `MultiPolygon` to `rings`. Rings are rewound to Esri's
+    /// convention of clockwise exterior rings and counter-clockwise holes. A feature without a
+    /// geometry produces one without a `geometry` member. The feature `id` is not written,
+    /// since Esri keeps object IDs among the attributes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::EsriUnsupportedGeometry`](enum.Error.html#variant.EsriUnsupportedGeometry)
+    /// for a `GeometryCollection`, which Esri JSON can't represent.
+    ///
+    /// ```
+    /// use geojson::{Feature, Geometry, Value};
+    /// use serde_json::json;
+    ///
+    /// let mut feature = Feature {
+    ///     bbox: None,
+    ///     geometry: Some(Geometry::new(Value::Point(vec![-118.15, 33.80]))),
+    ///     id: None,
+    ///     properties: None,
+    ///     foreign_members: None,
+    /// };
+    /// feature.set_property("name", "Long Beach");
+    ///
+    /// assert_eq!(
+    ///     feature.to_esri_json().unwrap(),
+    ///     json!({
+    ///         "geometry": {"x": -118.15, "y": 33.80, "spatialReference": {"wkid": 4326}},
+    ///         "attributes": {"name": "Long Beach"}
+    ///     })
+    /// );
+    /// ```
+    pub fn to_esri_json(&self) -> Result<JsonValue, Error> {
+        let mut esri = JsonObject::new();
+        if let Some(geometry) = &self.geometry {
+            esri.insert("geometry".to_owned(), esri_geometry(&geometry.value)?);
+        }
+        esri.insert(
+            "attributes".to_owned(),
+            JsonValue::Object(self.properties.clone().unwrap_or_default()),
+        );
+        Ok(JsonValue::Object(esri))
+    }
+
+    /// Parse an Esri JSON feature, such as a member of an ArcGIS REST query response's
+    /// `features`, into a GeoJSON feature.
+    ///
+    /// `attributes` become the properties, and the geometry is mapped back from `x` / `y`,
+    /// `points`, `paths` or `rings`. Esri polygons are a flat list of rings where clockwise rings
+    /// are exteriors and counter-clockwise rings are holes, so each hole is assigned to the
+    /// exterior containing it, and the rings are rewound to RFC 7946 order. A single path or
+    /// polygon produces a `LineString` or `Polygon`, and several produce the `Multi` variant.
+    /// `m` values are dropped. Coordinates are taken as they are, so the geometry should be in
+    /// WGS 84 (`"wkid": 4326`). A missing, `null` or empty geometry produces a feature without
+    /// one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::EsriUnrecognizedGeometry`](enum.Error.html#variant.EsriUnrecognizedGeometry)
+    /// if the geometry has none of the Esri geometry members or a position without both `x`
+    /// and `y`, and
+    /// [`Error::MalformedJson`](enum.Error.html#variant.MalformedJson) if its coordinates aren't
+    /// arrays of numbers.
+    ///
+    /// ```
+    /// use geojson::{Feature, Value};
+    /// use serde_json::json;
+    ///
+    /// let esri = json!({
+    ///     "geometry": {"paths": [[[0.0, 0.0], [1.0, 1.0]]]},
+    ///     "attributes": {"OBJECTID": 7}
+    /// });
+    /// let feature = Feature::from_esri_json(&esri).unwrap();
+    /// assert_eq!(feature.property("OBJECTID"), Some(&json!(7)));
+    /// assert_eq!(
+    ///     feature.geometry.unwrap().value,
+    ///     Value::LineString(vec![vec![0.0, 0.0], vec![1.0, 1.0]])
+    /// );
+    /// ```
+    pub fn from_esri_json(value: &JsonValue) -> Result<Feature, Error> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| Error::ExpectedObjectValue(value.clone()))?;
+        let geometry = match object.get("geometry") {
+            None | Some(JsonValue::Null) => None,
+            Some(geometry) => geojson_value(geometry)?.map(Geometry::new),
+        };
+        let properties = match object.get("attributes") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::Object(attributes)) => Some(attributes.clone()),
+            Some(other) => return Err(Error::PropertiesExpectedObjectOrNull(other.clone())),
+        };
+        Ok(Feature {
+            bbox: None,
+            geometry,
+            id: None,
+            properties,
+            foreign_members: None,
+        })
+    }
+
+    /// Parse an Esri JSON feature like [`from_esri_json`](#method.from_esri_json), also using
+    /// the attribute named `object_id_field` as the feature's `id`.
+    ///
+    /// `object_id_field` is usually the `objectIdFieldName` of the query response. The attribute
+    /// is kept among the properties. If it's missing or `null`, the feature has no `id`.
+    ///
+    /// ```
+    /// use geojson::{feature::Id, Feature};
+    /// use serde_json::json;
+    ///
+    /// let esri = json!({"attributes": {"FID": 12, "name": "Depot"}});
+    /// let feature = Feature::from_esri_json_with_id(&esri, "FID").unwrap();
+    /// assert_eq!(feature.id, Some(Id::Number(12.into())));
+    /// ```
+    pub fn from_esri_json_with_id(
+        value: &JsonValue,
+        object_id_field: &str,
+    ) -> Result<Feature, Error> {
+        let mut feature = Feature::from_esri_json(value)?;
+        if let Some(object_id) = feature.property(object_id_field) {
+            feature.id = util::expect_id(object_id.clone())?;
+        }
+        Ok(feature)
+    }
+}
+
+fn esri_geometry(value: &Value) -> Result<JsonValue, Error> {
+    let mut geometry = match value {
+        Value::Point(position) => {
+            let mut point = json!({ "x": position.first(), "y": position.get(1) });
+            if let Some(z) = position.get(2) {
+                point["z"] = json!(z);
+            }
+            point
+        }
+        Value::MultiPoint(positions) => json!({ "points": positions }),
+        Value::LineString(line) => json!({ "paths": [line] }),
+        Value::MultiLineString(lines) => json!({ "paths": lines }),
+        Value::Polygon(rings) => json!({ "rings": esri_rings(rings) }),
+        Value::MultiPolygon(polygons) => json!({
+            "rings": polygons
+                .iter()
+                .flat_map(|rings| esri_rings(rings))
+                .collect::<Vec<_>>()
+        }),
+        Value::GeometryCollection(_) | Value::Unknown(_) => {
+            return Err(Error::EsriUnsupportedGeometry(value.clone()))
+        }
+    };
+    if !matches!(value, Value::Point(_)) && value.has_z() {
+        geometry["hasZ"] = json!(true);
+    }
+    geometry["spatialReference"] = json!({ "wkid": WGS84_WKID });
+    Ok(geometry)
+}
+
+/// Copy a GeoJSON polygon's rings, winding the exterior clockwise and any holes counter-clockwise
+fn esri_rings(rings: &[Vec<Position>]) -> Vec<Vec<Position>> {
+    rings
+        .iter()
+        .enumerate()
+        .map(|(i, ring)| {
+            let mut ring = ring.clone();
+            let exterior = i == 0;
+            if (ring_signed_area(&ring) > 0.) == exterior {
+                ring.reverse();
+            }
+            ring
+        })
+        .collect()
+}
+
+fn geojson_value(geometry: &JsonValue) -> Result<Option<Value>, Error> {
+    let object = geometry
+        .as_object()
+        .ok_or_else(|| Error::ExpectedObjectValue(geometry.clone()))?;
+    let has_z = object.get("hasZ") == Some(&JsonValue::Bool(true));
+    let has_m = object.get("hasM") == Some(&JsonValue::Bool(true));
+    let positions = |key: &str| -> Result<Vec<Position>, Error> {
+        let mut positions: Vec<Position> =
+            serde_json::from_value(object[key].clone()).map_err(Error::MalformedJson)?;
+        for position in &mut positions {
+            drop_m(position, has_z, has_m);
+        }
+        check_positions(geometry, &positions)?;
+        Ok(positions)
+    };
+    let nested = |key: &str| -> Result<Vec<Vec<Position>>, Error> {
+        let mut parts: Vec<Vec<Position>> =
+            serde_json::from_value(object[key].clone()).map_err(Error::MalformedJson)?;
+        for position in parts.iter_mut().flatten() {
+            drop_m(position, has_z, has_m);
+        }
+        for part in &parts {
+            check_positions(geometry, part)?;
+        }
+        Ok(parts)
+    };
+
+    let value = if let Some(x) = object.get("x") {
+        if x.is_null() {
+            return Ok(None);
+        }
+        let y = object
+            .get("y")
+            .ok_or_else(|| Error::EsriUnrecognizedGeometry(geometry.clone()))?;
+        let mut position = vec![util::expect_f64(x)?, util::expect_f64(y)?];
+        if let Some(z) = object.get("z").filter(|z| !z.is_null()) {
+            position.push(util::expect_f64(z)?);
+        }
+        Value::Point(position)
+    } else if object.contains_key("points") {
+        Value::MultiPoint(positions("points")?)
+    } else if object.contains_key("paths") {
+        let mut paths = nested("paths")?;
+        match paths.len() {
+            0 => return Ok(None),
+            1 => Value::LineString(paths.remove(0)),
+            _ => Value::MultiLineString(paths),
+        }
+    } else if object.contains_key("rings") {
+        let mut polygons = geojson_polygons(nested("rings")?);
+        match polygons.len() {
+            0 => return Ok(None),
+            1 => Value::Polygon(polygons.remove(0)),
+            _ => Value::MultiPolygon(polygons),
+        }
+    } else {
+        return Err(Error::EsriUnrecognizedGeometry(geometry.clone()));
+    };
+    Ok(Some(value))
+}
+
+/// Fails if any of `positions` lacks an `x` or `y`
+fn check_positions(geometry: &JsonValue, positions: &[Position]) -> Result<(), Error> {
+    if positions.iter().any(|position| position.len() < 2) {
+        return Err(Error::EsriUnrecognizedGeometry(geometry.clone()));
+    }
+    Ok(())
+}
+
+/// Remove the `m` component, which follows `z` when there is one
+fn drop_m(position: &mut Position, has_z: bool, has_m: bool) {
+    let m = if has_z { 3 } else { 2 };
+    if has_m && position.len() > m {
+        position.remove(m);
+    }
+}
+
+/// Group a flat list of Esri rings into GeoJSON polygons with RFC 7946 winding
+///
+/// Clockwise rings start polygons, and each counter-clockwise ring becomes a hole of the first
+/// of them that contains it. A hole outside every exterior is kept as a polygon of its own.
+fn geojson_polygons(rings: Vec<Vec<Position>>) -> Vec<Vec<Vec<Position>>> {
+    let (mut exteriors, holes): (Vec<_>, Vec<_>) = rings
+        .into_iter()
+        .filter(|ring| !ring.is_empty())
+        .partition(|ring| ring_signed_area(ring) <= 0.);
+    let mut polygons: Vec<Vec<Vec<Position>>> = exteriors
+        .iter_mut()
+        .map(|exterior| {
+            exterior.reverse();
+            vec![std::mem::take(exterior)]
+        })
+        .collect();
+    for mut hole in holes {
+        match polygons
+            .iter_mut()
+            .find(|polygon| ring_contains(&polygon[0], &hole[0]))
+        {
+            Some(polygon) => {
+                hole.reverse();
+                polygon.push(hole);
+            }
+            None => polygons.push(vec![hole]),
+        }
+    }
+    polygons
+}
+
+/// Whether `point` lies inside `ring`, by ray casting
+fn ring_contains(ring: &[Position], point: &[f64]) -> bool {
+    let (x, y) = (point[0], point[1]);
+    let mut inside = false;
+    for (a, b) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+        if (a[1] > y) != (b[1] > y) && x < (b[0] - a[0]) * (y - a[1]) / (b[1] - a[1]) + a[0] {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Feature, Geometry, Value};
+    use serde_json::json;
+
+    fn feature(value: Value) -> Feature {
+        Feature {
+            bbox: None,
+            geometry: Some(Geometry::new(value)),
+            id: None,
+            properties: None,
+            foreign_members: None,
+        }
+    }
+
+    #[test]
+    fn to_esri_json_rewinds_rings() {
+        // RFC 7946 winding: counter-clockwise exterior, clockwise hole
+        let polygon = Value::Polygon(vec![
+            vec![
+                vec![0., 0.],
+                vec![4., 0.],
+                vec![4., 4.],
+                vec![0., 4.],
+                vec![0., 0.],
+            ],
+            vec![
+                vec![1., 1.],
+                vec![1., 2.],
+                vec![2., 2.],
+                vec![2., 1.],
+                vec![1., 1.],
+            ],
+        ]);
+        let esri = feature(polygon).to_esri_json().unwrap();
+        assert_eq!(
+            esri["geometry"]["rings"],
+            json!([
+                [[0., 0.], [0., 4.], [4., 4.], [4., 0.], [0., 0.]],
+                [[1., 1.], [2., 1.], [2., 2.], [1., 2.], [1., 1.]],
+            ])
+        );
+        assert_eq!(esri["attributes"], json!({}));
+    }
+
+    #[test]
+    fn to_esri_json_paths_and_points() {
+        let line = vec![vec![0., 0., 5.], vec![1., 1., 6.]];
+        let esri = feature(Value::LineString(line.clone()))
+            .to_esri_json()
+            .unwrap();
+        assert_eq!(
+            esri["geometry"],
+            json!({"paths": [line], "hasZ": true, "spatialReference": {"wkid": 4326}})
+        );
+
+        let esri = feature(Value::MultiPoint(vec![vec![1., 2.]]))
+            .to_esri_json()
+            .unwrap();
+        assert_eq!(esri["geometry"]["points"], json!([[1., 2.]]));
+        assert_eq!(esri["geometry"].get("hasZ"), None);
+    }
+
+    #[test]
+    fn to_esri_json_unsupported() {
+        let collection = feature(Value::GeometryCollection(vec![]));
+        assert!(matches!(
+            collection.to_esri_json(),
+            Err(Error::EsriUnsupportedGeometry(_))
+        ));
+
+        let mut empty = collection;
+        empty.geometry = None;
+        assert_eq!(empty.to_esri_json().unwrap(), json!({"attributes": {}}));
+    }
+
+    #[test]
+    fn from_esri_json_assigns_holes() {
+        // Esri winding: clockwise exteriors, counter-clockwise holes, in no particular order
+        let esri = json!({
+            "geometry": {
+                "rings": [
+                    [[1., 1.], [2., 1.], [2., 2.], [1., 2.], [1., 1.]],
+                    [[10., 10.], [10., 11.], [11., 11.], [11., 10.], [10., 10.]],
+                    [[0., 0.], [0., 4.], [4., 4.], [4., 0.], [0., 0.]],
+                ],
+                "spatialReference": {"wkid": 4326}
+            },
+            "attributes": {"name": "parcels"}
+        });
+        let feature = Feature::from_esri_json(&esri).unwrap();
+        assert_eq!(feature.property("name"), Some(&json!("parcels")));
+        assert_eq!(
+            feature.geometry.unwrap().value,
+            Value::MultiPolygon(vec![
+                vec![vec![
+                    vec![10., 10.],
+                    vec![11., 10.],
+                    vec![11., 11.],
+                    vec![10., 11.],
+                    vec![10., 10.]
+                ]],
+                vec![
+                    vec![
+                        vec![0., 0.],
+                        vec![4., 0.],
+                        vec![4., 4.],
+                        vec![0., 4.],
+                        vec![0., 0.]
+                    ],
+                    vec![
+                        vec![1., 1.],
+                        vec![1., 2.],
+                        vec![2., 2.],
+                        vec![2., 1.],
+                        vec![1., 1.]
+                    ],
+                ],
+            ])
+        );
+    }
+
+    #[test]
+    fn esri_round_trip() {
+        let polygon = feature(Value::Polygon(vec![
+            vec![
+                vec![0., 0.],
+                vec![4., 0.],
+                vec![4., 4.],
+                vec![0., 4.],
+                vec![0., 0.],
+            ],
+            vec![
+                vec![1., 1.],
+                vec![1., 2.],
+                vec![2., 2.],
+                vec![2., 1.],
+                vec![1., 1.],
+            ],
+        ]));
+        let point = feature(Value::Point(vec![1., 2., 3.]));
+        let lines = feature(Value::MultiLineString(vec![
+            vec![vec![0., 0.], vec![1., 1.]],
+            vec![vec![2., 2.], vec![3., 3.]],
+        ]));
+        for original in [polygon, point, lines].iter() {
+            let mut parsed = Feature::from_esri_json(&original.to_esri_json().unwrap()).unwrap();
+            parsed.properties = None;
+            assert_eq!(&parsed, original);
+        }
+    }
+
+    #[test]
+    fn from_esri_json_m_values_and_ids() {
+        let esri = json!({
+            "geometry": {"points": [[1., 2., 3., 4.]], "hasZ": true, "hasM": true},
+            "attributes": {"OBJECTID": "a-1"}
+        });
+        let feature = Feature::from_esri_json_with_id(&esri, "OBJECTID").unwrap();
+        assert_eq!(
+            feature.geometry.unwrap().value,
+            Value::MultiPoint(vec![vec![1., 2., 3.]])
+        );
+        assert_eq!(
+            feature.id,
+            Some(crate::feature::Id::String("a-1".to_owned()))
+        );
+
+        let empty = Feature::from_esri_json(&json!({"geometry": {"x": null}})).unwrap();
+        assert_eq!(empty.geometry, None);
+        assert!(matches!(
+            Feature::from_esri_json(&json!({"geometry": {"curveRings": []}})),
+            Err(Error::EsriUnrecognizedGeometry(_))
+        ));
+    }
+
+    #[test]
+    fn from_esri_json_short_positions() {
+        let square = json!([[0, 0], [0, 4], [4, 4], [4, 0], [0, 0]]);
+        for geometry in &[
+            json!({"rings": [[[1], [2], [3], [1]]]}),
+            json!({"rings": [square, [[1]]]}),
+            json!({"paths": [[[0, 0], []]]}),
+            json!({"points": [[1]]}),
+            json!({"x": 1}),
+        ] {
+            assert!(matches!(
+                Feature::from_esri_json(&json!({ "geometry": geometry })),
+                Err(Error::EsriUnrecognizedGeometry(_))
+            ));
+        }
+    }
+}
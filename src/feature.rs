@@ -14,10 +14,11 @@
 
 use std::convert::TryFrom;
 
+use crate::de::{Kind, ObjectMembers};
 use crate::errors::Error;
 use crate::json::{Deserialize, Deserializer, JsonObject, JsonValue, Serialize, Serializer};
-use crate::serde_json::json;
-use crate::{util, Feature};
+use crate::{util, Feature, Geometry, ParseOptions};
+use serde_json::json;
 
 impl<'a> From<&'a Feature> for JsonObject {
     fn from(feature: &'a Feature) -> JsonObject {
@@ -54,6 +55,17 @@ impl<'a> From<&'a Feature> for JsonObject {
 }
 
 impl Feature {
+    /// Create a feature with a `null` geometry and no properties
+    pub fn with_null_geometry() -> Self {
+        Feature {
+            bbox: None,
+            geometry: None,
+            id: None,
+            properties: None,
+            foreign_members: None,
+        }
+    }
+
     pub fn from_json_object(object: JsonObject) -> Result<Self, Error> {
         Self::try_from(object)
     }
@@ -62,6 +74,21 @@ impl Feature {
         Self::try_from(value)
     }
 
+    /// Convert this Feature into a `serde_json::Value`, with the same members as its serialized form
+    pub fn to_json_value(&self) -> JsonValue {
+        JsonValue::Object(JsonObject::from(self))
+    }
+
+    /// Return true iff this feature's geometry isn't `null`
+    pub fn has_geometry(&self) -> bool {
+        self.geometry.is_some()
+    }
+
+    /// Return this feature's geometry, or `None` if it's `null`
+    pub fn geometry_ref(&self) -> Option<&Geometry> {
+        self.geometry.as_ref()
+    }
+
     /// Return the value of this property, if it's set
     pub fn property(&self, key: impl AsRef<str>) -> Option<&JsonValue> {
         self.properties
@@ -91,7 +118,78 @@ impl Feature {
     /// Removes a key from the `properties` map, returning the value at the key if the key
     /// was previously in the `properties` map.
     pub fn remove_property(&mut self, key: impl AsRef<str>) -> Option<JsonValue> {
-        self.properties.as_mut().and_then(|props| props.remove(key.as_ref()))
+        self.properties
+            .as_mut()
+            .and_then(|props| props.remove(key.as_ref()))
+    }
+
+    /// Remove each of `keys` from the `properties` map, if present
+    pub fn without_properties(&mut self, keys: &[&str]) {
+        if let Some(props) = self.properties.as_mut() {
+            for key in keys {
+                props.remove(*key);
+            }
+        }
+    }
+
+    /// Remove every property except those named in `keys`
+    pub fn retain_properties(&mut self, keys: &[&str]) {
+        if let Some(props) = self.properties.as_mut() {
+            let retained = std::mem::take(props)
+                .into_iter()
+                .filter(|(key, _)| keys.contains(&key.as_str()))
+                .collect();
+            *props = retained;
+        }
+    }
+
+    /// Copy every member of `other` into the `properties` map, creating the map if this
+    /// feature has none
+    ///
+    /// A key present in both maps with a different value is a conflict, which `policy`
+    /// resolves. Under `MergePolicy::Error` a conflict leaves the properties untouched and
+    /// returns `Error::PropertyConflict` for the first conflicting key of `other`.
+    ///
+    /// ```
+    /// use geojson::feature::MergePolicy;
+    /// use geojson::Feature;
+    ///
+    /// let mut feature = Feature::with_null_geometry();
+    /// feature.set_property("name", "A");
+    ///
+    /// let mut other = serde_json::Map::new();
+    /// other.insert("name".to_string(), "B".into());
+    /// other.insert("population".to_string(), 3.into());
+    ///
+    /// assert!(feature.merge_properties(&other, MergePolicy::Error).is_err());
+    /// feature.merge_properties(&other, MergePolicy::KeepExisting).unwrap();
+    /// assert_eq!(feature.property("name").unwrap(), "A");
+    /// assert_eq!(feature.property("population").unwrap(), 3);
+    /// ```
+    pub fn merge_properties(
+        &mut self,
+        other: &JsonObject,
+        policy: MergePolicy,
+    ) -> Result<(), Error> {
+        let props = self.properties.get_or_insert_with(JsonObject::new);
+        if policy == MergePolicy::Error {
+            if let Some(key) = other
+                .iter()
+                .find(
+                    |(key, value)| matches!(props.get(*key), Some(existing) if existing != *value),
+                )
+                .map(|(key, _)| key)
+            {
+                return Err(Error::PropertyConflict { key: key.clone() });
+            }
+        }
+        for (key, value) in other {
+            if policy == MergePolicy::KeepExisting && props.contains_key(key) {
+                continue;
+            }
+            props.insert(key.clone(), value.clone());
+        }
+        Ok(())
     }
 
     /// The number of properties
@@ -109,19 +207,135 @@ impl Feature {
             Some(props) => Box::new(props.iter()),
         }
     }
+
+    /// Whether two features have the same content, for assertions where the derived
+    /// `PartialEq` is too strict
+    ///
+    /// Compared:
+    ///
+    /// * the geometries' values, exactly, including the order of positions and their foreign
+    ///   members
+    /// * the ids, with numbers compared as JSON numbers, so `1` and `1.0` differ
+    /// * the properties and the feature's foreign members, as sets of key / value pairs
+    ///
+    /// Ignored:
+    ///
+    /// * the `bbox` of both the feature and its geometry
+    /// * the order of keys, at any depth
+    /// * the difference between no properties and an empty `properties` object, which are
+    ///   serialized the same way
+    ///
+    /// ```
+    /// use geojson::{Feature, Geometry, Value};
+    ///
+    /// let mut a = Feature::with_null_geometry();
+    /// a.geometry = Some(Geometry::new(Value::Point(vec![1.0, 2.0])));
+    /// a.set_property("name", "a");
+    /// let mut b = a.clone();
+    /// b.bbox = Some(vec![1.0, 2.0, 1.0, 2.0]);
+    /// assert!(a.semantically_eq(&b));
+    /// assert_ne!(a, b);
+    ///
+    /// b.set_property("name", "b");
+    /// assert!(!a.semantically_eq(&b));
+    /// ```
+    pub fn semantically_eq(&self, other: &Feature) -> bool {
+        fn same_members(a: Option<&JsonObject>, b: Option<&JsonObject>) -> bool {
+            let (a_len, b_len) = (a.map_or(0, JsonObject::len), b.map_or(0, JsonObject::len));
+            a_len == b_len
+                && a.into_iter()
+                    .flatten()
+                    .all(|(key, value)| b.and_then(|b| b.get(key)) == Some(value))
+        }
+
+        let same_geometry = match (&self.geometry, &other.geometry) {
+            (None, None) => true,
+            (Some(a), Some(b)) => {
+                a.value == b.value
+                    && same_members(a.foreign_members.as_ref(), b.foreign_members.as_ref())
+            }
+            _ => false,
+        };
+        same_geometry
+            && self.id == other.id
+            && same_members(self.properties.as_ref(), other.properties.as_ref())
+            && same_members(
+                self.foreign_members.as_ref(),
+                other.foreign_members.as_ref(),
+            )
+    }
+
+    /// Split this feature into one feature per part of its geometry, as
+    /// [`Value::explode`](enum.Value.html#method.explode) does, each with a copy of the
+    /// `id`, `properties` and foreign members
+    ///
+    /// The geometry's foreign members are copied onto each part as well. `bbox`es are left out,
+    /// since they would bound the whole geometry rather than the part. A feature without a
+    /// geometry, or whose geometry has no parts, is returned unchanged as the only element.
+    ///
+    /// ```
+    /// use geojson::{Feature, Geometry, Value};
+    ///
+    /// let mut feature = Feature::with_null_geometry();
+    /// feature.geometry = Some(Geometry::new(Value::MultiPoint(vec![vec![1.0, 2.0], vec![3.0, 4.0]])));
+    /// feature.set_property("name", "stops");
+    ///
+    /// let parts = feature.explode();
+    /// assert_eq!(parts.len(), 2);
+    /// assert_eq!(parts[1].geometry.as_ref().unwrap().value, Value::Point(vec![3.0, 4.0]));
+    /// assert_eq!(parts[1].property("name").unwrap(), "stops");
+    /// ```
+    pub fn explode(&self) -> Vec<Feature> {
+        let geometry = match &self.geometry {
+            Some(geometry) => geometry,
+            None => return vec![self.clone()],
+        };
+        let parts = geometry.value.explode();
+        if parts.is_empty() {
+            return vec![self.clone()];
+        }
+        parts
+            .into_iter()
+            .map(|value| Feature {
+                bbox: None,
+                geometry: Some(Geometry {
+                    bbox: None,
+                    value,
+                    foreign_members: geometry.foreign_members.clone(),
+                }),
+                id: self.id.clone(),
+                properties: self.properties.clone(),
+                foreign_members: self.foreign_members.clone(),
+            })
+            .collect()
+    }
 }
 
 impl TryFrom<JsonObject> for Feature {
     type Error = Error;
 
-    fn try_from(mut object: JsonObject) -> Result<Self, Error> {
+    fn try_from(object: JsonObject) -> Result<Self, Error> {
+        Self::from_json_object_with(object, &ParseOptions::default())
+    }
+}
+
+impl Feature {
+    pub(crate) fn from_json_object_with(
+        mut object: JsonObject,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
         let res = &*util::expect_type(&mut object)?;
+        util::check_feature_member(
+            res,
+            object.contains_key("geometry"),
+            object.contains_key("features"),
+        )?;
         match res {
             "Feature" => Ok(Feature {
-                geometry: util::get_geometry(&mut object)?,
+                geometry: util::get_geometry(&mut object, options)?,
                 properties: util::get_properties(&mut object)?,
                 id: util::get_id(&mut object)?,
-                bbox: util::get_bbox(&mut object)?,
+                bbox: util::get_bbox(&mut object, options)?,
                 foreign_members: util::get_foreign_members(object)?,
             }),
             _ => Err(Error::NotAFeature(res.to_string())),
@@ -155,18 +369,28 @@ impl<'de> Deserialize<'de> for Feature {
     where
         D: Deserializer<'de>,
     {
-        use serde::de::Error as SerdeError;
-
-        let val = JsonObject::deserialize(deserializer)?;
-
-        Feature::from_json_object(val).map_err(|e| D::Error::custom(e.to_string()))
+        ObjectMembers::deserialize(deserializer, Kind::Feature)?.into_feature()
     }
 }
 
+/// How `Feature::merge_properties` resolves a key present in both property maps
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Keep the feature's own value
+    KeepExisting,
+    /// Replace the feature's value with the incoming one
+    Overwrite,
+    /// Fail with `Error::PropertyConflict` if the values differ
+    Error,
+}
+
 /// Feature identifier
 ///
+/// Numeric identifiers are kept as a `serde_json::Number`, so integers are written back as
+/// integers, e.g. `7` rather than `7.0`.
+///
 /// [GeoJSON Format Specification § 3.2](https://tools.ietf.org/html/rfc7946#section-3.2)
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Id {
     String(String),
     Number(serde_json::Number),
@@ -350,22 +574,32 @@ mod tests {
     fn decode_feature_with_invalid_id_type_object() {
         let feature_json_str = "{\"geometry\":{\"coordinates\":[1.1,2.1],\"type\":\"Point\"},\"id\":{},\"properties\":{},\"type\":\"Feature\"}";
         let result = match feature_json_str.parse::<GeoJson>() {
-            Err(Error::FeatureInvalidIdentifierType(_)) => true,
+            Err(Error::InvalidFeatureId { found }) => found == "object",
             Ok(_) => false,
             _ => false,
         };
-        assert_eq!(result, true,)
+        assert!(result)
     }
 
     #[test]
-    fn decode_feature_with_invalid_id_type_null() {
-        let feature_json_str = "{\"geometry\":{\"coordinates\":[1.1,2.1],\"type\":\"Point\"},\"id\":null,\"properties\":{},\"type\":\"Feature\"}";
+    fn decode_feature_with_invalid_id_type_bool() {
+        let feature_json_str = "{\"geometry\":{\"coordinates\":[1.1,2.1],\"type\":\"Point\"},\"id\":true,\"properties\":{},\"type\":\"Feature\"}";
         let result = match feature_json_str.parse::<GeoJson>() {
-            Err(Error::FeatureInvalidIdentifierType(_)) => true,
+            Err(Error::InvalidFeatureId { found }) => found == "boolean",
             Ok(_) => false,
             _ => false,
         };
-        assert_eq!(result, true,)
+        assert!(result)
+    }
+
+    #[test]
+    fn decode_feature_with_id_type_null() {
+        let feature_json_str = "{\"geometry\":{\"coordinates\":[1.1,2.1],\"type\":\"Point\"},\"id\":null,\"properties\":{},\"type\":\"Feature\"}";
+        let feature = match feature_json_str.parse::<GeoJson>() {
+            Ok(GeoJson::Feature(f)) => f,
+            _ => unreachable!(),
+        };
+        assert_eq!(feature.id, None);
     }
 
     #[test]
@@ -409,13 +643,13 @@ mod tests {
 
         assert_eq!(feature.len_properties(), 0);
         assert_eq!(feature.property("foo"), None);
-        assert_eq!(feature.contains_property("foo"), false);
+        assert!(!feature.contains_property("foo"));
         assert_eq!(feature.properties_iter().collect::<Vec<_>>(), vec![]);
 
         feature.set_property("foo", 12);
         assert_eq!(feature.property("foo"), Some(&json!(12)));
         assert_eq!(feature.len_properties(), 1);
-        assert_eq!(feature.contains_property("foo"), true);
+        assert!(feature.contains_property("foo"));
         assert_eq!(
             feature.properties_iter().collect::<Vec<_>>(),
             vec![(&"foo".to_string(), &json!(12))]
@@ -424,7 +658,217 @@ mod tests {
         assert_eq!(Some(json!(12)), feature.remove_property("foo"));
         assert_eq!(feature.property("foo"), None);
         assert_eq!(feature.len_properties(), 0);
-        assert_eq!(feature.contains_property("foo"), false);
+        assert!(!feature.contains_property("foo"));
         assert_eq!(feature.properties_iter().collect::<Vec<_>>(), vec![]);
     }
+
+    #[test]
+    fn null_geometry_helpers() {
+        let null = crate::Feature::with_null_geometry();
+        assert!(!null.has_geometry());
+        assert_eq!(null.geometry_ref(), None);
+        assert_eq!(
+            encode(&null),
+            "{\"geometry\":null,\"properties\":{},\"type\":\"Feature\"}"
+        );
+
+        let located = feature();
+        assert!(located.has_geometry());
+        assert_eq!(located.geometry_ref(), located.geometry.as_ref());
+    }
+
+    #[cfg(feature = "arbitrary_precision")]
+    #[test]
+    fn property_numbers_keep_their_precision() {
+        let json = concat!(
+            r#"{"geometry":{"coordinates":[1.5,2.25],"type":"Point"},"#,
+            r#""properties":{"big":123456789012345678,"precise":0.10000000000000000000001},"#,
+            r#""type":"Feature"}"#
+        );
+
+        let via_object = match json.parse::<GeoJson>().unwrap() {
+            GeoJson::Feature(feature) => feature,
+            _ => unreachable!(),
+        };
+        let via_serde: Feature = serde_json::from_str(json).unwrap();
+        assert_eq!(via_object, via_serde);
+        assert_eq!(
+            via_serde.geometry.as_ref().unwrap().value,
+            Value::Point(vec![1.5, 2.25])
+        );
+        assert_eq!(via_object.to_string(), json);
+        assert_eq!(serde_json::to_string(&via_serde).unwrap(), json);
+    }
+
+    #[test]
+    fn null_and_empty_properties() {
+        use serde_json::json;
+
+        let parse = |properties: &str| {
+            let json = format!(
+                r#"{{"type": "Feature", "geometry": null, "properties": {}}}"#,
+                properties
+            );
+            let parsed = match json.parse::<GeoJson>() {
+                Ok(GeoJson::Feature(feature)) => Ok(feature.properties),
+                Ok(_) => unreachable!(),
+                Err(err) => Err(err),
+            };
+            let deserialized = serde_json::from_str::<Feature>(&json).map(|f| f.properties);
+            assert_eq!(parsed.is_ok(), deserialized.is_ok());
+            if let (Ok(parsed), Ok(deserialized)) = (&parsed, &deserialized) {
+                assert_eq!(parsed, deserialized);
+            }
+            parsed
+        };
+
+        assert_eq!(parse("null").unwrap(), None);
+        assert_eq!(parse("{}").unwrap(), properties());
+        assert_eq!(
+            parse(r#"{"a": 1}"#).unwrap(),
+            json!({"a": 1}).as_object().cloned()
+        );
+        for invalid in &["[]", "1", "\"a\"", "true"] {
+            match parse(invalid) {
+                Err(Error::PropertiesExpectedObjectOrNull(value)) => {
+                    assert_eq!(value.to_string(), *invalid)
+                }
+                other => panic!("expected an error for {}, got {:?}", invalid, other),
+            }
+        }
+    }
+
+    #[test]
+    fn numeric_ids_keep_their_type() {
+        let mut feature = Feature::with_null_geometry();
+        feature.id = Some(feature::Id::Number(7.into()));
+        assert_eq!(
+            feature.to_string(),
+            r#"{"geometry":null,"id":7,"properties":{},"type":"Feature"}"#
+        );
+
+        for (input, expected) in [("42", "42"), ("-3", "-3"), ("1.5", "1.5"), ("2.0", "2.0")] {
+            let json = format!(
+                r#"{{"type":"Feature","geometry":null,"properties":null,"id":{}}}"#,
+                input
+            );
+            let via_object = json.parse::<GeoJson>().unwrap().to_string();
+            let via_serde =
+                serde_json::to_string(&serde_json::from_str::<Feature>(&json).unwrap()).unwrap();
+            for output in [via_object, via_serde] {
+                assert!(
+                    output.contains(&format!(r#""id":{}"#, expected)),
+                    "{}",
+                    output
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn semantically_eq() {
+        use serde_json::json;
+
+        let mut a: Feature = serde_json::from_value(json!({
+            "type": "Feature",
+            "id": 1,
+            "bbox": [0, 0, 1, 1],
+            "geometry": {"type": "LineString", "coordinates": [[0, 0], [1, 1]], "bbox": [0, 0, 1, 1]},
+            "properties": {"a": 1, "b": {"x": [1, 2], "y": null}},
+            "title": "lines",
+        }))
+        .unwrap();
+        let mut b: Feature = serde_json::from_value(json!({
+            "title": "lines",
+            "properties": {"b": {"y": null, "x": [1, 2]}, "a": 1},
+            "geometry": {"coordinates": [[0, 0], [1, 1]], "type": "LineString"},
+            "id": 1,
+            "type": "Feature",
+        }))
+        .unwrap();
+        assert!(a.semantically_eq(&b));
+        assert!(b.semantically_eq(&a));
+
+        b.geometry.as_mut().unwrap().value.reverse_coords();
+        assert!(!a.semantically_eq(&b));
+        b.geometry.as_mut().unwrap().value.reverse_coords();
+        b.id = Some(feature::Id::String("1".to_string()));
+        assert!(!a.semantically_eq(&b));
+        b.id = a.id.clone();
+        b.remove_property("a");
+        assert!(!a.semantically_eq(&b));
+        a.remove_property("a");
+        a.foreign_members = None;
+        assert!(!a.semantically_eq(&b));
+
+        let mut empty = Feature::with_null_geometry();
+        let none = empty.clone();
+        empty.properties = properties();
+        assert!(empty.semantically_eq(&none));
+    }
+
+    #[test]
+    fn feature_property_projection() {
+        use serde_json::json;
+
+        let mut feature = feature();
+        feature.properties = None;
+        feature.without_properties(&["foo"]);
+        feature.retain_properties(&["foo"]);
+        assert_eq!(feature.properties, None);
+
+        feature.set_property("foo", 1);
+        feature.set_property("bar", 2);
+        feature.set_property("baz", 3);
+        feature.without_properties(&["bar", "missing"]);
+        assert_eq!(
+            feature.properties,
+            json!({"foo": 1, "baz": 3}).as_object().cloned()
+        );
+
+        feature.retain_properties(&["baz"]);
+        assert_eq!(feature.properties, json!({"baz": 3}).as_object().cloned());
+    }
+
+    #[test]
+    fn merge_properties() {
+        use feature::MergePolicy;
+        use serde_json::json;
+
+        let other = json!({"name": "B", "kind": "city"})
+            .as_object()
+            .cloned()
+            .unwrap();
+        let merged = |policy| {
+            let mut feature = feature();
+            feature.properties = json!({"name": "A", "kind": "city"}).as_object().cloned();
+            feature
+                .merge_properties(&other, policy)
+                .map(|_| feature.properties)
+        };
+
+        assert_eq!(
+            merged(MergePolicy::KeepExisting).unwrap(),
+            json!({"name": "A", "kind": "city"}).as_object().cloned()
+        );
+        assert_eq!(
+            merged(MergePolicy::Overwrite).unwrap(),
+            json!({"name": "B", "kind": "city"}).as_object().cloned()
+        );
+        match merged(MergePolicy::Error) {
+            Err(Error::PropertyConflict { key }) => assert_eq!(key, "name"),
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+
+        // Equal values don't conflict, and a missing map is created
+        let mut feature = feature();
+        feature.properties = None;
+        feature
+            .merge_properties(&other, MergePolicy::Error)
+            .unwrap();
+        feature
+            .merge_properties(&other, MergePolicy::Error)
+            .unwrap();
+        assert_eq!(feature.properties, Some(other));
+    }
 }
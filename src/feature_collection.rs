@@ -12,12 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::convert::TryFrom;
 
+use crate::de::{Kind, ObjectMembers};
 use crate::errors::Error;
+use crate::feature::Id;
 use crate::json::{Deserialize, Deserializer, JsonObject, JsonValue, Serialize, Serializer};
-use crate::serde_json::json;
-use crate::{util, Bbox, Feature};
+use crate::ser::content_hash;
+use crate::{util, Bbox, Feature, ParseOptions, SerializeOptions};
+use serde_json::json;
 
 /// Feature Collection Objects
 ///
@@ -47,7 +51,7 @@ use crate::{util, Bbox, Feature};
 /// );
 /// # }
 /// ```
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct FeatureCollection {
     /// Bounding Box
     ///
@@ -83,7 +87,39 @@ impl<'a> From<&'a FeatureCollection> for JsonObject {
     }
 }
 
+impl From<Vec<Feature>> for FeatureCollection {
+    fn from(features: Vec<Feature>) -> FeatureCollection {
+        FeatureCollection {
+            bbox: None,
+            features,
+            foreign_members: None,
+        }
+    }
+}
+
 impl FeatureCollection {
+    /// A collection with no features, `bbox` or foreign members
+    pub const EMPTY: FeatureCollection = FeatureCollection::empty();
+
+    /// Returns a collection with no features, `bbox` or foreign members, without allocating
+    ///
+    /// ```
+    /// use geojson::FeatureCollection;
+    ///
+    /// let collection = FeatureCollection::empty();
+    /// assert!(collection.features.is_empty());
+    /// assert_eq!(collection.features.capacity(), 0);
+    /// assert_eq!(collection, FeatureCollection::default());
+    /// assert_eq!(collection, FeatureCollection::EMPTY);
+    /// ```
+    pub const fn empty() -> Self {
+        FeatureCollection {
+            bbox: None,
+            features: Vec::new(),
+            foreign_members: None,
+        }
+    }
+
     pub fn from_json_object(object: JsonObject) -> Result<Self, Error> {
         Self::try_from(object)
     }
@@ -91,16 +127,321 @@ impl FeatureCollection {
     pub fn from_json_value(value: JsonValue) -> Result<Self, Error> {
         Self::try_from(value)
     }
+
+    /// Parse either a FeatureCollection object or a bare JSON array of Feature objects, as
+    /// returned by some APIs
+    ///
+    /// A collection parsed from an array has no `bbox` or foreign members. Every element of the
+    /// array must be a valid Feature.
+    ///
+    /// ```
+    /// use geojson::FeatureCollection;
+    /// use serde_json::json;
+    ///
+    /// let features = json!([
+    ///     {"type": "Feature", "geometry": null, "properties": {"name": "a"}},
+    ///     {"type": "Feature", "geometry": null, "properties": {"name": "b"}}
+    /// ]);
+    /// let collection = FeatureCollection::from_features_json(features).unwrap();
+    /// assert_eq!(collection.features.len(), 2);
+    /// ```
+    pub fn from_features_json(value: JsonValue) -> Result<Self, Error> {
+        match value {
+            JsonValue::Array(features) => Ok(FeatureCollection::from(
+                features
+                    .into_iter()
+                    .map(Feature::try_from)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+            other => Self::try_from(other),
+        }
+    }
+
+    /// Convert this FeatureCollection into a `serde_json::Value`, with the same members as its serialized form
+    pub fn to_json_value(&self) -> JsonValue {
+        JsonValue::Object(JsonObject::from(self))
+    }
+
+    /// Serialize the features as newline-delimited GeoJSON, one feature per line with each
+    /// line ending in `\n`, as [`SerializeOptions::to_ndjson_writer`] writes them with the
+    /// default options
+    ///
+    /// The collection's own `bbox` and foreign members aren't written. Each line parses back
+    /// into the feature it was written from.
+    ///
+    /// [`SerializeOptions::to_ndjson_writer`]: struct.SerializeOptions.html#method.to_ndjson_writer
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection};
+    ///
+    /// let collection = FeatureCollection::from(vec![Feature::with_null_geometry(); 2]);
+    /// let line = r#"{"geometry":null,"properties":{},"type":"Feature"}"#;
+    /// assert_eq!(
+    ///     collection.to_ndjson_string().unwrap(),
+    ///     format!("{}\n{}\n", line, line)
+    /// );
+    /// ```
+    pub fn to_ndjson_string(&self) -> Result<String, Error> {
+        let mut written = Vec::new();
+        SerializeOptions::new()
+            .to_ndjson_writer(&mut written, &self.features)
+            .map_err(Error::MalformedJson)?;
+        Ok(String::from_utf8(written).expect("serde_json writes UTF-8"))
+    }
+
+    /// An empty collection with room for at least `capacity` features before reallocating
+    ///
+    /// ```
+    /// use geojson::{estimate_feature_count, FeatureCollection};
+    ///
+    /// let input = br#"{"type": "FeatureCollection", "features": [{}, {}, {}]}"#;
+    /// let capacity = estimate_feature_count(input).unwrap_or(0);
+    /// let collection = FeatureCollection::with_capacity(capacity);
+    /// assert!(collection.features.capacity() >= 3);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        FeatureCollection::from(Vec::with_capacity(capacity))
+    }
+
+    /// Reserve room for at least `additional` more features, as `Vec::reserve` does
+    pub fn reserve(&mut self, additional: usize) {
+        self.features.reserve(additional)
+    }
+
+    /// Consume this collection, returning its features
+    pub fn into_features(self) -> Vec<Feature> {
+        self.features
+    }
+
+    /// Move the features out of this collection, leaving it with none
+    ///
+    /// The collection's `bbox` is left as it was.
+    pub fn take_features(&mut self) -> Vec<Feature> {
+        std::mem::take(&mut self.features)
+    }
+
+    /// Return the numeric value of a property for each feature, in feature order
+    ///
+    /// The entry is `None` for features where the property is missing or isn't a number.
+    pub fn property_column_f64(&self, key: &str) -> Vec<Option<f64>> {
+        self.property_column(key, JsonValue::as_f64)
+    }
+
+    /// Return the string value of a property for each feature, in feature order
+    ///
+    /// The entry is `None` for features where the property is missing or isn't a string.
+    pub fn property_column_str(&self, key: &str) -> Vec<Option<&str>> {
+        self.property_column(key, JsonValue::as_str)
+    }
+
+    /// Return the boolean value of a property for each feature, in feature order
+    ///
+    /// The entry is `None` for features where the property is missing or isn't a boolean.
+    pub fn property_column_bool(&self, key: &str) -> Vec<Option<bool>> {
+        self.property_column(key, JsonValue::as_bool)
+    }
+
+    /// The number of features whose geometry is `null`
+    pub fn count_null_geometries(&self) -> usize {
+        self.features
+            .iter()
+            .filter(|feature| !feature.has_geometry())
+            .count()
+    }
+
+    /// Call `f` on every feature's properties, in feature order
+    ///
+    /// Features without properties are given an empty map first, so `f` can always add keys,
+    /// and every feature's `properties` is `Some` afterwards.
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection};
+    ///
+    /// let mut collection = FeatureCollection::from(vec![Feature::with_null_geometry()]);
+    /// collection.map_properties(|properties| {
+    ///     properties.insert("source".to_string(), "survey".into());
+    /// });
+    /// assert_eq!(
+    ///     collection.features[0].property("source").and_then(|v| v.as_str()),
+    ///     Some("survey")
+    /// );
+    /// ```
+    pub fn map_properties<F: FnMut(&mut JsonObject)>(&mut self, mut f: F) {
+        for feature in &mut self.features {
+            f(feature.properties.get_or_insert_with(JsonObject::new));
+        }
+    }
+
+    /// Keep only the properties named in `keys` on every feature
+    ///
+    /// See [`Feature::retain_properties`](struct.Feature.html#method.retain_properties).
+    pub fn project_properties(&mut self, keys: &[&str]) {
+        for feature in &mut self.features {
+            feature.retain_properties(keys);
+        }
+    }
+
+    /// Remove features whose geometry and properties are both equal to those of an earlier
+    /// feature, keeping the first occurrence of each
+    pub fn dedup_geometries(&mut self) {
+        self.dedup_by(
+            |feature| json!([feature.geometry, feature.properties]),
+            |a, b| a.geometry == b.geometry && a.properties == b.properties,
+        )
+    }
+
+    /// Remove features whose geometry is equal to that of an earlier feature, ignoring
+    /// properties, and keeping the first occurrence of each
+    pub fn dedup_by_geometry(&mut self) {
+        self.dedup_by(
+            |feature| json!(feature.geometry),
+            |a, b| a.geometry == b.geometry,
+        )
+    }
+
+    /// Replace every feature with its parts, as
+    /// [`Feature::explode`](struct.Feature.html#method.explode) splits them, keeping the
+    /// features' order
+    pub fn explode_all(&mut self) {
+        self.features = self.features.iter().flat_map(Feature::explode).collect();
+    }
+
+    /// A collection of up to `n` features chosen uniformly at random, in their original order
+    ///
+    /// The choice is made by reservoir sampling from a generator seeded with `seed`, so the same
+    /// seed always picks the same features. Every feature is kept if there are no more than `n`.
+    /// The sample has the collection's foreign members, but no `bbox`.
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection};
+    ///
+    /// let collection = FeatureCollection::from(vec![Feature::with_null_geometry(); 100]);
+    /// let preview = collection.sample(10, 42);
+    /// assert_eq!(preview.features.len(), 10);
+    /// assert_eq!(preview, collection.sample(10, 42));
+    /// assert_eq!(collection.sample(1000, 42).features.len(), 100);
+    /// ```
+    pub fn sample(&self, n: usize, seed: u64) -> FeatureCollection {
+        // SplitMix64, for a reproducible sequence without depending on a random number crate
+        let mut state = seed;
+        let mut next_random = || {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+        let mut chosen: Vec<usize> = (0..n.min(self.features.len())).collect();
+        for i in n..self.features.len() {
+            let j = (next_random() % (i as u64 + 1)) as usize;
+            if j < n {
+                chosen[j] = i;
+            }
+        }
+        chosen.sort_unstable();
+        FeatureCollection {
+            bbox: None,
+            features: chosen.iter().map(|&i| self.features[i].clone()).collect(),
+            foreign_members: self.foreign_members.clone(),
+        }
+    }
+
+    /// Each `id` shared by more than one feature, with the indices of the features sharing it,
+    /// in order of first occurrence
+    ///
+    /// Features without an `id` are ignored. A string id and a number id are distinct even if
+    /// they are written the same, as with `"1"` and `1`.
+    ///
+    /// ```
+    /// use geojson::feature::Id;
+    /// use geojson::{Feature, FeatureCollection};
+    ///
+    /// let feature = |id: &str| {
+    ///     let mut feature = Feature::with_null_geometry();
+    ///     feature.id = Some(Id::String(id.to_string()));
+    ///     feature
+    /// };
+    /// let collection = FeatureCollection::from(vec![feature("a"), feature("b"), feature("a")]);
+    /// assert_eq!(
+    ///     collection.duplicate_ids(),
+    ///     vec![(Id::String("a".to_string()), vec![0, 2])]
+    /// );
+    /// ```
+    pub fn duplicate_ids(&self) -> Vec<(Id, Vec<usize>)> {
+        let mut indices: HashMap<&Id, Vec<usize>> = HashMap::new();
+        let mut order = Vec::new();
+        for (i, feature) in self.features.iter().enumerate() {
+            if let Some(id) = &feature.id {
+                let shared = indices.entry(id).or_default();
+                if shared.is_empty() {
+                    order.push(id);
+                }
+                shared.push(i);
+            }
+        }
+        order
+            .into_iter()
+            .filter_map(|id| {
+                let shared = indices.remove(id).filter(|shared| shared.len() > 1)?;
+                Some((id.clone(), shared))
+            })
+            .collect()
+    }
+
+    /// Deduplicate features by hashing `key`, falling back to `eq` when hashes collide
+    fn dedup_by(
+        &mut self,
+        key: impl Fn(&Feature) -> JsonValue,
+        eq: impl Fn(&Feature, &Feature) -> bool,
+    ) {
+        let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+        let mut kept: Vec<Feature> = Vec::with_capacity(self.features.len());
+        for feature in self.features.drain(..) {
+            let candidates = seen.entry(content_hash(&key(&feature))).or_default();
+            if candidates.iter().any(|&i| eq(&kept[i], &feature)) {
+                continue;
+            }
+            candidates.push(kept.len());
+            kept.push(feature);
+        }
+        self.features = kept;
+    }
+
+    fn property_column<'a, T>(
+        &'a self,
+        key: &str,
+        f: impl Fn(&'a JsonValue) -> Option<T>,
+    ) -> Vec<Option<T>> {
+        self.features
+            .iter()
+            .map(|feature| feature.property(key).and_then(&f))
+            .collect()
+    }
 }
 
 impl TryFrom<JsonObject> for FeatureCollection {
     type Error = Error;
 
-    fn try_from(mut object: JsonObject) -> Result<Self, Error> {
-        match util::expect_type(&mut object)? {
+    fn try_from(object: JsonObject) -> Result<Self, Error> {
+        Self::from_json_object_with(object, &ParseOptions::default())
+    }
+}
+
+impl FeatureCollection {
+    pub(crate) fn from_json_object_with(
+        mut object: JsonObject,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        let type_ = util::expect_type(&mut object)?;
+        util::check_feature_member(
+            &type_,
+            object.contains_key("geometry"),
+            object.contains_key("features"),
+        )?;
+        match type_ {
             ref type_ if type_ == "FeatureCollection" => Ok(FeatureCollection {
-                bbox: util::get_bbox(&mut object)?,
-                features: util::get_features(&mut object)?,
+                bbox: util::get_bbox(&mut object, options)?,
+                features: util::get_features(&mut object, options)?,
                 foreign_members: util::get_foreign_members(object)?,
             }),
             type_ => Err(Error::ExpectedType {
@@ -137,10 +478,396 @@ impl<'de> Deserialize<'de> for FeatureCollection {
     where
         D: Deserializer<'de>,
     {
-        use serde::de::Error as SerdeError;
+        ObjectMembers::deserialize(deserializer, Kind::FeatureCollection)?.into_feature_collection()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Feature, FeatureCollection};
+    use serde_json::json;
+
+    fn feature_with(key: &str, value: serde_json::Value) -> Feature {
+        let mut feature = Feature::with_null_geometry();
+        feature.set_property(key, value);
+        feature
+    }
+
+    #[test]
+    fn property_columns_are_aligned_with_features() {
+        let collection = FeatureCollection::from(vec![
+            feature_with("pop", json!(10.5)),
+            feature_with("name", json!("Null Island")),
+            feature_with("pop", json!("many")),
+            feature_with("pop", json!(3)),
+        ]);
+
+        assert_eq!(
+            collection.property_column_f64("pop"),
+            vec![Some(10.5), None, None, Some(3.0)]
+        );
+        assert_eq!(
+            collection.property_column_str("pop"),
+            vec![None, None, Some("many"), None]
+        );
+        assert_eq!(
+            collection.property_column_bool("pop"),
+            vec![None, None, None, None]
+        );
+    }
+
+    #[test]
+    fn dedup() {
+        let mut a = feature_with("name", json!("a"));
+        a.geometry = Some(crate::Geometry::new(crate::Value::Point(vec![1.0, 2.0])));
+        let mut b = a.clone();
+        b.set_property("name", "b");
+
+        let mut collection =
+            FeatureCollection::from(vec![a.clone(), b.clone(), a.clone(), b.clone()]);
+        collection.dedup_geometries();
+        assert_eq!(collection.features, vec![a.clone(), b]);
+
+        collection.dedup_by_geometry();
+        assert_eq!(collection.features, vec![a]);
+    }
+
+    #[test]
+    fn from_features_json() {
+        let feature = json!({"type": "Feature", "geometry": null, "properties": {"a": 1}});
+        let wrapped = json!({"type": "FeatureCollection", "features": [feature.clone()]});
+        assert_eq!(
+            FeatureCollection::from_features_json(json!([feature.clone()])).unwrap(),
+            FeatureCollection::from_features_json(wrapped).unwrap()
+        );
+
+        let point = json!({"type": "Point", "coordinates": [1.0, 2.0]});
+        match FeatureCollection::from_features_json(json!([feature, point])) {
+            Err(Error::NotAFeature(actual)) => assert_eq!(actual, "Point"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(FeatureCollection::from_features_json(json!("features")).is_err());
+    }
+
+    #[test]
+    fn take_features() {
+        let features = vec![feature_with("name", json!("a"))];
+        let mut collection = FeatureCollection::from(features.clone());
+        assert_eq!(collection.take_features(), features);
+        assert!(collection.features.is_empty());
+
+        collection.features = features.clone();
+        assert_eq!(collection.into_features(), features);
+    }
+
+    #[test]
+    fn count_null_geometries() {
+        let mut located = feature_with("name", json!("a"));
+        located.geometry = Some(crate::Geometry::new(crate::Value::Point(vec![1.0, 2.0])));
+        let collection = FeatureCollection::from(vec![
+            Feature::with_null_geometry(),
+            located,
+            feature_with("name", json!("b")),
+        ]);
+        assert_eq!(collection.count_null_geometries(), 2);
+    }
+
+    #[test]
+    fn project_properties() {
+        let mut feature = feature_with("name", json!("a"));
+        feature.set_property("secret", 1);
+        feature.set_property("pop", 2);
+        let mut collection =
+            FeatureCollection::from(vec![feature, feature_with("secret", json!(3))]);
+
+        collection.project_properties(&["name", "pop"]);
+        assert_eq!(
+            collection.features[0].properties,
+            json!({"name": "a", "pop": 2}).as_object().cloned()
+        );
+        assert_eq!(collection.features[1].len_properties(), 0);
+    }
+
+    #[test]
+    fn map_properties() {
+        let mut collection = FeatureCollection::from(vec![
+            feature_with("name", json!("a")),
+            Feature::with_null_geometry(),
+        ]);
+        collection.map_properties(|properties| {
+            if let Some(name) = properties.remove("name") {
+                properties.insert("title".to_owned(), name);
+            }
+            properties.insert("n".to_owned(), json!(properties.len()));
+        });
+        assert_eq!(
+            collection.features[0].properties,
+            json!({"title": "a", "n": 1}).as_object().cloned()
+        );
+        assert_eq!(
+            collection.features[1].properties,
+            json!({"n": 0}).as_object().cloned()
+        );
+    }
+
+    #[test]
+    fn explode_all() {
+        use crate::{feature::Id, Geometry, Value};
+
+        let mut multi = feature_with("name", json!("multi"));
+        multi.id = Some(Id::String("m".to_string()));
+        multi.bbox = Some(vec![0., 0., 2., 2.]);
+        multi.geometry = Some(Geometry::new(Value::GeometryCollection(vec![
+            Geometry::new(Value::MultiLineString(vec![
+                vec![vec![0., 0.], vec![1., 1.]],
+                vec![vec![1., 1.], vec![2., 2.]],
+            ])),
+            Geometry::new(Value::Point(vec![0., 2.])),
+        ])));
+        let empty = {
+            let mut empty = feature_with("name", json!("empty"));
+            empty.geometry = Some(Geometry::new(Value::MultiPolygon(vec![])));
+            empty
+        };
+        let null = feature_with("name", json!("null"));
+
+        let mut collection =
+            FeatureCollection::from(vec![multi.clone(), empty.clone(), null.clone()]);
+        collection.explode_all();
+        assert_eq!(collection.features.len(), 5);
+        let values: Vec<_> = collection.features[..3]
+            .iter()
+            .map(|feature| feature.geometry.as_ref().unwrap().value.clone())
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                Value::LineString(vec![vec![0., 0.], vec![1., 1.]]),
+                Value::LineString(vec![vec![1., 1.], vec![2., 2.]]),
+                Value::Point(vec![0., 2.]),
+            ]
+        );
+        for part in &collection.features[..3] {
+            assert_eq!(part.id, multi.id);
+            assert_eq!(part.properties, multi.properties);
+            assert_eq!(part.bbox, None);
+        }
+        assert_eq!(collection.features[3], empty);
+        assert_eq!(collection.features[4], null);
+    }
+
+    #[test]
+    fn ndjson_round_trip() {
+        let mut with_geometry = feature_with("name", json!("a\nb"));
+        with_geometry.geometry = Some(crate::Geometry::new(crate::Value::Point(vec![1., 2.])));
+        let collection = FeatureCollection::from(vec![with_geometry, feature_with("n", json!(1))]);
+        let written = collection.to_ndjson_string().unwrap();
+        let parsed: Vec<Feature> = written
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(parsed, collection.features);
+        assert_eq!(FeatureCollection::EMPTY.to_ndjson_string().unwrap(), "");
+    }
+
+    #[test]
+    fn duplicate_ids() {
+        use crate::feature::Id;
+
+        let ids = [
+            Some(Id::Number(1.into())),
+            None,
+            Some(Id::String("1".to_string())),
+            Some(Id::String("b".to_string())),
+            None,
+            Some(Id::String("b".to_string())),
+            Some(Id::Number(1.into())),
+            Some(Id::String("b".to_string())),
+            Some(Id::String("1".to_string())),
+        ];
+        let features = ids
+            .iter()
+            .map(|id| {
+                let mut feature = Feature::with_null_geometry();
+                feature.id = id.clone();
+                feature
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            FeatureCollection::from(features).duplicate_ids(),
+            vec![
+                (Id::Number(1.into()), vec![0, 6]),
+                (Id::String("1".to_string()), vec![2, 8]),
+                (Id::String("b".to_string()), vec![3, 5, 7]),
+            ]
+        );
+        assert!(FeatureCollection::EMPTY.duplicate_ids().is_empty());
+    }
+
+    #[test]
+    fn wrong_feature_member() {
+        use crate::GeoJson;
+
+        let feature = r#"{"type": "Feature", "features": [], "properties": null}"#;
+        match feature.parse::<GeoJson>() {
+            Err(Error::UnexpectedMember {
+                type_,
+                found,
+                expected,
+            }) => assert_eq!(
+                (type_.as_str(), found.as_str(), expected.as_str()),
+                ("Feature", "features", "geometry")
+            ),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        let error = serde_json::from_str::<Feature>(feature).unwrap_err();
+        assert!(error.to_string().contains("needs a `geometry` member"));
+
+        let collection = r#"{"type": "FeatureCollection", "geometry": null}"#;
+        assert!(matches!(
+            collection.parse::<GeoJson>(),
+            Err(Error::UnexpectedMember { .. })
+        ));
+        let error = serde_json::from_str::<FeatureCollection>(collection).unwrap_err();
+        assert!(error.to_string().contains("needs a `features` member"));
 
-        let val = JsonObject::deserialize(deserializer)?;
+        // Missing and mistyped `features` are told apart
+        assert!(matches!(
+            r#"{"type": "FeatureCollection"}"#.parse::<GeoJson>(),
+            Err(Error::ExpectedProperty(member)) if member == "features"
+        ));
+        assert!(matches!(
+            r#"{"type": "FeatureCollection", "features": {}}"#.parse::<GeoJson>(),
+            Err(Error::FeaturesExpectedArray(_))
+        ));
 
-        FeatureCollection::from_json_object(val).map_err(|e| D::Error::custom(e.to_string()))
+        // The other member is only a mistake if the right one is missing
+        let foreign =
+            r#"{"type": "Feature", "geometry": null, "properties": null, "features": []}"#;
+        assert!(foreign.parse::<GeoJson>().is_ok());
+    }
+
+    #[test]
+    fn sample() {
+        let mut collection = FeatureCollection::from(
+            (0..50)
+                .map(|i| feature_with("i", json!(i)))
+                .collect::<Vec<_>>(),
+        );
+        collection.bbox = Some(vec![0., 0., 1., 1.]);
+        collection.foreign_members = json!({"name": "points"}).as_object().cloned();
+        let indices = |sample: &FeatureCollection| -> Vec<u64> {
+            sample
+                .features
+                .iter()
+                .map(|feature| feature.property("i").unwrap().as_u64().unwrap())
+                .collect()
+        };
+
+        let sample = collection.sample(5, 7);
+        assert_eq!(sample.bbox, None);
+        assert_eq!(sample.foreign_members, collection.foreign_members);
+        let chosen = indices(&sample);
+        assert_eq!(chosen.len(), 5);
+        assert!(chosen.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(indices(&collection.sample(5, 7)), chosen);
+        assert_ne!(indices(&collection.sample(5, 8)), chosen);
+
+        assert!(collection.sample(0, 7).features.is_empty());
+        assert_eq!(
+            indices(&collection.sample(50, 7)),
+            (0..50).collect::<Vec<_>>()
+        );
+        assert_eq!(collection.sample(51, 7).features, collection.features);
+
+        // Over many seeds, every feature is picked about equally often
+        let mut counts = [0; 50];
+        for seed in 0..2000 {
+            for i in indices(&collection.sample(5, seed)) {
+                counts[i as usize] += 1;
+            }
+        }
+        assert!(
+            counts.iter().all(|&count| (140..260).contains(&count)),
+            "{:?}",
+            counts
+        );
+    }
+
+    #[test]
+    fn bboxes_at_every_level_round_trip() {
+        use crate::{FeatureReader, FeatureWriter, GeoJson, SerializeOptions};
+
+        // Deliberately different boxes, so that none can stand in for another
+        let input = json!({
+            "type": "FeatureCollection",
+            "bbox": [-10.0, -10.0, 10.0, 10.0],
+            "features": [{
+                "type": "Feature",
+                "bbox": [-5.0, -5.0, 5.0, 5.0],
+                "geometry": {
+                    "type": "GeometryCollection",
+                    "bbox": [0.0, 0.0, 0.0, 2.0, 2.0, 2.0],
+                    "geometries": [{
+                        "type": "Point",
+                        "bbox": [1.0, 1.0, 1.0, 1.0],
+                        "coordinates": [1.0, 1.0]
+                    }]
+                },
+                "properties": {}
+            }]
+        });
+        let check = |collection: &FeatureCollection| {
+            assert_eq!(collection.bbox, Some(vec![-10.0, -10.0, 10.0, 10.0]));
+            let feature = &collection.features[0];
+            assert_eq!(feature.bbox, Some(vec![-5.0, -5.0, 5.0, 5.0]));
+            let geometry = feature.geometry.as_ref().unwrap();
+            assert_eq!(geometry.bbox, Some(vec![0.0, 0.0, 0.0, 2.0, 2.0, 2.0]));
+            match &geometry.value {
+                crate::Value::GeometryCollection(members) => {
+                    assert_eq!(members[0].bbox, Some(vec![1.0, 1.0, 1.0, 1.0]))
+                }
+                other => panic!("unexpected geometry: {:?}", other),
+            }
+        };
+
+        let input_str = input.to_string();
+        let deserialized: FeatureCollection = serde_json::from_str(&input_str).unwrap();
+        check(&deserialized);
+        let parsed = match input_str.parse::<GeoJson>().unwrap() {
+            GeoJson::FeatureCollection(collection) => collection,
+            other => panic!("unexpected GeoJSON: {:?}", other),
+        };
+        assert_eq!(parsed, deserialized);
+
+        let reparse = |written: &str| {
+            let collection: FeatureCollection = serde_json::from_str(written).unwrap();
+            check(&collection);
+            assert_eq!(
+                serde_json::from_str::<serde_json::Value>(written).unwrap(),
+                input
+            );
+        };
+        reparse(&parsed.to_string());
+        reparse(&SerializeOptions::new().precision(1).to_string(&parsed));
+
+        // Calculated feature boxes replace only the feature's own
+        let mut written = vec![];
+        SerializeOptions::new()
+            .feature_bboxes(true)
+            .to_ndjson_writer(&mut written, &parsed.features)
+            .unwrap();
+        let line: serde_json::Value = serde_json::from_slice(&written).unwrap();
+        assert_eq!(line["bbox"], json!([1.0, 1.0, 1.0, 1.0]));
+        assert_eq!(line["geometry"], input["features"][0]["geometry"]);
+
+        // Streamed features keep their own and their geometries' boxes
+        let mut writer = FeatureWriter::from_writer(vec![]);
+        for feature in FeatureReader::from_reader(input_str.as_bytes()) {
+            writer.write_feature(&feature.unwrap()).unwrap();
+        }
+        let streamed: FeatureCollection =
+            serde_json::from_slice(&writer.finish().unwrap()).unwrap();
+        assert_eq!(streamed.features, parsed.features);
     }
 }
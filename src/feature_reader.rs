@@ -0,0 +1,812 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::io::{self, BufReader, Read, Write};
+
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+
+use crate::bbox::extent_2d;
+use crate::errors::Error;
+use crate::json::{Deserializer, JsonValue};
+use crate::{BboxDimension, Feature, FeatureCollection, FeatureWriter};
+
+/// Stream the features of a `FeatureCollection` one at a time
+///
+/// Rather than building the whole collection in memory, `FeatureReader` scans the input for the
+/// `features` array and parses each member as it is reached. Only one feature is held in memory
+/// at a time.
+///
+/// A feature which fails to parse is yielded as an `Err`, and reading resumes with the next
+/// feature. If the `features` array itself is malformed, for example by a missing comma or stray
+/// characters between features, the reader skips ahead to the next `{` and carries on from there.
+/// Errors outside the `features` array, and I/O errors, end the stream.
+///
+/// Features are only read as they are asked for, so `reader.take(n)` reads just the first `n`,
+/// leaving the rest of the input unread, to preview or cap the ingestion of a large file.
+///
+/// # Example
+///
+/// ```
+/// use geojson::FeatureReader;
+///
+/// let geojson_str = r#"{
+///     "type": "FeatureCollection",
+///     "features": [
+///         {"type": "Feature", "geometry": null, "properties": {"name": "a"}},
+///         {"type": "Feature", "geometry": null, "properties": {"name": "b"}}
+///     ]
+/// }"#;
+///
+/// let reader = FeatureReader::from_reader(geojson_str.as_bytes());
+/// let names: Vec<String> = reader
+///     .map(|feature| feature.unwrap().property("name").unwrap().to_string())
+///     .collect();
+/// assert_eq!(names, vec!["\"a\"", "\"b\""]);
+/// ```
+pub struct FeatureReader<R> {
+    scanner: Scanner<R>,
+    state: State,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Start,
+    First,
+    Rest,
+    /// Skipping ahead to the next feature after an error
+    Resync,
+    Done,
+}
+
+impl<R: Read> FeatureReader<R> {
+    pub fn from_reader(reader: R) -> Self {
+        FeatureReader {
+            scanner: Scanner::new(reader),
+            state: State::Start,
+        }
+    }
+
+    /// Skip over features which failed to parse, yielding only those which succeeded
+    pub fn skip_errors(self) -> impl Iterator<Item = Feature> {
+        self.filter_map(Result::ok)
+    }
+
+    /// Only yield features for which `predicate` returns `true`
+    ///
+    /// Errors are passed through unchanged.
+    pub fn filter_features<F>(
+        self,
+        mut predicate: F,
+    ) -> impl Iterator<Item = Result<Feature, Error>>
+    where
+        F: FnMut(&Feature) -> bool,
+    {
+        self.filter(move |feature| match feature {
+            Ok(feature) => predicate(feature),
+            Err(_) => true,
+        })
+    }
+
+    /// Only yield features whose `key` property equals `value`
+    ///
+    /// The property is checked before the feature is fully parsed, so features which don't match
+    /// are never built. That also means they're never checked to be valid GeoJSON: a feature
+    /// with, say, a malformed `geometry` is only reported as an error if its property matches,
+    /// and is skipped silently otherwise. Malformed JSON, and errors reading the `features` array,
+    /// are always passed through.
+    ///
+    /// ```
+    /// use geojson::FeatureReader;
+    ///
+    /// let geojson_str = r#"{"type": "FeatureCollection", "features": [
+    ///     {"type": "Feature", "geometry": null, "properties": {"kind": "road"}},
+    ///     {"type": "Feature", "geometry": null, "properties": {"kind": "river"}}
+    /// ]}"#;
+    ///
+    /// let rivers = FeatureReader::from_reader(geojson_str.as_bytes())
+    ///     .filter_by_property_eq("kind", "river")
+    ///     .count();
+    /// assert_eq!(rivers, 1);
+    /// ```
+    pub fn filter_by_property_eq(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<JsonValue>,
+    ) -> impl Iterator<Item = Result<Feature, Error>> {
+        let key = key.into();
+        let value = value.into();
+        std::iter::from_fn(move || loop {
+            let raw = match self.next_raw()? {
+                Ok(raw) => raw,
+                Err(e) => return Some(Err(e)),
+            };
+            match property_of(&raw, &key) {
+                Ok(Some(ref property)) if *property == value => return Some(parse_feature(&raw)),
+                Ok(_) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        })
+    }
+
+    /// Return the raw bytes of the next member of the `features` array
+    fn next_raw(&mut self) -> Option<Result<Vec<u8>, Error>> {
+        match self.advance() {
+            Ok(true) => {}
+            Ok(false) => {
+                self.state = State::Done;
+                return None;
+            }
+            Err(e) => {
+                if self.state != State::Resync {
+                    self.state = State::Done;
+                }
+                return Some(Err(e));
+            }
+        }
+
+        let mut raw = Vec::new();
+        match self.scanner.read_value(&mut raw) {
+            Ok(()) => Some(Ok(raw)),
+            Err(e) => {
+                self.state = State::Done;
+                Some(Err(e))
+            }
+        }
+    }
+
+    /// Move to the start of the next feature, returning `false` once the array is exhausted
+    fn advance(&mut self) -> Result<bool, Error> {
+        match self.state {
+            State::Start => {
+                self.scanner.find_features()?;
+                self.state = State::First;
+                self.advance()
+            }
+            State::First => {
+                self.state = State::Rest;
+                Ok(self.scanner.skip_whitespace()? != Some(b']'))
+            }
+            State::Rest => match self.scanner.skip_whitespace()? {
+                Some(b',') => {
+                    self.scanner.next_byte()?;
+                    Ok(true)
+                }
+                Some(b']') => Ok(false),
+                _ => {
+                    self.state = State::Resync;
+                    Err(syntax_error("expected `,` or `]` in `features` array"))
+                }
+            },
+            State::Resync => {
+                self.state = State::Rest;
+                self.scanner.skip_to_feature()
+            }
+            State::Done => Ok(false),
+        }
+    }
+}
+
+impl<R: Read> Iterator for FeatureReader<R> {
+    type Item = Result<Feature, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_raw()
+            .map(|raw| raw.and_then(|raw| parse_feature(&raw)))
+    }
+}
+
+impl FeatureCollection {
+    /// Parse each feature of the `FeatureCollection` in `reader` and pass it to `f`, holding
+    /// only one feature in memory at a time
+    ///
+    /// Unlike [`FeatureReader`](struct.FeatureReader.html), this stops at the first error,
+    /// whether it comes from parsing or from `f`, and returns it.
+    ///
+    /// ```
+    /// use geojson::FeatureCollection;
+    ///
+    /// let geojson_str = r#"{"type": "FeatureCollection", "features": [
+    ///     {"type": "Feature", "geometry": null, "properties": {"name": "a"}},
+    ///     {"type": "Feature", "geometry": null, "properties": {"name": "b"}}
+    /// ]}"#;
+    ///
+    /// let mut names = vec![];
+    /// FeatureCollection::for_each_feature(geojson_str.as_bytes(), |feature| {
+    ///     names.push(feature.property("name").unwrap().to_string());
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// assert_eq!(names, vec!["\"a\"", "\"b\""]);
+    /// ```
+    pub fn for_each_feature<R, F>(reader: R, mut f: F) -> Result<(), Error>
+    where
+        R: Read,
+        F: FnMut(Feature) -> Result<(), Error>,
+    {
+        FeatureReader::from_reader(reader).try_for_each(|feature| f(feature?))
+    }
+
+    /// Parse at most the first `max_features` features of the `FeatureCollection` in
+    /// `reader`, leaving the rest of the input unread
+    ///
+    /// Only the features are kept: the collection's `bbox`, which may not bound them, and its
+    /// foreign members are not read. As with
+    /// [`for_each_feature`](#method.for_each_feature), the first error is returned.
+    ///
+    /// ```
+    /// use geojson::FeatureCollection;
+    ///
+    /// let geojson_str = r#"{"type": "FeatureCollection", "features": [
+    ///     {"type": "Feature", "geometry": null, "properties": {"name": "a"}},
+    ///     {"type": "Feature", "geometry": null, "properties": {"name": "b"}}
+    /// ]}"#;
+    ///
+    /// let preview = FeatureCollection::from_reader_limited(geojson_str.as_bytes(), 1).unwrap();
+    /// assert_eq!(preview.features.len(), 1);
+    /// ```
+    pub fn from_reader_limited<R: Read>(
+        reader: R,
+        max_features: usize,
+    ) -> Result<FeatureCollection, Error> {
+        FeatureReader::from_reader(reader)
+            .take(max_features)
+            .collect::<Result<Vec<_>, _>>()
+            .map(FeatureCollection::from)
+    }
+}
+
+/// Estimates how many features the `FeatureCollection` in `input` has, without parsing them
+///
+/// The members of the `features` array are counted by matching brackets, so this is much
+/// cheaper than a full parse, but the features aren't checked to be valid. If the array is
+/// malformed or cut short, the members before the problem are counted. Returns `None` if
+/// `input` isn't an object with a `features` array.
+///
+/// ```
+/// use geojson::estimate_feature_count;
+///
+/// let geojson_str = r#"{"type": "FeatureCollection", "features": [
+///     {"type": "Feature", "geometry": null, "properties": {"name": "[a}"}},
+///     {"type": "Feature", "geometry": null, "properties": null}
+/// ]}"#;
+/// assert_eq!(estimate_feature_count(geojson_str.as_bytes()), Some(2));
+/// assert_eq!(estimate_feature_count(br#"{"type": "Point", "coordinates": [1, 2]}"#), None);
+/// ```
+pub fn estimate_feature_count(input: &[u8]) -> Option<usize> {
+    let mut scanner = Scanner::new(input);
+    scanner.find_features().ok()?;
+    let mut count = 0;
+    let mut skipped = Vec::new();
+    loop {
+        match scanner.skip_whitespace() {
+            Ok(Some(b']')) | Ok(None) | Err(_) => return Some(count),
+            Ok(Some(_)) => {}
+        }
+        skipped.clear();
+        if scanner.read_value(&mut skipped).is_err() {
+            return Some(count);
+        }
+        count += 1;
+        match scanner.skip_whitespace() {
+            Ok(Some(b',')) => scanner.peeked = None,
+            _ => return Some(count),
+        }
+    }
+}
+
+/// Copy the features of the `FeatureCollection` in `reader` whose geometries intersect or touch
+/// `bbox` to a new `FeatureCollection` written to `writer`, returning how many were written
+///
+/// Features are streamed through a [`FeatureReader`](struct.FeatureReader.html) and a
+/// [`FeatureWriter`](struct.FeatureWriter.html), so only one is held in memory at a time. Each
+/// is tested by the 2D bounding box of its geometry, computed from its coordinates rather than
+/// taken from any `bbox` member, so a feature whose box overlaps the area is kept even if its
+/// geometry itself falls just outside. Features without a geometry are left out.
+///
+/// `bbox` may be a 4- or 6-element box, whose `z` bounds are ignored; one with a west edge
+/// greater than its east edge crosses the antimeridian. Any other length is an
+/// `Error::BboxUnexpectedLength`. The first error while reading or writing is returned, leaving
+/// `writer` with an unfinished collection.
+///
+/// ```
+/// let geojson_str = r#"{"type": "FeatureCollection", "features": [
+///     {"type": "Feature", "geometry": {"type": "Point", "coordinates": [1, 1]}, "properties": {}},
+///     {"type": "Feature", "geometry": {"type": "Point", "coordinates": [5, 5]}, "properties": {}}
+/// ]}"#;
+///
+/// let mut clipped = vec![];
+/// let written = geojson::extract_bbox(geojson_str.as_bytes(), &mut clipped, &[0., 0., 2., 2.])
+///     .unwrap();
+/// assert_eq!(written, 1);
+/// assert!(String::from_utf8(clipped).unwrap().contains("[1.0,1.0]"));
+/// ```
+pub fn extract_bbox<R: Read, W: Write>(reader: R, writer: W, bbox: &[f64]) -> Result<usize, Error> {
+    let [west, south, east, north] = extent_2d(bbox).ok_or(Error::BboxUnexpectedLength {
+        expected: "4 or 6",
+        actual: bbox.len(),
+    })?;
+    let overlaps = |extent: [f64; 4]| {
+        let longitudes = if west <= east {
+            extent[0] <= east && extent[2] >= west
+        } else {
+            extent[2] >= west || extent[0] <= east
+        };
+        longitudes && extent[1] <= north && extent[3] >= south
+    };
+
+    let mut output = FeatureWriter::from_writer(writer);
+    let mut written = 0;
+    for feature in FeatureReader::from_reader(reader) {
+        let feature = feature?;
+        let extent = feature
+            .bbox_calculated_with(BboxDimension::Force2D)
+            .and_then(|bbox| extent_2d(&bbox));
+        if matches!(extent, Some(extent) if overlaps(extent)) {
+            output.write_feature(&feature)?;
+            written += 1;
+        }
+    }
+    output.finish()?;
+    Ok(written)
+}
+
+fn parse_feature(raw: &[u8]) -> Result<Feature, Error> {
+    serde_json::from_slice(raw).map_err(Error::MalformedJson)
+}
+
+fn syntax_error(msg: &str) -> Error {
+    Error::MalformedJson(de::Error::custom(msg))
+}
+
+/// A byte-level scanner which can pick out whole JSON values without parsing them
+struct Scanner<R> {
+    bytes: io::Bytes<BufReader<R>>,
+    peeked: Option<u8>,
+}
+
+impl<R: Read> Scanner<R> {
+    fn new(reader: R) -> Self {
+        Scanner {
+            bytes: BufReader::new(reader).bytes(),
+            peeked: None,
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, Error> {
+        if self.peeked.is_none() {
+            self.peeked = match self.bytes.next() {
+                Some(byte) => Some(byte.map_err(Error::Io)?),
+                None => None,
+            };
+        }
+        Ok(self.peeked)
+    }
+
+    fn next_byte(&mut self) -> Result<Option<u8>, Error> {
+        let byte = self.peek()?;
+        self.peeked = None;
+        Ok(byte)
+    }
+
+    /// Skip a UTF-8 byte order mark at the current position
+    fn skip_bom(&mut self) -> Result<(), Error> {
+        if self.peek()? != Some(0xEF) {
+            return Ok(());
+        }
+        self.peeked = None;
+        if self.next_byte()? == Some(0xBB) && self.next_byte()? == Some(0xBF) {
+            Ok(())
+        } else {
+            Err(syntax_error("expected `{`"))
+        }
+    }
+
+    /// Skip whitespace, returning the next byte without consuming it
+    fn skip_whitespace(&mut self) -> Result<Option<u8>, Error> {
+        while let Some(byte) = self.peek()? {
+            if !byte.is_ascii_whitespace() {
+                return Ok(Some(byte));
+            }
+            self.peeked = None;
+        }
+        Ok(None)
+    }
+
+    /// Skip to the next `{`, returning `false` if the end of the array or input comes first
+    fn skip_to_feature(&mut self) -> Result<bool, Error> {
+        while let Some(byte) = self.peek()? {
+            match byte {
+                b'{' => return Ok(true),
+                b']' => return Ok(false),
+                _ => self.peeked = None,
+            }
+        }
+        Ok(false)
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), Error> {
+        if self.skip_whitespace()? == Some(expected) {
+            self.peeked = None;
+            Ok(())
+        } else {
+            Err(syntax_error(&format!("expected `{}`", expected as char)))
+        }
+    }
+
+    /// Consume the top-level object up to and including the `[` opening its `features` array
+    fn find_features(&mut self) -> Result<(), Error> {
+        self.skip_bom()?;
+        if self.skip_whitespace()?.is_none() {
+            return Err(Error::EmptyInput);
+        }
+        self.expect(b'{')?;
+        if self.skip_whitespace()? == Some(b'}') {
+            return Err(syntax_error("missing `features` member"));
+        }
+        let mut key = Vec::new();
+        let mut skipped = Vec::new();
+        loop {
+            key.clear();
+            self.read_value(&mut key)?;
+            self.expect(b':')?;
+            if key == b"\"features\"" {
+                return self.expect(b'[');
+            }
+            skipped.clear();
+            self.read_value(&mut skipped)?;
+            match self.skip_whitespace()? {
+                Some(b',') => self.peeked = None,
+                Some(b'}') => return Err(syntax_error("missing `features` member")),
+                _ => return Err(syntax_error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    /// Append the raw bytes of the next JSON value to `out`
+    fn read_value(&mut self, out: &mut Vec<u8>) -> Result<(), Error> {
+        match self.skip_whitespace()? {
+            Some(b'"') => self.read_string(out),
+            Some(b'{') | Some(b'[') => self.read_nested(out),
+            Some(_) => {
+                while let Some(byte) = self.peek()? {
+                    if matches!(byte, b',' | b'}' | b']') || byte.is_ascii_whitespace() {
+                        break;
+                    }
+                    out.push(byte);
+                    self.peeked = None;
+                }
+                Ok(())
+            }
+            None => Err(syntax_error("unexpected end of input")),
+        }
+    }
+
+    fn read_string(&mut self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.extend(self.next_byte()?);
+        loop {
+            match self.next_byte()? {
+                Some(b'"') => {
+                    out.push(b'"');
+                    return Ok(());
+                }
+                Some(b'\\') => {
+                    out.push(b'\\');
+                    out.extend(self.next_byte()?);
+                }
+                Some(byte) => out.push(byte),
+                None => return Err(syntax_error("unexpected end of input in string")),
+            }
+        }
+    }
+
+    /// Read an object or array, matching brackets until it is closed
+    fn read_nested(&mut self, out: &mut Vec<u8>) -> Result<(), Error> {
+        let mut depth = 0usize;
+        loop {
+            match self.peek()? {
+                Some(b'"') => {
+                    self.read_string(out)?;
+                    continue;
+                }
+                Some(byte @ b'{') | Some(byte @ b'[') => {
+                    depth += 1;
+                    out.push(byte);
+                }
+                Some(byte @ b'}') | Some(byte @ b']') => {
+                    depth -= 1;
+                    out.push(byte);
+                    if depth == 0 {
+                        self.peeked = None;
+                        return Ok(());
+                    }
+                }
+                Some(byte) => out.push(byte),
+                None => return Err(syntax_error("unexpected end of input")),
+            }
+            self.peeked = None;
+        }
+    }
+}
+
+/// Pull the `key` property out of a raw feature, skipping over everything else
+fn property_of(raw: &[u8], key: &str) -> Result<Option<JsonValue>, Error> {
+    let mut deserializer = serde_json::Deserializer::from_slice(raw);
+    PropertyProbe { key, nested: false }
+        .deserialize(&mut deserializer)
+        .map_err(Error::MalformedJson)
+}
+
+/// Visits a feature object, then its `properties` object, looking for a single key
+struct PropertyProbe<'a> {
+    key: &'a str,
+    nested: bool,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for PropertyProbe<'a> {
+    type Value = Option<JsonValue>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for PropertyProbe<'a> {
+    type Value = Option<JsonValue>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a GeoJSON Feature object")
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut found = None;
+        while let Some(member) = map.next_key::<std::borrow::Cow<str>>()? {
+            if self.nested && member == self.key {
+                found = Some(map.next_value()?);
+            } else if !self.nested && member == "properties" {
+                found = map.next_value_seed(PropertyProbe {
+                    key: self.key,
+                    nested: true,
+                })?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_bbox, FeatureReader};
+    use crate::{Error, FeatureCollection, GeoJson};
+    use serde_json::json;
+
+    const COLLECTION: &str = r#"{
+        "type": "FeatureCollection",
+        "name": "with } and ] in a \"string\"",
+        "bbox": [0, 0, 2, 2],
+        "features": [
+            {"type": "Feature", "geometry": {"type": "Point", "coordinates": [0, 0]}, "properties": {"n": 1}},
+            {"type": "Feature", "geometry": {"type": "Point", "coordinates": [1, 1]}, "properties": {"n": 2}},
+            {"type": "Feature", "geometry": {"type": "Point", "coordinates": [2, 2]}, "properties": {"n": 3}}
+        ]
+    }"#;
+
+    #[test]
+    fn reads_features_in_order() {
+        let features: Vec<_> = FeatureReader::from_reader(COLLECTION.as_bytes())
+            .map(|feature| feature.unwrap())
+            .collect();
+        assert_eq!(features.len(), 3);
+        assert_eq!(features[2].property("n"), Some(&json!(3)));
+    }
+
+    #[test]
+    fn matches_full_parse() {
+        let geojson_str = include_str!("../tests/fixtures/countries.geojson");
+        let collection: crate::FeatureCollection = serde_json::from_str(geojson_str).unwrap();
+        let features: Vec<_> = FeatureReader::from_reader(geojson_str.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(features, collection.features);
+    }
+
+    #[test]
+    fn filters() {
+        let reader = FeatureReader::from_reader(COLLECTION.as_bytes());
+        let odd = reader
+            .filter_features(|feature| feature.property("n").unwrap().as_i64().unwrap() % 2 == 1)
+            .count();
+        assert_eq!(odd, 2);
+
+        let reader = FeatureReader::from_reader(COLLECTION.as_bytes());
+        let features: Vec<_> = reader.filter_by_property_eq("n", 2).collect();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0].as_ref().unwrap().property("n"), Some(&json!(2)));
+
+        // Invalid features are only parsed, and so reported, if they match
+        let geojson_str = r#"{"features": [
+            {"type": "Feature", "geometry": 7, "properties": {"n": 1}},
+            {"type": "Feature", "geometry": 7, "properties": {"n": 2}},
+            {"type": "Feature", "geometry": null, "properties": {"n": 1, "m": tru}}
+        ]}"#;
+        let results: Vec<_> = FeatureReader::from_reader(geojson_str.as_bytes())
+            .filter_by_property_eq("n", 2)
+            .collect();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(Result::is_err));
+    }
+
+    #[test]
+    fn bad_feature_does_not_end_the_stream() {
+        let geojson_str = r#"{"features": [
+            {"type": "Feature", "geometry": null, "properties": null},
+            {"type": "Feature", "geometry": 7, "properties": null},
+            {"type": "Feature", "geometry": null, "properties": null}
+        ]}"#;
+        let results: Vec<_> = FeatureReader::from_reader(geojson_str.as_bytes()).collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(results[2].is_ok());
+    }
+
+    #[test]
+    fn for_each_feature_stops_at_first_error() {
+        let mut seen = vec![];
+        let result = FeatureCollection::for_each_feature(COLLECTION.as_bytes(), |feature| {
+            let n = feature.property("n").unwrap().as_i64().unwrap();
+            seen.push(n);
+            if n == 2 {
+                Err(Error::PropertiesExpectedObjectOrNull(json!(n)))
+            } else {
+                Ok(())
+            }
+        });
+        assert!(matches!(
+            result,
+            Err(Error::PropertiesExpectedObjectOrNull(_))
+        ));
+        assert_eq!(seen, vec![1, 2]);
+
+        let geojson_str = r#"{"features": [
+            {"type": "Feature", "geometry": 7, "properties": null},
+            {"type": "Feature", "geometry": null, "properties": null}
+        ]}"#;
+        let mut count = 0;
+        let result = FeatureCollection::for_each_feature(geojson_str.as_bytes(), |_| {
+            count += 1;
+            Ok(())
+        });
+        assert!(result.is_err());
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn estimate_feature_count() {
+        use super::estimate_feature_count;
+
+        assert_eq!(estimate_feature_count(COLLECTION.as_bytes()), Some(3));
+        assert_eq!(estimate_feature_count(br#"{"features": []}"#), Some(0));
+        assert_eq!(
+            estimate_feature_count(br#"{"features": [{}, {"a": [}"#),
+            Some(1)
+        );
+        assert_eq!(estimate_feature_count(b""), None);
+        assert_eq!(estimate_feature_count(br#"{"features": {}}"#), None);
+    }
+
+    #[test]
+    fn missing_features() {
+        let mut reader = FeatureReader::from_reader(r#"{"type": "FeatureCollection"}"#.as_bytes());
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn resynchronizes_after_malformed_array() {
+        let geojson_str = r#"{"features": [
+            {"type": "Feature", "geometry": null, "properties": {"n": 1}}
+            {"type": "Feature", "geometry": null, "properties": {"n": 2}},
+            oops, {"type": "Feature", "geometry": null, "properties": {"n": 3}} #,
+            {"type": "Feature", "geometry": null, "properties": {"n": 4}}
+        ]}"#;
+        let results: Vec<_> = FeatureReader::from_reader(geojson_str.as_bytes()).collect();
+        let ok: Vec<_> = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .map(|feature| feature.property("n").unwrap().as_i64().unwrap())
+            .collect();
+        assert_eq!(ok, vec![1, 2, 3, 4]);
+        assert!(results.iter().any(|result| result.is_err()));
+
+        let features = FeatureReader::from_reader(geojson_str.as_bytes()).skip_errors();
+        assert_eq!(features.count(), 4);
+    }
+
+    #[test]
+    fn limited_reads_leave_the_rest_unread() {
+        /// Fails the test if the reader gets as far as it
+        struct Unreachable;
+
+        impl std::io::Read for Unreachable {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                panic!("read past the requested features")
+            }
+        }
+
+        let start = &COLLECTION[..COLLECTION.find("[2, 2]").unwrap()];
+        let reader = std::io::Read::chain(start.as_bytes(), Unreachable);
+        let collection = FeatureCollection::from_reader_limited(reader, 2).unwrap();
+        assert_eq!(collection.features.len(), 2);
+        assert_eq!(collection.features[1].property("n"), Some(&json!(2)));
+
+        let all = FeatureCollection::from_reader_limited(COLLECTION.as_bytes(), 10).unwrap();
+        assert_eq!(all.features.len(), 3);
+        let none = FeatureCollection::from_reader_limited(Unreachable, 0).unwrap();
+        assert!(none.features.is_empty());
+    }
+
+    #[test]
+    fn extract_bbox_streams_matching_features() {
+        let geojson_str = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "geometry": {"type": "Point", "coordinates": [179, 0]}, "properties": {"n": 1}},
+            {"type": "Feature", "geometry": {"type": "LineString", "coordinates": [[0, 5], [0, 20]]}, "properties": {"n": 2}},
+            {"type": "Feature", "geometry": null, "properties": {"n": 3}},
+            {"type": "Feature", "geometry": {"type": "Point", "coordinates": [-179, 0]}, "properties": {"n": 4}},
+            {"type": "Feature", "bbox": [0, 0, 1, 1], "geometry": {"type": "Point", "coordinates": [50, 50]}, "properties": {"n": 5}}
+        ]}"#;
+        let extract = |bbox: &[f64]| {
+            let mut output = vec![];
+            let written = extract_bbox(geojson_str.as_bytes(), &mut output, bbox)?;
+            let n: Vec<_> = match String::from_utf8(output).unwrap().parse::<GeoJson>()? {
+                GeoJson::FeatureCollection(collection) => collection
+                    .features
+                    .iter()
+                    .map(|feature| feature.property("n").unwrap().clone())
+                    .collect(),
+                other => panic!("expected a FeatureCollection, got {:?}", other),
+            };
+            assert_eq!(n.len(), written);
+            Ok::<_, Error>(n)
+        };
+
+        // The line touches the box along its top edge
+        assert_eq!(extract(&[-10., -10., 10., 5.]).unwrap(), vec![json!(2)]);
+        assert_eq!(
+            extract(&[170., -1., -170., 1., 0., 0.]).unwrap(),
+            vec![json!(1), json!(4)]
+        );
+        assert!(extract(&[100., 100., 101., 101.]).unwrap().is_empty());
+        assert!(matches!(
+            extract(&[0., 0., 1.]),
+            Err(Error::BboxUnexpectedLength { actual: 3, .. })
+        ));
+
+        let mut output = vec![];
+        let truncated = &geojson_str[..geojson_str.find("null").unwrap()];
+        assert!(extract_bbox(truncated.as_bytes(), &mut output, &[-10., -10., 10., 5.]).is_err());
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{self, Write};
+
+use crate::bbox::Extent;
+use crate::errors::Error;
+use crate::json::{JsonObject, JsonValue};
+use crate::{Bbox, BboxDimension, Feature, SerializeOptions};
+
+const HEADER: &[u8] = br#"{"type":"FeatureCollection","features":["#;
+
+/// Where a [`FeatureWriter`](struct.FeatureWriter.html) given
+/// [`with_auto_bbox`](struct.FeatureWriter.html#method.with_auto_bbox) writes the collection's
+/// `bbox`
+///
+/// The members of a GeoJSON object may come in any order, so a `bbox` after the features is as
+/// valid as one before them, and needs neither buffering nor a seekable writer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BboxPosition {
+    /// Before the `features` array, for readers which want the extent first. Every feature is
+    /// buffered in memory until [`finish`](struct.FeatureWriter.html#method.finish).
+    BeforeFeatures,
+    /// After the `features` array, so features are still written as they come
+    AfterFeatures,
+}
+
+/// Write a `FeatureCollection` one feature at a time
+///
+/// The counterpart of [`FeatureReader`](struct.FeatureReader.html): each feature is serialized
+/// as it is written, so the collection is never held in memory. Call
+/// [`finish`](#method.finish) to close the collection once every feature has been written.
+///
+/// # Example
+///
+/// ```
+/// use geojson::{Feature, FeatureWriter, Geometry, Value};
+///
+/// let mut writer = FeatureWriter::from_writer(vec![]).with_precision(2);
+/// for x in &[0.123, 4.567] {
+///     let mut feature = Feature::with_null_geometry();
+///     feature.geometry = Some(Geometry::new(Value::Point(vec![*x, 1.0])));
+///     writer.write_feature(&feature).unwrap();
+/// }
+/// let written = String::from_utf8(writer.finish().unwrap()).unwrap();
+/// assert_eq!(
+///     written,
+///     r#"{"type":"FeatureCollection","features":[{"geometry":{"coordinates":[0.12,1.0],"type":"Point"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[4.57,1.0],"type":"Point"},"properties":{},"type":"Feature"}]}"#
+/// );
+/// ```
+pub struct FeatureWriter<W: Write> {
+    writer: W,
+    options: SerializeOptions,
+    written: usize,
+    bbox_position: Option<BboxPosition>,
+    extent: Extent,
+    /// The features written so far, with `BboxPosition::BeforeFeatures`
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> FeatureWriter<W> {
+    pub fn from_writer(writer: W) -> Self {
+        FeatureWriter {
+            writer,
+            options: SerializeOptions::new(),
+            written: 0,
+            bbox_position: None,
+            extent: Extent::default(),
+            buffer: vec![],
+        }
+    }
+
+    /// Write features using `options`
+    pub fn with_options(mut self, options: SerializeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Round the positions and `bbox` of each feature to `decimals` decimal places as it is
+    /// written
+    ///
+    /// See [`SerializeOptions::precision`](struct.SerializeOptions.html#method.precision).
+    pub fn with_precision(mut self, decimals: u32) -> Self {
+        self.options = self.options.precision(decimals);
+        self
+    }
+
+    /// Give the collection a `bbox` of every written feature's geometry, at `position`
+    ///
+    /// The box is calculated as by
+    /// [`FeatureCollection::bbox_calculated`](struct.FeatureCollection.html#method.bbox_calculated),
+    /// and is left out if no feature has any positions.
+    ///
+    /// ```
+    /// use geojson::{BboxPosition, Feature, FeatureWriter, Geometry, Value};
+    ///
+    /// let mut writer =
+    ///     FeatureWriter::from_writer(vec![]).with_auto_bbox(BboxPosition::BeforeFeatures);
+    /// for x in &[3.0, -1.0] {
+    ///     let mut feature = Feature::with_null_geometry();
+    ///     feature.geometry = Some(Geometry::new(Value::Point(vec![*x, 1.0])));
+    ///     writer.write_feature(&feature).unwrap();
+    /// }
+    /// let written = String::from_utf8(writer.finish().unwrap()).unwrap();
+    /// assert!(written.starts_with(r#"{"type":"FeatureCollection","bbox":[-1.0,1.0,3.0,1.0],"features":["#));
+    /// ```
+    pub fn with_auto_bbox(mut self, position: BboxPosition) -> Self {
+        self.bbox_position = Some(position);
+        self
+    }
+
+    /// Write `feature` as the next member of the `features` array
+    ///
+    /// Nothing is written if the options skip the feature, as with
+    /// [`EmptyGeometries::Skip`](enum.EmptyGeometries.html#variant.Skip).
+    pub fn write_feature(&mut self, feature: &Feature) -> Result<(), Error> {
+        if self.options.skips(feature) {
+            return Ok(());
+        }
+        let buffered = self.bbox_position == Some(BboxPosition::BeforeFeatures);
+        let separator: &[u8] = match self.written {
+            0 if buffered => b"",
+            0 => HEADER,
+            _ => b",",
+        };
+        let writer: &mut dyn Write = if buffered {
+            &mut self.buffer
+        } else {
+            &mut self.writer
+        };
+        writer.write_all(separator).map_err(Error::Io)?;
+        self.options
+            .write_value(writer, JsonValue::Object(JsonObject::from(feature)))
+            .map_err(|e| Error::Io(io::Error::from(e)))?;
+        if let (Some(_), Some(geometry)) = (self.bbox_position, &feature.geometry) {
+            geometry
+                .value
+                .coords_iter()
+                .for_each(|position| self.extent.add(position));
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Close the collection, returning the underlying writer
+    pub fn finish(mut self) -> Result<W, Error> {
+        let bbox = self
+            .bbox_position
+            .and(self.extent.bbox(BboxDimension::Auto));
+        match self.bbox_position {
+            Some(BboxPosition::BeforeFeatures) => {
+                self.writer
+                    .write_all(br#"{"type":"FeatureCollection","#)
+                    .map_err(Error::Io)?;
+                if let Some(bbox) = bbox {
+                    self.write_bbox(bbox)?;
+                    self.writer.write_all(b",").map_err(Error::Io)?;
+                }
+                self.writer
+                    .write_all(br#""features":["#)
+                    .map_err(Error::Io)?;
+                self.writer.write_all(&self.buffer).map_err(Error::Io)?;
+                self.writer.write_all(b"]}").map_err(Error::Io)?;
+            }
+            _ => {
+                if self.written == 0 {
+                    self.writer.write_all(HEADER).map_err(Error::Io)?;
+                }
+                self.writer.write_all(b"]").map_err(Error::Io)?;
+                if let Some(bbox) = bbox {
+                    self.writer.write_all(b",").map_err(Error::Io)?;
+                    self.write_bbox(bbox)?;
+                }
+                self.writer.write_all(b"}").map_err(Error::Io)?;
+            }
+        }
+        self.writer.flush().map_err(Error::Io)?;
+        Ok(self.writer)
+    }
+
+    /// Write `bbox` as a `"bbox"` member, rounded like the features
+    fn write_bbox(&mut self, bbox: Bbox) -> Result<(), Error> {
+        self.writer.write_all(br#""bbox":"#).map_err(Error::Io)?;
+        self.options
+            .write_value(&mut self.writer, JsonValue::from(bbox))
+            .map_err(|e| Error::Io(io::Error::from(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BboxPosition, FeatureWriter};
+    use crate::{EmptyGeometries, Feature, FeatureCollection, Geometry, SerializeOptions, Value};
+
+    #[test]
+    fn round_trips_through_feature_collection() {
+        let writer = FeatureWriter::from_writer(vec![]);
+        let empty: FeatureCollection = serde_json::from_slice(&writer.finish().unwrap()).unwrap();
+        assert!(empty.features.is_empty());
+
+        let geojson_str = include_str!("../tests/fixtures/countries.geojson");
+        let collection: FeatureCollection = serde_json::from_str(geojson_str).unwrap();
+        let mut writer = FeatureWriter::from_writer(vec![]).with_precision(1);
+        for feature in &collection.features {
+            writer.write_feature(feature).unwrap();
+        }
+        let written: FeatureCollection = serde_json::from_slice(&writer.finish().unwrap()).unwrap();
+        assert_eq!(written.features.len(), collection.features.len());
+        let first = written.features[0].geometry.as_ref().unwrap();
+        assert_eq!(first.value.max_coordinate_precision(), 1);
+        assert_eq!(
+            written.features[0].properties,
+            collection.features[0].properties
+        );
+    }
+
+    #[test]
+    fn auto_bbox() {
+        let point = |x: f64, y: f64| {
+            let mut feature = Feature::with_null_geometry();
+            feature.geometry = Some(Geometry::new(Value::Point(vec![x, y])));
+            feature
+        };
+        let features = [
+            point(1.234, 5.),
+            Feature::with_null_geometry(),
+            point(-2., 0.5),
+        ];
+        let write = |position| {
+            let options = SerializeOptions::new()
+                .precision(1)
+                .empty_geometries(EmptyGeometries::Skip);
+            let mut writer = FeatureWriter::from_writer(vec![])
+                .with_options(options)
+                .with_auto_bbox(position);
+            // Skipped features are left out of the box
+            let mut empty = Feature::with_null_geometry();
+            empty.geometry = Some(Geometry::new(Value::LineString(vec![])));
+            writer.write_feature(&empty).unwrap();
+            for feature in &features {
+                writer.write_feature(feature).unwrap();
+            }
+            String::from_utf8(writer.finish().unwrap()).unwrap()
+        };
+
+        let before = write(BboxPosition::BeforeFeatures);
+        let after = write(BboxPosition::AfterFeatures);
+        assert!(before
+            .starts_with(r#"{"type":"FeatureCollection","bbox":[-2.0,0.5,1.2,5.0],"features":[{"#));
+        assert!(after.ends_with(r#"}],"bbox":[-2.0,0.5,1.2,5.0]}"#));
+
+        let before: FeatureCollection = serde_json::from_str(&before).unwrap();
+        let after: FeatureCollection = serde_json::from_str(&after).unwrap();
+        assert_eq!(before, after);
+        assert_eq!(before.features.len(), 3);
+
+        // Without positions there is no box to write
+        for position in &[BboxPosition::BeforeFeatures, BboxPosition::AfterFeatures] {
+            let mut writer = FeatureWriter::from_writer(vec![]).with_auto_bbox(*position);
+            writer.write_feature(&features[1]).unwrap();
+            let written = writer.finish().unwrap();
+            assert!(!String::from_utf8(written.clone()).unwrap().contains("bbox"));
+            let collection: FeatureCollection = serde_json::from_slice(&written).unwrap();
+            assert_eq!(collection.features.len(), 1);
+        }
+    }
+}
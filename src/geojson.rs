@@ -12,12 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::de::{Kind, ObjectMembers};
 use crate::errors::Error;
 use crate::json::{self, Deserialize, Deserializer, JsonObject, JsonValue, Serialize, Serializer};
-use crate::serde;
-use crate::{Feature, FeatureCollection, Geometry};
+use crate::{util, Feature, FeatureCollection, Geometry, ParseOptions, Value};
+use serde::de::{DeserializeOwned, Error as _, IgnoredAny};
 use std::convert::TryFrom;
 use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
 use std::str::FromStr;
 
 /// GeoJSON Objects
@@ -75,6 +79,12 @@ impl From<Geometry> for GeoJson {
     }
 }
 
+impl From<Value> for GeoJson {
+    fn from(value: Value) -> Self {
+        GeoJson::Geometry(Geometry::new(value))
+    }
+}
+
 impl From<Feature> for GeoJson {
     fn from(feature: Feature) -> Self {
         GeoJson::Feature(feature)
@@ -143,6 +153,69 @@ impl GeoJson {
         Self::try_from(object)
     }
 
+    /// Returns `true` if this is a `Geometry`
+    pub fn is_geometry(&self) -> bool {
+        matches!(self, GeoJson::Geometry(_))
+    }
+
+    /// Returns the `Geometry`, if this is one
+    pub fn as_geometry(&self) -> Option<&Geometry> {
+        match self {
+            GeoJson::Geometry(geometry) => Some(geometry),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Geometry`, if this is one, consuming `self`
+    pub fn into_geometry_opt(self) -> Option<Geometry> {
+        match self {
+            GeoJson::Geometry(geometry) => Some(geometry),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this is a `Feature`
+    pub fn is_feature(&self) -> bool {
+        matches!(self, GeoJson::Feature(_))
+    }
+
+    /// Returns the `Feature`, if this is one
+    pub fn as_feature(&self) -> Option<&Feature> {
+        match self {
+            GeoJson::Feature(feature) => Some(feature),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Feature`, if this is one, consuming `self`
+    pub fn into_feature_opt(self) -> Option<Feature> {
+        match self {
+            GeoJson::Feature(feature) => Some(feature),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this is a `FeatureCollection`
+    pub fn is_feature_collection(&self) -> bool {
+        matches!(self, GeoJson::FeatureCollection(_))
+    }
+
+    /// Returns the `FeatureCollection`, if this is one
+    pub fn as_feature_collection(&self) -> Option<&FeatureCollection> {
+        match self {
+            GeoJson::FeatureCollection(feature_collection) => Some(feature_collection),
+            _ => None,
+        }
+    }
+
+    /// Returns the `FeatureCollection`, if this is one, consuming `self`
+    pub fn into_feature_collection_opt(self) -> Option<FeatureCollection> {
+        match self {
+            GeoJson::FeatureCollection(feature_collection) => Some(feature_collection),
+            _ => None,
+        }
+    }
+
     /// Converts a JSON Value into a GeoJson object.
     ///
     /// # Example
@@ -206,8 +279,8 @@ impl GeoJson {
     ///     })
     ///    );
     /// ```
-    pub fn to_json_value(self) -> JsonValue {
-        JsonValue::from(self)
+    pub fn to_json_value(&self) -> JsonValue {
+        JsonValue::Object(JsonObject::from(self))
     }
 
     // Deserialize a GeoJson object from an IO stream of JSON
@@ -217,23 +290,101 @@ impl GeoJson {
     {
         serde_json::from_reader(rdr)
     }
+
+    /// Deserialize a GeoJson object from a slice of JSON bytes
+    ///
+    /// A leading UTF-8 byte order mark is skipped.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes = util::strip_bom(bytes);
+        util::expect_non_empty(bytes)?;
+        serde_json::from_slice(bytes).map_err(Error::MalformedJson)
+    }
+
+    /// Read a GeoJson object from the file at `path`.
+    ///
+    /// With the `mmap` feature enabled, the file is memory-mapped and parsed in place, which
+    /// avoids copying very large files onto the heap first. Files which can't be mapped are
+    /// read normally.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let mut file = File::open(path).map_err(Error::Io)?;
+
+        #[cfg(feature = "mmap")]
+        {
+            // Safety: the mapping is read-only and dropped before returning. As with any
+            // memory-mapped file, another process truncating or rewriting it while parsing is
+            // undefined behaviour, which is why this is opt-in.
+            if let Ok(mmap) = unsafe { memmap2::Mmap::map(&file) } {
+                return Self::from_slice(&mmap);
+            }
+        }
+
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).map_err(Error::Io)?;
+        Self::from_slice(&bytes)
+    }
+
+    /// Read GeoJSON objects written one after another, separated by nothing but optional
+    /// whitespace, as some tools stream them instead of in a `FeatureCollection`
+    ///
+    /// Each object may be a geometry, `Feature` or `FeatureCollection`; NDJSON is a special case.
+    /// Iteration ends after the first error, as the rest of the stream can't be found reliably.
+    /// The reader is read a byte at a time, so wrap unbuffered readers in a `BufReader`.
+    ///
+    /// ```
+    /// use geojson::GeoJson;
+    ///
+    /// let stream = r#"{"type": "Point", "coordinates": [1, 2]}
+    ///     {"type": "Feature", "geometry": null, "properties": null}{"type": "Point", "coordinates": [3, 4]}"#;
+    /// let objects: Vec<GeoJson> = GeoJson::parse_many(stream.as_bytes())
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(objects.len(), 3);
+    /// assert!(matches!(objects[1], GeoJson::Feature(_)));
+    /// ```
+    pub fn parse_many<R: Read>(reader: R) -> impl Iterator<Item = Result<GeoJson, Error>> {
+        let mut failed = false;
+        serde_json::Deserializer::from_reader(reader)
+            .into_iter::<GeoJson>()
+            .map_while(move |result| {
+                if failed {
+                    return None;
+                }
+                failed = result.is_err();
+                Some(result.map_err(Error::MalformedJson))
+            })
+    }
 }
 
 impl TryFrom<JsonObject> for GeoJson {
     type Error = Error;
 
     fn try_from(object: JsonObject) -> Result<Self, Self::Error> {
+        Self::from_json_object_with(object, &ParseOptions::default())
+    }
+}
+
+impl GeoJson {
+    pub(crate) fn from_json_object_with(
+        object: JsonObject,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
         let type_ = match object.get("type") {
             Some(json::JsonValue::String(t)) => Type::from_str(t),
             _ => return Err(Error::GeometryUnknownType("type".to_owned())),
         };
-        let type_ = type_.ok_or(Error::EmptyType)?;
         match type_ {
-            Type::Feature => Feature::try_from(object).map(GeoJson::Feature),
-            Type::FeatureCollection => {
-                FeatureCollection::try_from(object).map(GeoJson::FeatureCollection)
+            Some(Type::Feature) => {
+                Feature::from_json_object_with(object, options).map(GeoJson::Feature)
             }
-            _ => Geometry::try_from(object).map(GeoJson::Geometry),
+            Some(Type::FeatureCollection) => {
+                FeatureCollection::from_json_object_with(object, options)
+                    .map(GeoJson::FeatureCollection)
+            }
+            Some(_) => Geometry::from_json_object_with(object, options).map(GeoJson::Geometry),
+            None if options.allow_unknown_geometries => {
+                Geometry::from_json_object_with(object, options).map(GeoJson::Geometry)
+            }
+            None => Err(Error::EmptyType),
         }
     }
 }
@@ -250,6 +401,99 @@ impl TryFrom<JsonValue> for GeoJson {
     }
 }
 
+/// The kind of a GeoJSON object, as found by [`peek_type`](fn.peek_type.html)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeoJsonType {
+    Geometry,
+    Feature,
+    FeatureCollection,
+}
+
+/// Find whether `input` holds a geometry, `Feature` or `FeatureCollection` by reading the
+/// top-level object only as far as its `type` member
+///
+/// Members before `type` are skipped over without being deserialized, and nothing after it is
+/// read, so the rest of the input isn't checked. A leading UTF-8 byte order mark is skipped.
+///
+/// # Errors
+///
+/// `Error::ExpectedProperty` if the object has no `type` member, `Error::EmptyType` if its
+/// value isn't a GeoJSON type, and `Error::GeoJsonExpectedObject` if the input isn't an object.
+///
+/// ```
+/// use geojson::{peek_type, GeoJsonType};
+///
+/// let input = br#"{"features": [], "type": "FeatureCollection", "bbox": [0, 0, 1, 1"#;
+/// assert_eq!(peek_type(input).unwrap(), GeoJsonType::FeatureCollection);
+/// assert!(peek_type(br#"{"features": []}"#).is_err());
+/// ```
+pub fn peek_type(input: &[u8]) -> Result<GeoJsonType, Error> {
+    let input = util::strip_bom(input);
+    util::expect_non_empty(input)?;
+    let mut rest = input;
+    if next_byte(&mut rest) != Some(b'{') {
+        let value = serde_json::from_slice(input).map_err(Error::MalformedJson)?;
+        return Err(Error::GeoJsonExpectedObject(value));
+    }
+    let malformed = |expected: &str| {
+        Error::MalformedJson(serde_json::Error::custom(format!(
+            "expected `{}` within the top-level object",
+            expected
+        )))
+    };
+    let mut peeked = rest;
+    if next_byte(&mut peeked) == Some(b'}') {
+        return Err(Error::ExpectedProperty("type".to_string()));
+    }
+    loop {
+        let key: String = next_value(&mut rest)?;
+        if next_byte(&mut rest) != Some(b':') {
+            return Err(malformed(":"));
+        }
+        if key == "type" {
+            return match next_value(&mut rest)? {
+                JsonValue::String(type_) => match Type::from_str(&type_) {
+                    Some(Type::Feature) => Ok(GeoJsonType::Feature),
+                    Some(Type::FeatureCollection) => Ok(GeoJsonType::FeatureCollection),
+                    Some(_) => Ok(GeoJsonType::Geometry),
+                    None => Err(Error::EmptyType),
+                },
+                value => Err(Error::ExpectedStringValue(value)),
+            };
+        }
+        next_value::<IgnoredAny>(&mut rest)?;
+        match next_byte(&mut rest) {
+            Some(b',') => {}
+            Some(b'}') => break,
+            _ => return Err(malformed(",` or `}")),
+        }
+    }
+    Err(Error::ExpectedProperty("type".to_string()))
+}
+
+/// Take the next byte of `input` which isn't JSON whitespace
+fn next_byte(input: &mut &[u8]) -> Option<u8> {
+    let start = input.iter().position(|byte| !b" \t\n\r".contains(byte))?;
+    let byte = input[start];
+    *input = &input[start + 1..];
+    Some(byte)
+}
+
+/// Deserialize the single JSON value at the start of `input`, and advance past it
+fn next_value<T: DeserializeOwned>(input: &mut &[u8]) -> Result<T, Error> {
+    let mut stream = serde_json::Deserializer::from_slice(input).into_iter::<T>();
+    let value = match stream.next() {
+        Some(value) => value.map_err(Error::MalformedJson)?,
+        None => {
+            return Err(Error::MalformedJson(serde_json::Error::custom(
+                "unexpected end of input",
+            )))
+        }
+    };
+    *input = &input[stream.byte_offset()..];
+    Ok(value)
+}
+
 #[derive(PartialEq, Clone, Copy)]
 enum Type {
     Point,
@@ -294,11 +538,7 @@ impl<'de> Deserialize<'de> for GeoJson {
     where
         D: Deserializer<'de>,
     {
-        use serde::de::Error as SerdeError;
-
-        let val = JsonObject::deserialize(deserializer)?;
-
-        GeoJson::from_json_object(val).map_err(|e| D::Error::custom(e.to_string()))
+        ObjectMembers::deserialize(deserializer, Kind::Any)?.into_geojson()
     }
 }
 
@@ -342,6 +582,8 @@ impl FromStr for GeoJson {
 }
 
 fn get_object(s: &str) -> Result<json::JsonObject, Error> {
+    let s = util::strip_bom_str(s);
+    util::expect_non_empty(s.as_bytes())?;
     match ::serde_json::from_str(s) {
         Ok(json::JsonValue::Object(object)) => Ok(object),
         Ok(other) => Err(Error::ExpectedObjectValue(other)),
@@ -351,43 +593,52 @@ fn get_object(s: &str) -> Result<json::JsonObject, Error> {
 
 impl fmt::Display for GeoJson {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        ::serde_json::to_string(self)
-            .map_err(|_| fmt::Error)
-            .and_then(|s| f.write_str(&s))
+        crate::ser::write_to_formatter(f, self)
     }
 }
 
 impl fmt::Display for Feature {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        ::serde_json::to_string(self)
-            .map_err(|_| fmt::Error)
-            .and_then(|s| f.write_str(&s))
+        crate::ser::write_to_formatter(f, self)
     }
 }
 
 impl fmt::Display for Geometry {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        ::serde_json::to_string(self)
-            .map_err(|_| fmt::Error)
-            .and_then(|s| f.write_str(&s))
+        crate::ser::write_to_formatter(f, self)
     }
 }
 
 impl fmt::Display for FeatureCollection {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        ::serde_json::to_string(self)
-            .map_err(|_| fmt::Error)
-            .and_then(|s| f.write_str(&s))
+        crate::ser::write_to_formatter(f, self)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Error, Feature, GeoJson, Geometry, Value};
+    use crate::{Error, Feature, FeatureCollection, GeoJson, Geometry, Value};
     use serde_json::json;
     use std::convert::TryInto;
     use std::str::FromStr;
 
+    #[test]
+    fn display_matches_serialization() {
+        use std::fmt::Write;
+
+        let mut feature = Feature::with_computed_bbox(Geometry::new(Value::Point(vec![1.5, -2.])));
+        feature.set_property("name", "Zürich \"HB\" \u{1F686}\n");
+        let geojson = GeoJson::from(feature.clone());
+
+        let expected = serde_json::to_string(&feature).unwrap();
+        assert_eq!(feature.to_string(), expected);
+        assert_eq!(geojson.to_string(), expected);
+
+        let mut logged = String::from("feature: ");
+        write!(logged, "{}", geojson).unwrap();
+        assert_eq!(logged, format!("feature: {}", expected));
+    }
+
     #[test]
     fn test_geojson_from_reader() {
         let json_str = r#"{
@@ -465,4 +716,252 @@ mod tests {
             Err(Error::MalformedJson(_))
         ))
     }
+
+    #[test]
+    fn geojson_from_value_and_geometry() {
+        let value = Value::Point(vec![1.0, 2.0]);
+        let geojson = GeoJson::from(value.clone());
+        assert_eq!(geojson, GeoJson::Geometry(Geometry::new(value.clone())));
+        assert_eq!(GeoJson::from(Geometry::new(value)), geojson);
+
+        let roundtrip: GeoJson = geojson.to_string().parse().unwrap();
+        assert_eq!(roundtrip, geojson);
+    }
+
+    #[test]
+    fn geojson_from_feature() {
+        let feature = Feature {
+            bbox: None,
+            geometry: Some(Geometry::new(Value::Point(vec![1.0, 2.0]))),
+            id: None,
+            properties: None,
+            foreign_members: None,
+        };
+        let geojson = GeoJson::from(feature.clone());
+        assert_eq!(geojson, GeoJson::Feature(feature.clone()));
+
+        let roundtrip: Feature = geojson.try_into().unwrap();
+        assert_eq!(roundtrip, feature);
+    }
+
+    #[test]
+    fn feature_collection_from_features() {
+        let feature = Feature {
+            bbox: None,
+            geometry: None,
+            id: None,
+            properties: None,
+            foreign_members: None,
+        };
+        let collection = FeatureCollection::from(vec![feature.clone()]);
+        assert_eq!(
+            collection,
+            FeatureCollection {
+                bbox: None,
+                features: vec![feature],
+                foreign_members: None,
+            }
+        );
+
+        let roundtrip: FeatureCollection = GeoJson::from(collection.clone()).try_into().unwrap();
+        assert_eq!(roundtrip, collection);
+    }
+
+    #[test]
+    fn to_json_value_matches_serialization() {
+        let geojson_str = include_str!("../tests/fixtures/countries.geojson");
+        let geojson: GeoJson = geojson_str.parse().unwrap();
+        assert_eq!(geojson.to_json_value().to_string(), geojson.to_string());
+
+        let collection: FeatureCollection = geojson.clone().try_into().unwrap();
+        assert_eq!(collection.to_json_value(), geojson.to_json_value());
+        let feature = &collection.features[0];
+        assert_eq!(feature.to_json_value().to_string(), feature.to_string());
+        let geometry = feature.geometry.as_ref().unwrap();
+        assert_eq!(geometry.to_json_value().to_string(), geometry.to_string());
+    }
+
+    #[test]
+    fn empty_input() {
+        for input in &["", " \n\t\r\n"] {
+            assert!(matches!(input.parse::<GeoJson>(), Err(Error::EmptyInput)));
+            assert!(matches!(
+                GeoJson::from_slice(input.as_bytes()),
+                Err(Error::EmptyInput)
+            ));
+            assert!(matches!(
+                Value::from_slice(input.as_bytes()),
+                Err(Error::EmptyInput)
+            ));
+            assert!(matches!(
+                crate::ParseOptions::new().parse(input),
+                Err(Error::EmptyInput)
+            ));
+            let mut reader = crate::FeatureReader::from_reader(input.as_bytes());
+            assert!(matches!(reader.next(), Some(Err(Error::EmptyInput))));
+            assert!(reader.next().is_none());
+        }
+        assert!(matches!(
+            " {".parse::<GeoJson>(),
+            Err(Error::MalformedJson(_))
+        ));
+    }
+
+    #[test]
+    fn leading_bom() {
+        let geojson_str = r#"{"type": "Point", "coordinates": [1.0, 2.0]}"#;
+        let expected = geojson_str.parse::<GeoJson>().unwrap();
+        let with_bom = format!("\u{FEFF}{}", geojson_str);
+        assert_eq!(with_bom.parse::<GeoJson>().unwrap(), expected);
+        assert_eq!(GeoJson::from_slice(with_bom.as_bytes()).unwrap(), expected);
+        assert_eq!(
+            crate::ParseOptions::new().parse(&with_bom).unwrap(),
+            expected
+        );
+        assert_eq!(
+            GeoJson::Geometry(Value::from_slice(with_bom.as_bytes()).unwrap().into()),
+            expected
+        );
+        assert!(matches!(
+            "\u{FEFF} ".parse::<GeoJson>(),
+            Err(Error::EmptyInput)
+        ));
+
+        let collection = "\u{FEFF}{\"type\": \"FeatureCollection\", \"features\": []}";
+        let mut reader = crate::FeatureReader::from_reader(collection.as_bytes());
+        assert!(reader.next().is_none());
+
+        // Only a single mark at the very start is skipped
+        for input in &[
+            format!("\u{FEFF}\u{FEFF}{}", geojson_str),
+            format!(" \u{FEFF}{}", geojson_str),
+        ] {
+            assert!(input.parse::<GeoJson>().is_err());
+            assert!(GeoJson::from_slice(input.as_bytes()).is_err());
+        }
+        let geojson = GeoJson::from_slice(
+            r#"{"type": "Feature", "geometry": null, "properties": {"name": "\uFEFF"}}"#.as_bytes(),
+        )
+        .unwrap();
+        match geojson {
+            GeoJson::Feature(feature) => assert_eq!(feature.property("name").unwrap(), "\u{FEFF}"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn from_path() {
+        let path =
+            std::env::temp_dir().join(format!("geojson-from-path-{}.json", std::process::id()));
+        let geojson_str = include_str!("../tests/fixtures/countries.geojson");
+        std::fs::write(&path, geojson_str).unwrap();
+        let geojson = GeoJson::from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(geojson.unwrap(), geojson_str.parse::<GeoJson>().unwrap());
+
+        match GeoJson::from_path(std::env::temp_dir().join("geojson-does-not-exist.json")) {
+            Err(Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn variant_accessors() {
+        let geojson = GeoJson::from(FeatureCollection::from(vec![]));
+        assert!(geojson.is_feature_collection());
+        assert!(!geojson.is_feature());
+        assert!(!geojson.is_geometry());
+        assert_eq!(geojson.as_feature_collection().unwrap().features, vec![]);
+        assert_eq!(geojson.as_feature(), None);
+        assert_eq!(geojson.clone().into_geometry_opt(), None);
+        assert_eq!(
+            geojson.into_feature_collection_opt(),
+            Some(FeatureCollection::from(vec![]))
+        );
+
+        let geojson = GeoJson::from(Value::Point(vec![1., 2.]));
+        assert!(geojson.is_geometry());
+        assert_eq!(
+            geojson.as_geometry().map(|geometry| &geometry.value),
+            Some(&Value::Point(vec![1., 2.]))
+        );
+        assert_eq!(geojson.into_feature_opt(), None);
+    }
+
+    #[test]
+    fn parse_many() {
+        let stream = br#"
+            {"type": "FeatureCollection", "features": []}
+            {"type": "Point", "coordinates": [1, 2]}[1, 2]
+            {"type": "Point", "coordinates": [3, 4]}
+        "#;
+        let mut objects = GeoJson::parse_many(&stream[..]);
+        assert!(matches!(
+            objects.next(),
+            Some(Ok(GeoJson::FeatureCollection(collection))) if collection.features.is_empty()
+        ));
+        assert!(matches!(
+            objects.next(),
+            Some(Ok(GeoJson::Geometry(Geometry {
+                value: Value::Point(_),
+                ..
+            })))
+        ));
+        assert!(matches!(objects.next(), Some(Err(Error::MalformedJson(_)))));
+        assert!(objects.next().is_none());
+
+        assert_eq!(GeoJson::parse_many(&b"  \n"[..]).count(), 0);
+    }
+
+    #[test]
+    fn peek_type() {
+        use crate::{peek_type, GeoJsonType};
+
+        let peek = |input: &str| peek_type(input.as_bytes());
+        assert_eq!(
+            peek("\u{feff} { \"type\" : \"Feature\" }").unwrap(),
+            GeoJsonType::Feature
+        );
+        assert_eq!(
+            peek(r#"{"bbox": [0, 0, 1, 1], "coordinates": [[0, 0]], "t\u0079pe": "LineString"}"#)
+                .unwrap(),
+            GeoJsonType::Geometry
+        );
+        // Nothing after `type` is read
+        assert_eq!(
+            peek(r#"{"properties": {"type": "Point"}, "type": "FeatureCollection", "#).unwrap(),
+            GeoJsonType::FeatureCollection
+        );
+
+        assert!(matches!(
+            peek(r#"{"features": [], "crs": null}"#),
+            Err(Error::ExpectedProperty(member)) if member == "type"
+        ));
+        assert!(matches!(peek("{}"), Err(Error::ExpectedProperty(_))));
+        assert!(matches!(
+            peek(r#"{"type": "Circle"}"#),
+            Err(Error::EmptyType)
+        ));
+        assert!(matches!(
+            peek(r#"{"type": 7}"#),
+            Err(Error::ExpectedStringValue(_))
+        ));
+        assert!(matches!(
+            peek("[1, 2]"),
+            Err(Error::GeoJsonExpectedObject(_))
+        ));
+        assert!(matches!(peek("  "), Err(Error::EmptyInput)));
+        for malformed in &[
+            r#"{"a" 1}"#,
+            r#"{"a": 1 "type": "Point"}"#,
+            r#"{"a": "#,
+            "{",
+        ] {
+            assert!(
+                matches!(peek(malformed), Err(Error::MalformedJson(_))),
+                "{}",
+                malformed
+            );
+        }
+    }
 }
@@ -14,10 +14,10 @@
 
 use std::{convert::TryFrom, fmt};
 
+use crate::de::{Kind, ObjectMembers};
 use crate::errors::Error;
 use crate::json::{Deserialize, Deserializer, JsonObject, JsonValue, Serialize, Serializer};
-use crate::serde;
-use crate::{util, Bbox, LineStringType, PointType, PolygonType};
+use crate::{util, Bbox, LineStringType, ParseOptions, PointType, PolygonType};
 
 /// The underlying value for a `Geometry`.
 ///
@@ -80,20 +80,63 @@ pub enum Value {
     ///
     /// [GeoJSON Format Specification § 3.1.8](https://tools.ietf.org/html/rfc7946#section-3.1.8)
     GeometryCollection(Vec<Geometry>),
+
+    /// A geometry object whose `type` isn't one of the above, kept as its raw JSON object,
+    /// `type` member included, so that it is written back out unchanged.
+    ///
+    /// This is only produced when parsing with
+    /// [`ParseOptions::allow_unknown_geometries`](struct.ParseOptions.html#method.allow_unknown_geometries).
+    /// It has no positions, so geometric operations treat it as empty.
+    Unknown(JsonObject),
+}
+
+/// Defines `as_*` and `into_*` accessors for each `Value` variant
+macro_rules! variant_accessors {
+    ($($variant:ident($ty:ty): $name:literal, $as_fn:ident, $into_fn:ident;)*) => {
+        impl Value {
+            $(
+                #[doc = concat!("Returns the coordinates, if this is a `", $name, "`")]
+                pub fn $as_fn(&self) -> Option<&$ty> {
+                    match self {
+                        Value::$variant(inner) => Some(inner),
+                        _ => None,
+                    }
+                }
+
+                #[doc = concat!("Returns the coordinates, if this is a `", $name, "`, consuming `self`")]
+                ///
+                /// Otherwise returns `Error::UnexpectedGeometryType`.
+                pub fn $into_fn(self) -> Result<$ty, Error> {
+                    match self {
+                        Value::$variant(inner) => Ok(inner),
+                        other => Err(Error::UnexpectedGeometryType {
+                            expected: $name.to_owned(),
+                            found: other.type_name().to_owned(),
+                        }),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+variant_accessors! {
+    Point(PointType): "Point", as_point, into_point;
+    MultiPoint(Vec<PointType>): "MultiPoint", as_multi_point, into_multi_point;
+    LineString(LineStringType): "LineString", as_line_string, into_line_string;
+    MultiLineString(Vec<LineStringType>): "MultiLineString", as_multi_line_string, into_multi_line_string;
+    Polygon(PolygonType): "Polygon", as_polygon, into_polygon;
+    MultiPolygon(Vec<PolygonType>): "MultiPolygon", as_multi_polygon, into_multi_polygon;
+    GeometryCollection(Vec<Geometry>): "GeometryCollection", as_geometry_collection, into_geometry_collection;
 }
 
 impl<'a> From<&'a Value> for JsonObject {
     fn from(value: &'a Value) -> JsonObject {
+        if let Value::Unknown(raw) = value {
+            return raw.clone();
+        }
         let mut map = JsonObject::new();
-        let ty = String::from(match value {
-            Value::Point(..) => "Point",
-            Value::MultiPoint(..) => "MultiPoint",
-            Value::LineString(..) => "LineString",
-            Value::MultiLineString(..) => "MultiLineString",
-            Value::Polygon(..) => "Polygon",
-            Value::MultiPolygon(..) => "MultiPolygon",
-            Value::GeometryCollection(..) => "GeometryCollection",
-        });
+        let ty = String::from(value.type_name());
 
         map.insert(String::from("type"), ::serde_json::to_value(&ty).unwrap());
 
@@ -102,7 +145,7 @@ impl<'a> From<&'a Value> for JsonObject {
                 Value::GeometryCollection(..) => "geometries",
                 _ => "coordinates",
             }),
-            ::serde_json::to_value(&value).unwrap(),
+            ::serde_json::to_value(value).unwrap(),
         );
         map
     }
@@ -116,13 +159,184 @@ impl Value {
     pub fn from_json_value(value: JsonValue) -> Result<Self, Error> {
         Self::try_from(value)
     }
+
+    /// Deserialize a `Value` from a slice of GeoJSON geometry bytes.
+    ///
+    /// The coordinates are read straight into the matching `Value` variant, rather than
+    /// being collected into an intermediate `serde_json::Value` tree first. JSON numbers still
+    /// have to be parsed into `f64`s, so nothing is borrowed from `bytes`.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let value = Value::from_slice(br#"{"type": "Point", "coordinates": [1.0, 2.0]}"#).unwrap();
+    /// assert_eq!(value, Value::Point(vec![1.0, 2.0]));
+    /// ```
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes = util::strip_bom(bytes);
+        util::expect_non_empty(bytes)?;
+        serde_json::from_slice(bytes).map_err(Error::MalformedJson)
+    }
+
+    /// The GeoJSON `type` of this value, which for `Unknown` is the type it was parsed with
+    pub(crate) fn type_name(&self) -> &str {
+        match self {
+            Value::Point(..) => "Point",
+            Value::MultiPoint(..) => "MultiPoint",
+            Value::LineString(..) => "LineString",
+            Value::MultiLineString(..) => "MultiLineString",
+            Value::Polygon(..) => "Polygon",
+            Value::MultiPolygon(..) => "MultiPolygon",
+            Value::GeometryCollection(..) => "GeometryCollection",
+            Value::Unknown(raw) => raw.get("type").and_then(JsonValue::as_str).unwrap_or(""),
+        }
+    }
+
+    /// The member geometries of a `GeometryCollection`, or an empty slice for other variants
+    ///
+    /// ```
+    /// use geojson::{geometry_collection, Value};
+    ///
+    /// let collection = geometry_collection(vec![Value::Point(vec![1.0, 2.0])]);
+    /// assert_eq!(collection.geometries().len(), 1);
+    /// assert!(Value::Point(vec![1.0, 2.0]).geometries().is_empty());
+    /// ```
+    pub fn geometries(&self) -> &[Geometry] {
+        match self {
+            Value::GeometryCollection(geometries) => geometries,
+            _ => &[],
+        }
+    }
+
+    /// The member geometries of a `GeometryCollection`, mutably, or an empty slice for other
+    /// variants
+    ///
+    /// Members can be edited, but not added or removed; match on the variant for that.
+    pub fn geometries_mut(&mut self) -> &mut [Geometry] {
+        match self {
+            Value::GeometryCollection(geometries) => geometries,
+            _ => &mut [],
+        }
+    }
+
+    /// Append `part` to this multi-part value, for building one up incrementally
+    ///
+    /// A `MultiPoint`, `MultiLineString` or `MultiPolygon` accepts its single-part type, or
+    /// another value of its own type whose parts are all appended. A `GeometryCollection`
+    /// accepts any value as a new member. Anything else is an
+    /// `Error::UnexpectedGeometryType`, and leaves `self` unchanged.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let mut lines = Value::MultiLineString(vec![]);
+    /// lines.push_part(Value::LineString(vec![vec![0., 0.], vec![1., 1.]])).unwrap();
+    /// lines.push_part(Value::LineString(vec![vec![2., 2.], vec![3., 3.]])).unwrap();
+    /// assert_eq!(lines.as_multi_line_string().unwrap().len(), 2);
+    ///
+    /// assert!(lines.push_part(Value::Point(vec![0., 0.])).is_err());
+    /// ```
+    pub fn push_part(&mut self, part: Value) -> Result<(), Error> {
+        match (self, part) {
+            (Value::MultiPoint(points), Value::Point(point)) => points.push(point),
+            (Value::MultiPoint(points), Value::MultiPoint(more)) => points.extend(more),
+            (Value::MultiLineString(lines), Value::LineString(line)) => lines.push(line),
+            (Value::MultiLineString(lines), Value::MultiLineString(more)) => lines.extend(more),
+            (Value::MultiPolygon(polygons), Value::Polygon(polygon)) => polygons.push(polygon),
+            (Value::MultiPolygon(polygons), Value::MultiPolygon(more)) => polygons.extend(more),
+            (Value::GeometryCollection(geometries), part) => geometries.push(Geometry::new(part)),
+            (this, part) => {
+                let (expected, found) = match this {
+                    Value::MultiPoint(_) => ("Point", part.type_name()),
+                    Value::MultiLineString(_) => ("LineString", part.type_name()),
+                    Value::MultiPolygon(_) => ("Polygon", part.type_name()),
+                    _ => (
+                        "MultiPoint, MultiLineString, MultiPolygon or GeometryCollection",
+                        this.type_name(),
+                    ),
+                };
+                return Err(Error::UnexpectedGeometryType {
+                    expected: expected.to_owned(),
+                    found: found.to_owned(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if this value has no coordinates at all, such as a `LineString` with no
+    /// positions or a `GeometryCollection` whose members are all empty
+    ///
+    /// `Unknown` values are never considered empty.
+    ///
+    /// ```
+    /// use geojson::{Geometry, Value};
+    ///
+    /// assert!(Value::Polygon(vec![]).is_empty());
+    /// assert!(Value::GeometryCollection(vec![Geometry::new(Value::MultiPoint(vec![]))]).is_empty());
+    /// assert!(!Value::Point(vec![0., 0.]).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Value::Unknown(_) => false,
+            _ => self.coords_iter().all(|position| position.is_empty()),
+        }
+    }
+
+    /// Promote a `Point`, `LineString` or `Polygon` to its single-member `Multi` equivalent,
+    /// like PostGIS's `ST_Multi`. Other variants are returned unchanged.
+    ///
+    /// The coordinates are moved, not copied.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let point = Value::Point(vec![1.0, 2.0]);
+    /// assert_eq!(point.to_multi(), Value::MultiPoint(vec![vec![1.0, 2.0]]));
+    /// ```
+    pub fn to_multi(self) -> Value {
+        match self {
+            Value::Point(position) => Value::MultiPoint(vec![position]),
+            Value::LineString(line) => Value::MultiLineString(vec![line]),
+            Value::Polygon(rings) => Value::MultiPolygon(vec![rings]),
+            other => other,
+        }
+    }
+
+    /// Split this value into single-part values: each member of a `Multi` variant, and each
+    /// part of every member of a `GeometryCollection`, recursively
+    ///
+    /// Single-part and `Unknown` values are returned as the only element. An empty `Multi`
+    /// variant or `GeometryCollection` has no parts.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let points = Value::MultiPoint(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    /// assert_eq!(
+    ///     points.explode(),
+    ///     vec![Value::Point(vec![1.0, 2.0]), Value::Point(vec![3.0, 4.0])]
+    /// );
+    /// ```
+    pub fn explode(&self) -> Vec<Value> {
+        match self {
+            Value::MultiPoint(points) => points.iter().cloned().map(Value::Point).collect(),
+            Value::MultiLineString(lines) => lines.iter().cloned().map(Value::LineString).collect(),
+            Value::MultiPolygon(polygons) => polygons.iter().cloned().map(Value::Polygon).collect(),
+            Value::GeometryCollection(geometries) => geometries
+                .iter()
+                .flat_map(|geometry| geometry.value.explode())
+                .collect(),
+            other => vec![other.clone()],
+        }
+    }
 }
 
 impl TryFrom<JsonObject> for Value {
     type Error = Error;
 
     fn try_from(mut object: JsonObject) -> Result<Self, Self::Error> {
-        util::get_value(&mut object)
+        util::get_value(&mut object, &ParseOptions::default())
     }
 }
 
@@ -140,9 +354,7 @@ impl TryFrom<JsonValue> for Value {
 
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        ::serde_json::to_string(&JsonObject::from(self))
-            .map_err(|_| fmt::Error)
-            .and_then(|s| f.write_str(&s))
+        crate::ser::write_to_formatter(f, &JsonObject::from(self))
     }
 }
 
@@ -156,6 +368,7 @@ impl<'a> From<&'a Value> for JsonValue {
             Value::Polygon(ref x) => ::serde_json::to_value(x),
             Value::MultiPolygon(ref x) => ::serde_json::to_value(x),
             Value::GeometryCollection(ref x) => ::serde_json::to_value(x),
+            Value::Unknown(ref x) => ::serde_json::to_value(x),
         }
         .unwrap()
     }
@@ -248,6 +461,87 @@ impl Geometry {
     }
 }
 
+/// Builds a `Value::GeometryCollection` one member at a time
+///
+/// ```
+/// use geojson::{GeometryCollectionBuilder, Geometry, Value};
+///
+/// let collection = GeometryCollectionBuilder::new()
+///     .push_point(1.0, 2.0)
+///     .push_value(Value::LineString(vec![vec![0.0, 0.0], vec![1.0, 1.0]]))
+///     .build();
+///
+/// assert_eq!(
+///     collection,
+///     Value::GeometryCollection(vec![
+///         Geometry::new(Value::Point(vec![1.0, 2.0])),
+///         Geometry::new(Value::LineString(vec![vec![0.0, 0.0], vec![1.0, 1.0]])),
+///     ])
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GeometryCollectionBuilder {
+    geometries: Vec<Geometry>,
+}
+
+impl GeometryCollectionBuilder {
+    /// Returns a builder for an empty collection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a `Point` at `x`, `y`
+    pub fn push_point(self, x: f64, y: f64) -> Self {
+        self.push_value(Value::Point(vec![x, y]))
+    }
+
+    /// Add a `LineString` through `positions`
+    pub fn push_line_string(self, positions: LineStringType) -> Self {
+        self.push_value(Value::LineString(positions))
+    }
+
+    /// Add a `Polygon` with the given rings
+    pub fn push_polygon(self, rings: PolygonType) -> Self {
+        self.push_value(Value::Polygon(rings))
+    }
+
+    /// Add any `Value`, wrapped in a `Geometry` without a `bbox` or foreign members
+    pub fn push_value(self, value: Value) -> Self {
+        self.push_geometry(Geometry::new(value))
+    }
+
+    /// Add a `Geometry` as-is
+    pub fn push_geometry(mut self, geometry: Geometry) -> Self {
+        self.geometries.push(geometry);
+        self
+    }
+
+    /// Returns the `Value::GeometryCollection` of everything pushed so far
+    pub fn build(self) -> Value {
+        Value::GeometryCollection(self.geometries)
+    }
+}
+
+/// Wrap each of `values` in a `Geometry` and collect them into a `Value::GeometryCollection`
+///
+/// The values may be of any mix of types, and the geometries have no `bbox` or foreign members.
+///
+/// ```
+/// use geojson::{geometry_collection, Geometry, Value};
+///
+/// let values = vec![
+///     Value::Point(vec![1.0, 2.0]),
+///     Value::LineString(vec![vec![0.0, 0.0], vec![1.0, 1.0]]),
+/// ];
+/// assert_eq!(
+///     geometry_collection(values.clone()),
+///     Value::GeometryCollection(values.into_iter().map(Geometry::new).collect())
+/// );
+/// ```
+pub fn geometry_collection(values: impl IntoIterator<Item = Value>) -> Value {
+    Value::GeometryCollection(values.into_iter().map(Geometry::new).collect())
+}
+
 impl<'a> From<&'a Geometry> for JsonObject {
     fn from(geometry: &'a Geometry) -> JsonObject {
         let mut map = JsonObject::from(&geometry.value);
@@ -272,14 +566,13 @@ impl Geometry {
     pub fn from_json_value(value: JsonValue) -> Result<Self, Error> {
         Self::try_from(value)
     }
-}
 
-impl TryFrom<JsonObject> for Geometry {
-    type Error = Error;
-
-    fn try_from(mut object: JsonObject) -> Result<Self, Self::Error> {
-        let bbox = util::get_bbox(&mut object)?;
-        let value = util::get_value(&mut object)?;
+    pub(crate) fn from_json_object_with(
+        mut object: JsonObject,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        let bbox = util::get_bbox(&mut object, options)?;
+        let value = util::get_value(&mut object, options)?;
         let foreign_members = util::get_foreign_members(object)?;
         Ok(Geometry {
             bbox,
@@ -287,6 +580,19 @@ impl TryFrom<JsonObject> for Geometry {
             foreign_members,
         })
     }
+
+    /// Convert this Geometry into a `serde_json::Value`, with the same members as its serialized form
+    pub fn to_json_value(&self) -> JsonValue {
+        JsonValue::Object(JsonObject::from(self))
+    }
+}
+
+impl TryFrom<JsonObject> for Geometry {
+    type Error = Error;
+
+    fn try_from(object: JsonObject) -> Result<Self, Self::Error> {
+        Self::from_json_object_with(object, &ParseOptions::default())
+    }
 }
 
 impl TryFrom<JsonValue> for Geometry {
@@ -315,11 +621,7 @@ impl<'de> Deserialize<'de> for Geometry {
     where
         D: Deserializer<'de>,
     {
-        use serde::de::Error as SerdeError;
-
-        let val = JsonObject::deserialize(deserializer)?;
-
-        Geometry::from_json_object(val).map_err(|e| D::Error::custom(e.to_string()))
+        ObjectMembers::deserialize(deserializer, Kind::Geometry)?.into_geometry()
     }
 }
 
@@ -336,7 +638,7 @@ where
 mod tests {
 
     use crate::json::JsonObject;
-    use crate::{GeoJson, Geometry, Value};
+    use crate::{Error, GeoJson, Geometry, GeometryCollectionBuilder, Value};
 
     fn encode(geometry: &Geometry) -> String {
         serde_json::to_string(&geometry).unwrap()
@@ -436,6 +738,34 @@ mod tests {
         assert_eq!(decoded_geometry, geometry);
     }
 
+    #[test]
+    fn foreign_members_round_trip_without_swallowing_required_members() {
+        let geometry_json_str = concat!(
+            r#"{"bbox":[0.0,0.0,1.0,1.0],"geometries":[{"coordinates":[[0.0,0.0],[1.0,1.0]],"#,
+            r#""style":{"stroke":"red"},"type":"LineString"}],"render":"dashed","#,
+            r#""type":"GeometryCollection"}"#
+        );
+
+        let parsed = match decode(geometry_json_str.into()) {
+            GeoJson::Geometry(g) => g,
+            _ => unreachable!(),
+        };
+        let deserialized: Geometry = serde_json::from_str(geometry_json_str).unwrap();
+        assert_eq!(parsed, deserialized);
+
+        assert_eq!(parsed.bbox, Some(vec![0., 0., 1., 1.]));
+        let foreign_members = parsed.foreign_members.as_ref().unwrap();
+        assert_eq!(foreign_members.keys().collect::<Vec<_>>(), vec!["render"]);
+        let line = match &parsed.value {
+            Value::GeometryCollection(geometries) => &geometries[0],
+            _ => unreachable!(),
+        };
+        let line_members = line.foreign_members.as_ref().unwrap();
+        assert_eq!(line_members.keys().collect::<Vec<_>>(), vec!["style"]);
+
+        assert_eq!(encode(&parsed), geometry_json_str);
+    }
+
     #[test]
     fn encode_decode_geometry_collection() {
         let geometry_collection = Geometry {
@@ -467,4 +797,186 @@ mod tests {
         };
         assert_eq!(decoded_geometry, geometry_collection);
     }
+
+    #[test]
+    fn build_geometry_collection() {
+        assert_eq!(
+            GeometryCollectionBuilder::new().build(),
+            Value::GeometryCollection(vec![])
+        );
+
+        let ring = vec![vec![0., 0.], vec![1., 0.], vec![0., 1.], vec![0., 0.]];
+        let collection = GeometryCollectionBuilder::new()
+            .push_polygon(vec![ring.clone()])
+            .push_line_string(ring.clone())
+            .push_geometry(Geometry::new(Value::Point(vec![3., 4.])))
+            .build();
+        assert_eq!(
+            collection,
+            Value::GeometryCollection(vec![
+                Geometry::new(Value::Polygon(vec![ring.clone()])),
+                Geometry::new(Value::LineString(ring)),
+                Geometry::new(Value::Point(vec![3., 4.])),
+            ])
+        );
+    }
+
+    #[test]
+    fn to_multi() {
+        let line = vec![vec![0., 0.], vec![1., 1.]];
+        assert_eq!(
+            Value::LineString(line.clone()).to_multi(),
+            Value::MultiLineString(vec![line.clone()])
+        );
+        assert_eq!(
+            Value::Polygon(vec![line.clone()]).to_multi(),
+            Value::MultiPolygon(vec![vec![line.clone()]])
+        );
+
+        let multi = Value::MultiLineString(vec![line.clone(), line]);
+        assert_eq!(multi.clone().to_multi(), multi);
+        let collection = Value::GeometryCollection(vec![]);
+        assert_eq!(collection.clone().to_multi(), collection);
+    }
+
+    #[test]
+    fn push_part() {
+        let point = |x: f64| Value::Point(vec![x, 0.]);
+        let mut points = Value::MultiPoint(vec![]);
+        points.push_part(point(1.)).unwrap();
+        points
+            .push_part(Value::MultiPoint(vec![vec![2., 0.], vec![3., 0.]]))
+            .unwrap();
+        assert_eq!(points.as_multi_point().unwrap().len(), 3);
+
+        let mut collection = Value::GeometryCollection(vec![]);
+        collection.push_part(point(1.)).unwrap();
+        collection.push_part(points.clone()).unwrap();
+        assert_eq!(collection.geometries()[1].value, points);
+
+        let mut polygons = Value::MultiPolygon(vec![]);
+        match polygons.push_part(Value::LineString(vec![])) {
+            Err(Error::UnexpectedGeometryType { expected, found }) => {
+                assert_eq!(
+                    (expected.as_str(), found.as_str()),
+                    ("Polygon", "LineString")
+                )
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(polygons, Value::MultiPolygon(vec![]));
+
+        let mut single = point(0.);
+        match single.push_part(point(1.)) {
+            Err(Error::UnexpectedGeometryType { found, .. }) => assert_eq!(found, "Point"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(single, point(0.));
+    }
+
+    #[test]
+    fn variant_accessors() {
+        let rings = vec![vec![vec![0., 0.], vec![1., 0.], vec![0., 1.], vec![0., 0.]]];
+        let polygon = Value::Polygon(rings.clone());
+        assert_eq!(polygon.as_polygon(), Some(&rings));
+        assert_eq!(polygon.as_point(), None);
+        assert_eq!(polygon.clone().into_polygon().unwrap(), rings);
+        match polygon.into_multi_polygon() {
+            Err(Error::UnexpectedGeometryType { expected, found }) => {
+                assert_eq!(
+                    (expected.as_str(), found.as_str()),
+                    ("MultiPolygon", "Polygon")
+                )
+            }
+            _ => unreachable!(),
+        }
+
+        let collection = Value::GeometryCollection(vec![]);
+        assert_eq!(collection.as_geometry_collection(), Some(&vec![]));
+        assert!(collection.into_line_string().is_err());
+    }
+
+    #[test]
+    fn wrong_geometry_member() {
+        let point = r#"{"type": "Point", "geometries": []}"#;
+        match point.parse::<GeoJson>() {
+            Err(Error::UnexpectedMember {
+                type_,
+                found,
+                expected,
+            }) => assert_eq!(
+                (type_.as_str(), found.as_str(), expected.as_str()),
+                ("Point", "geometries", "coordinates")
+            ),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        let error = Value::from_slice(point.as_bytes()).unwrap_err();
+        assert!(error.to_string().contains("needs a `coordinates` member"));
+
+        let collection = r#"{"type": "GeometryCollection", "coordinates": [1.0, 2.0]}"#;
+        assert!(matches!(
+            collection.parse::<GeoJson>(),
+            Err(Error::UnexpectedMember { .. })
+        ));
+        let error = serde_json::from_str::<Geometry>(collection).unwrap_err();
+        assert!(error.to_string().contains("needs a `geometries` member"));
+
+        // The other member is only a mistake if the right one is missing
+        let foreign = r#"{"type": "Point", "coordinates": [1.0, 2.0], "geometries": []}"#;
+        assert!(foreign.parse::<GeoJson>().is_ok());
+    }
+
+    #[test]
+    fn malformed_coordinates_are_errors_not_panics() {
+        // A small xorshift generator, so the cases are the same on every run
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % bound
+        };
+
+        fn random_coordinates(next: &mut dyn FnMut(u64) -> u64, depth: u32) -> String {
+            match next(if depth == 0 { 6 } else { 8 }) {
+                0 => "null".to_string(),
+                1 => "\"1\"".to_string(),
+                2 => "{}".to_string(),
+                3 => "-1e400".to_string(),
+                4 | 5 => format!("{}.5", next(200) as i64 - 100),
+                _ => {
+                    let items: Vec<_> = (0..next(5))
+                        .map(|_| random_coordinates(next, depth - 1))
+                        .collect();
+                    format!("[{}]", items.join(","))
+                }
+            }
+        }
+
+        let types = [
+            "Point",
+            "MultiPoint",
+            "LineString",
+            "MultiLineString",
+            "Polygon",
+            "MultiPolygon",
+        ];
+        for _ in 0..2000 {
+            let type_ = types[next(types.len() as u64) as usize];
+            let json = format!(
+                r#"{{"type":"{}","coordinates":{}}}"#,
+                type_,
+                random_coordinates(&mut next, 4)
+            );
+            let feature = format!(
+                r#"{{"type":"Feature","properties":null,"geometry":{}}}"#,
+                json
+            );
+            // Only the absence of a panic matters
+            let _ = json.parse::<GeoJson>();
+            let _ = serde_json::from_str::<Geometry>(&json);
+            let _ = feature.parse::<GeoJson>();
+            let _ = serde_json::from_str::<crate::Feature>(&feature);
+        }
+    }
 }
@@ -0,0 +1,283 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::bbox::extent_2d;
+use crate::{BboxDimension, FeatureCollection};
+
+/// The greatest number of children of a node
+const NODE_SIZE: usize = 16;
+
+/// A static R-tree over the 2D bounding boxes of a collection's features
+///
+/// Built by [`FeatureCollection::build_rtree`](struct.FeatureCollection.html#method.build_rtree).
+/// Features are referred to by their index in the collection, and those without any positions
+/// aren't indexed. The tree doesn't borrow the collection, so it isn't updated if the
+/// collection changes.
+///
+/// ```
+/// use geojson::{Feature, FeatureCollection, Geometry, Value};
+///
+/// let point = |x: f64, y: f64| Feature::with_computed_bbox(Geometry::new(Value::Point(vec![x, y])));
+/// let collection = FeatureCollection::from((0..100).map(|i| point(i as f64, 0.)).collect::<Vec<_>>());
+///
+/// let tree = collection.build_rtree();
+/// assert_eq!(tree.query_bbox(&[9.5, -1., 12., 1.]), vec![10, 11, 12]);
+/// assert_eq!(tree.nearest(41.8, 3.), Some(42));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RTree {
+    /// The nodes of each level, leaves first. A leaf's `index` is that of its feature, and any
+    /// other node's is that of its first child in the level below.
+    levels: Vec<Vec<Node>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    extent: [f64; 4],
+    index: usize,
+}
+
+impl RTree {
+    fn new(mut leaves: Vec<Node>) -> Self {
+        sort_tile(&mut leaves);
+        let mut levels = vec![leaves];
+        while let Some(below) = levels.last().filter(|nodes| nodes.len() > 1) {
+            let parents = below
+                .chunks(NODE_SIZE)
+                .enumerate()
+                .map(|(i, children)| Node {
+                    extent: children
+                        .iter()
+                        .skip(1)
+                        .fold(children[0].extent, |union, child| {
+                            [
+                                union[0].min(child.extent[0]),
+                                union[1].min(child.extent[1]),
+                                union[2].max(child.extent[2]),
+                                union[3].max(child.extent[3]),
+                            ]
+                        }),
+                    index: i * NODE_SIZE,
+                })
+                .collect();
+            levels.push(parents);
+        }
+        RTree { levels }
+    }
+
+    /// The number of indexed features
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// The indices, in ascending order, of the features whose bounding boxes intersect or
+    /// touch `bbox`, a 4- or 6-element box. A box of any other length matches nothing.
+    pub fn query_bbox(&self, bbox: &[f64]) -> Vec<usize> {
+        let query = match extent_2d(bbox) {
+            Some(query) => query,
+            None => return vec![],
+        };
+        let mut found = vec![];
+        let mut stack: Vec<(usize, usize)> = self.roots().collect();
+        while let Some((level, i)) = stack.pop() {
+            let node = &self.levels[level][i];
+            if node.extent[0] > query[2]
+                || node.extent[2] < query[0]
+                || node.extent[1] > query[3]
+                || node.extent[3] < query[1]
+            {
+                continue;
+            }
+            if level == 0 {
+                found.push(node.index);
+            } else {
+                stack.extend(self.children(level, node));
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+
+    /// The index of the feature whose bounding box is closest to `x`, `y`, or `None` if the tree
+    /// is empty
+    ///
+    /// Distances are planar, and are to the bounding box rather than the geometry, so are exact
+    /// only for point features. A point within a box is at distance zero from it.
+    pub fn nearest(&self, x: f64, y: f64) -> Option<usize> {
+        let candidate = |(level, index): (usize, usize)| {
+            let extent = self.levels[level][index].extent;
+            let dx = (extent[0] - x).max(x - extent[2]).max(0.);
+            let dy = (extent[1] - y).max(y - extent[3]).max(0.);
+            Candidate {
+                distance: dx * dx + dy * dy,
+                level,
+                index,
+            }
+        };
+        let mut queue: BinaryHeap<Candidate> = self.roots().map(candidate).collect();
+        while let Some(Candidate { level, index, .. }) = queue.pop() {
+            let node = &self.levels[level][index];
+            if level == 0 {
+                return Some(node.index);
+            }
+            queue.extend(self.children(level, node).map(candidate));
+        }
+        None
+    }
+
+    /// The `(level, index)` of each node of the top level
+    fn roots(&self) -> impl Iterator<Item = (usize, usize)> {
+        let top = self.levels.len() - 1;
+        (0..self.levels[top].len()).map(move |i| (top, i))
+    }
+
+    /// The `(level, index)` of each child of `node`, which is on `level`
+    fn children(&self, level: usize, node: &Node) -> impl Iterator<Item = (usize, usize)> {
+        let end = (node.index + NODE_SIZE).min(self.levels[level - 1].len());
+        (node.index..end).map(move |i| (level - 1, i))
+    }
+}
+
+/// Order `nodes` by sort-tile-recursive packing: into vertical slices by the centre `x`, and
+/// each slice by the centre `y`, so that each run of `NODE_SIZE` nodes is close together
+fn sort_tile(nodes: &mut [Node]) {
+    let center = |node: &Node, axis: usize| node.extent[axis] + node.extent[axis + 2];
+    nodes.sort_by(|a, b| center(a, 0).total_cmp(&center(b, 0)));
+    let leaves = nodes.len().div_ceil(NODE_SIZE);
+    let slices = (leaves as f64).sqrt().ceil().max(1.) as usize;
+    let slice_len = leaves.div_ceil(slices).max(1) * NODE_SIZE;
+    for slice in nodes.chunks_mut(slice_len) {
+        slice.sort_by(|a, b| center(a, 1).total_cmp(&center(b, 1)));
+    }
+}
+
+/// A node to visit in a nearest-neighbour search, ordered so that the closest is greatest
+struct Candidate {
+    /// The squared distance to the node's extent
+    distance: f64,
+    level: usize,
+    index: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+impl FeatureCollection {
+    /// Builds an [`RTree`](struct.RTree.html) over the 2D bounding boxes of the features'
+    /// geometries, ignoring any `bbox` members
+    pub fn build_rtree(&self) -> RTree {
+        let leaves = self
+            .features
+            .iter()
+            .enumerate()
+            .filter_map(|(index, feature)| {
+                let bbox = feature.bbox_calculated_with(BboxDimension::Force2D)?;
+                Some(Node {
+                    extent: extent_2d(&bbox)?,
+                    index,
+                })
+            })
+            .collect();
+        RTree::new(leaves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Feature, FeatureCollection, Geometry, Value};
+
+    fn point(x: f64, y: f64) -> Feature {
+        Feature::with_computed_bbox(Geometry::new(Value::Point(vec![x, y])))
+    }
+
+    #[test]
+    fn matches_linear_scan() {
+        // A grid large enough for a tree of three levels, with a gap of unindexed features
+        let mut collection = FeatureCollection::from(
+            (0..1000)
+                .map(|i| point((i % 40) as f64, (i / 40) as f64))
+                .collect::<Vec<_>>(),
+        );
+        for feature in &mut collection.features[100..110] {
+            feature.geometry = None;
+        }
+        let tree = collection.build_rtree();
+        assert_eq!(tree.len(), 990);
+
+        let query = [3.5, 2., 7., 4.5];
+        let expected: Vec<usize> = (0..1000)
+            .filter(|&i| !(100..110).contains(&i))
+            .filter(|i| {
+                let (x, y) = ((i % 40) as f64, (i / 40) as f64);
+                x >= query[0] && x <= query[2] && y >= query[1] && y <= query[3]
+            })
+            .collect();
+        assert_eq!(tree.query_bbox(&query), expected);
+        assert_eq!(
+            tree.query_bbox(&[100., 100., 101., 101.]),
+            Vec::<usize>::new()
+        );
+        assert_eq!(tree.query_bbox(&[0., 0.]), Vec::<usize>::new());
+
+        assert_eq!(tree.nearest(12.2, 7.6), Some(8 * 40 + 12));
+        assert_eq!(tree.nearest(-50., -50.), Some(0));
+        // The nearest indexed feature to a removed one
+        assert_eq!(tree.nearest(20.1, 2.), Some(40 + 20));
+    }
+
+    #[test]
+    fn polygons_and_empty_trees() {
+        let square = Feature::with_computed_bbox(Geometry::new(Value::Polygon(vec![vec![
+            vec![0., 0.],
+            vec![10., 0.],
+            vec![10., 10.],
+            vec![0., 10.],
+            vec![0., 0.],
+        ]])));
+        let collection = FeatureCollection::from(vec![square, point(20., 5.)]);
+        let tree = collection.build_rtree();
+        assert_eq!(tree.nearest(5., 5.), Some(0));
+        assert_eq!(tree.nearest(16., 5.), Some(1));
+        assert_eq!(tree.query_bbox(&[10., 10., 30., 30.]), vec![0]);
+
+        let empty = FeatureCollection::from(vec![]).build_rtree();
+        assert!(empty.is_empty());
+        assert_eq!(empty.nearest(0., 0.), None);
+        assert_eq!(empty.query_bbox(&[0., 0., 1., 1.]), Vec::<usize>::new());
+    }
+}
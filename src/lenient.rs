@@ -0,0 +1,137 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing hand-edited GeoJSON containing comments and trailing commas
+
+use crate::{Error, GeoJson};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "lenient")))]
+impl GeoJson {
+    /// Parse GeoJSON which may contain JavaScript-style `//` and `/* */` comments and trailing
+    /// commas in arrays and objects.
+    ///
+    /// These are removed before the input is parsed as usual, so everything else must be
+    /// strict JSON. Newlines inside comments are kept, so the line numbers of any
+    /// [`Error::MalformedJson`](enum.Error.html#variant.MalformedJson) still match the input.
+    ///
+    /// ```
+    /// use geojson::GeoJson;
+    ///
+    /// let geojson = GeoJson::from_str_lenient(
+    ///     r#"{
+    ///         // the depot
+    ///         "type": "Point",
+    ///         "coordinates": [1.0, 2.0,], /* lon, lat */
+    ///     }"#,
+    /// )
+    /// .unwrap();
+    /// assert!(geojson.is_geometry());
+    /// ```
+    pub fn from_str_lenient(s: &str) -> Result<Self, Error> {
+        strip_comments_and_trailing_commas(s).parse()
+    }
+}
+
+fn strip_comments_and_trailing_commas(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                out.push(c);
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => out.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while let Some(&c) = chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    if c == '\n' {
+                        out.push(c);
+                    }
+                    previous = c;
+                }
+                out.push(' ');
+            }
+            ']' | '}' => {
+                let content_end = out.trim_end().len();
+                if out[..content_end].ends_with(',') {
+                    out.remove(content_end - 1);
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strip_comments_and_trailing_commas;
+    use crate::GeoJson;
+
+    #[test]
+    fn strip() {
+        assert_eq!(
+            strip_comments_and_trailing_commas("[1, // one\n 2 /* two */, ]"),
+            "[1, \n 2   ]"
+        );
+        // Comment markers, brackets and commas within strings are untouched
+        let strings = r#"{"a": "// not a comment, ]", "b": "\" /* */ ,}"}"#;
+        assert_eq!(strip_comments_and_trailing_commas(strings), strings);
+    }
+
+    #[test]
+    fn from_str_lenient() {
+        let lenient = r#"
+        /* A feature collection
+           with a comment block */
+        {
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "geometry": null,
+                    "properties": {"url": "https://example.com",}, // trailing comma
+                },
+            ],
+        }"#;
+        let strict = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "geometry": null, "properties": {"url": "https://example.com"}}
+        ]}"#;
+        assert_eq!(
+            GeoJson::from_str_lenient(lenient).unwrap(),
+            strict.parse::<GeoJson>().unwrap()
+        );
+        assert!(lenient.parse::<GeoJson>().is_err());
+    }
+}
@@ -286,7 +286,7 @@
 //! ```
 //! ### Caveats
 //! - Round-tripping with intermediate processing using the `geo` types may not produce identical output,
-//! as e.g. outer `Polygon` rings are automatically closed.
+//!   as e.g. outer `Polygon` rings are automatically closed.
 //! - `geojson` attempts to output valid geometries. In particular, it may re-orient `Polygon` rings when serialising.
 //!
 //! The [`geojson_example`](https://github.com/urschrei/geojson_example) and
@@ -297,10 +297,6 @@
 // only enables the `doc_cfg` feature when
 // the `docsrs` configuration attribute is defined
 #![cfg_attr(docsrs, feature(doc_cfg))]
-#[cfg(feature = "geo-types")]
-use geo_types;
-use serde;
-use serde_json;
 
 /// Bounding Boxes
 ///
@@ -309,6 +305,11 @@ pub type Bbox = Vec<f64>;
 
 /// Positions
 ///
+/// Every coordinate of a position is kept when parsing and written back out when serializing,
+/// so extended positions such as XYZM survive a round trip. Operations which only use `x`, `y`
+/// and `z` leave any further coordinates in place, while conversions to `geo_types`, which has
+/// no such coordinates, keep only `x` and `y`.
+///
 /// [GeoJSON Format Specification § 3.1.1](https://tools.ietf.org/html/rfc7946#section-3.1.1)
 pub type Position = Vec<f64>;
 
@@ -316,27 +317,126 @@ pub type PointType = Position;
 pub type LineStringType = Vec<Position>;
 pub type PolygonType = Vec<Vec<Position>>;
 
+/// The location of a `Position` within a `Value`, as the index at each level of nesting
+///
+/// Indices run from the outermost level inwards: the member of a `GeometryCollection`, then
+/// the polygon of a `MultiPolygon`, the line or ring, and finally the position itself. The
+/// single position of a `Point` has an empty path.
+pub type CoordPath = Vec<usize>;
+
 mod util;
 
+/// Building blocks for parsing GeoJSON-like JSON, as used by this crate's own parsers
+///
+/// These functions are part of the public API and follow semver like the rest of it. Each
+/// fails with the same [`Error`](../enum.Error.html) variant the crate's parsers report for
+/// the same problem. The rest of the parsing internals aren't exposed and may change at any
+/// time.
+///
+/// ```
+/// use geojson::parse_helpers::{expect_type, json_to_position};
+/// use serde_json::json;
+///
+/// let mut object = json!({"type": "Point", "coordinates": [1.0, 2.0]})
+///     .as_object()
+///     .cloned()
+///     .unwrap();
+/// assert_eq!(expect_type(&mut object).unwrap(), "Point");
+/// assert_eq!(json_to_position(&object["coordinates"]).unwrap(), vec![1.0, 2.0]);
+/// assert!(json_to_position(&json!([1.0, "2"])).is_err());
+/// ```
+pub mod parse_helpers {
+    pub use crate::util::{
+        expect_array, expect_bbox, expect_f64, expect_id, expect_properties, expect_string,
+        expect_type, json_to_position,
+    };
+}
+
+mod bbox;
+pub use crate::bbox::{
+    bbox_of_positions, bbox_union, BboxAccumulator, BboxDimension, BboxExt, BboxIteratorExt,
+};
+
+mod de;
+
+mod algorithm;
+pub use crate::algorithm::{
+    geodesic_bearing, geodesic_destination, geodesic_midpoint, LongitudeMode, RepairReport,
+    ShapeMetrics, SimplifyOptions,
+};
+
 mod geojson;
-pub use crate::geojson::GeoJson;
+pub use crate::geojson::{peek_type, GeoJson, GeoJsonType};
 
 mod geometry;
-pub use crate::geometry::{Geometry, Value};
+pub use crate::geometry::{geometry_collection, Geometry, GeometryCollectionBuilder, Value};
 
 pub mod feature;
 
+pub mod ring;
+
 mod feature_collection;
 pub use crate::feature_collection::FeatureCollection;
 
+mod feature_reader;
+pub use crate::feature_reader::{estimate_feature_count, extract_bbox, FeatureReader};
+
+mod feature_writer;
+pub use crate::feature_writer::{BboxPosition, FeatureWriter};
+
+mod stats;
+pub use crate::stats::{CollectionStats, FieldSchema, FieldType, Schema};
+
+mod diff;
+pub use crate::diff::{CollectionDiff, PropertyDiff};
+
+mod visit;
+pub use crate::visit::{Visitor, VisitorMut};
+
+mod index;
+pub use crate::index::RTree;
+
 pub mod errors;
 pub use crate::errors::Error;
 
+mod parse;
+pub use crate::parse::ParseOptions;
+
+mod ser;
+pub use crate::ser::{
+    round_coordinate, serialize_rounded, serialize_rounded_with_bbox, EmptyGeometries, MemberOrder,
+    SerializeOptions,
+};
+
+mod validation;
+pub use crate::validation::ValidationError;
+
+mod wkt;
+
+mod record;
+
+#[cfg(feature = "esri")]
+mod esri;
+
+#[cfg(feature = "lenient")]
+mod lenient;
+
+#[cfg(feature = "async")]
+mod async_reader;
+
+#[cfg(feature = "rayon")]
+mod par;
+
+#[cfg(feature = "schemars")]
+mod schema;
+
 #[cfg(feature = "geo-types")]
 mod conversion;
 
 #[cfg(feature = "geo-types")]
-pub use conversion::quick_collection;
+pub use conversion::{
+    bbox_from_rect, quick_collection, rect_from_bbox, GeoTypesConversion, ZHandling,
+};
 
 /// Feature Objects
 ///
@@ -357,6 +457,9 @@ pub struct Feature {
     pub id: Option<feature::Id>,
     /// Properties
     ///
+    /// Parsing gives `None` for `"properties": null` and `Some` for an object, even an empty
+    /// one. Any other value is an `Error::PropertiesExpectedObjectOrNull`.
+    ///
     /// [GeoJSON Format Specification § 3.2](https://tools.ietf.org/html/rfc7946#section-3.2)
     pub properties: Option<json::JsonObject>,
     /// Foreign Members
@@ -0,0 +1,133 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parallel operations on `FeatureCollection`s, using `rayon`
+
+use rayon::prelude::*;
+
+use crate::{Feature, FeatureCollection, Position};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+impl FeatureCollection {
+    /// Apply `f` to every feature in parallel, collecting the `Some` results into a new
+    /// collection in the same order as their input features.
+    ///
+    /// The new collection keeps this collection's foreign members. If this collection has a
+    /// `bbox`, the new one's is calculated from the features it ends up with.
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection};
+    ///
+    /// let features = (0..100)
+    ///     .map(|i| {
+    ///         let mut feature = Feature {
+    ///             bbox: None,
+    ///             geometry: None,
+    ///             id: None,
+    ///             properties: None,
+    ///             foreign_members: None,
+    ///         };
+    ///         feature.set_property("i", i);
+    ///         feature
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let collection = FeatureCollection::from(features);
+    ///
+    /// let even = collection.par_filter_map(|feature| {
+    ///     let i = feature.property("i")?.as_u64()?;
+    ///     if i % 2 == 0 {
+    ///         Some(feature.clone())
+    ///     } else {
+    ///         None
+    ///     }
+    /// });
+    /// assert_eq!(even.features.len(), 50);
+    /// assert_eq!(even.features[49].property("i").unwrap(), 98);
+    /// ```
+    pub fn par_filter_map<F>(&self, f: F) -> FeatureCollection
+    where
+        F: Fn(&Feature) -> Option<Feature> + Sync,
+    {
+        let mut collection = FeatureCollection {
+            bbox: None,
+            features: self.features.par_iter().filter_map(&f).collect(),
+            foreign_members: self.foreign_members.clone(),
+        };
+        if self.bbox.is_some() {
+            collection.bbox = collection.bbox_calculated();
+        }
+        collection
+    }
+
+    /// Apply the fallible `transform` to every `Position` of every feature in parallel, as
+    /// [`try_map_coords`](#method.try_map_coords) does, e.g. to reproject a large collection.
+    ///
+    /// Projection objects often can't be shared between threads, so rather than one being
+    /// captured by `transform`, `make_transform` builds a fresh one for each batch of features a
+    /// worker thread takes on, which `transform` is then given. No two threads ever use the same
+    /// one. This is how to drive, for instance, a `proj::Proj` without wrapping it in a lock.
+    ///
+    /// Returns the index of each failed feature along with the first error `transform` returned
+    /// for it, in order. Failed features are left unmodified, and bboxes are recomputed as with
+    /// `map_coords`.
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection, Geometry, Value};
+    /// use std::cell::Cell;
+    ///
+    /// let features = (0..100)
+    ///     .map(|i| Feature::with_computed_bbox(Geometry::new(Value::Point(vec![i as f64, 0.0]))))
+    ///     .collect::<Vec<_>>();
+    /// let mut collection = FeatureCollection::from(features);
+    ///
+    /// // A `Cell` isn't `Sync`, so couldn't be shared by a closure run on many threads
+    /// let failed = collection.par_reproject(
+    ///     || Cell::new(1000.0),
+    ///     |scale, position| {
+    ///         if position[0] >= 99.0 {
+    ///             return Err("out of bounds");
+    ///         }
+    ///         position[0] *= scale.get();
+    ///         Ok(())
+    ///     },
+    /// );
+    /// assert_eq!(failed, vec![(99, "out of bounds")]);
+    /// let first = &collection.features[1];
+    /// assert_eq!(first.geometry.as_ref().unwrap().value, Value::Point(vec![1000.0, 0.0]));
+    /// assert_eq!(first.bbox, Some(vec![1000.0, 0.0, 1000.0, 0.0]));
+    /// ```
+    pub fn par_reproject<T, E, I, F>(&mut self, make_transform: I, transform: F) -> Vec<(usize, E)>
+    where
+        I: Fn() -> T + Sync + Send,
+        F: Fn(&mut T, &mut Position) -> Result<(), E> + Sync + Send,
+        E: Send,
+    {
+        let failed = self
+            .features
+            .par_iter_mut()
+            .enumerate()
+            .map_init(make_transform, |state, (i, feature)| {
+                feature
+                    .try_map_coords(|position| transform(state, position))
+                    .err()
+                    .map(|error| (i, error))
+            })
+            .flatten()
+            .collect();
+        if self.bbox.is_some() {
+            self.bbox = self.bbox_calculated();
+        }
+        failed
+    }
+}
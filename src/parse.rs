@@ -0,0 +1,447 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::Error;
+use crate::json::{JsonObject, JsonValue};
+use crate::{util, GeoJson};
+
+/// Options controlling how GeoJSON is parsed.
+///
+/// The defaults accept what `FromStr` and the `TryFrom` impls accept; each option relaxes or
+/// tightens that in one specific way. Options only apply when parsing through `ParseOptions`'
+/// own methods: the `Deserialize` impls, and so `GeoJson::from_slice`, `GeoJson::from_reader`
+/// and `FeatureReader`, always use the defaults.
+///
+/// # Example
+///
+/// ```
+/// use geojson::{GeoJson, ParseOptions, Value};
+///
+/// let geojson_str = r#"{"type": "Circle", "center": [0.0, 0.0], "radius": 10.0}"#;
+/// assert!(geojson_str.parse::<GeoJson>().is_err());
+///
+/// let options = ParseOptions::new().allow_unknown_geometries(true);
+/// match options.parse(geojson_str).unwrap() {
+///     GeoJson::Geometry(geometry) => assert!(matches!(geometry.value, Value::Unknown(_))),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseOptions {
+    pub(crate) allow_unknown_geometries: bool,
+    pub(crate) allow_string_geometries: bool,
+    pub(crate) expected_dimension: Option<usize>,
+    pub(crate) allow_point_bboxes: bool,
+    pub(crate) reject_inexact_coordinates: bool,
+    /// How many more levels of `GeometryCollection` may be nested within the object being
+    /// parsed
+    pub(crate) max_depth: usize,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            allow_unknown_geometries: false,
+            allow_string_geometries: false,
+            expected_dimension: None,
+            allow_point_bboxes: false,
+            reject_inexact_coordinates: false,
+            max_depth: 32,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Returns the default, strict options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep geometry objects with a `type` this crate doesn't model as
+    /// [`Value::Unknown`](enum.Value.html#variant.Unknown), instead of failing with
+    /// `Error::GeometryUnknownType`.
+    ///
+    /// The raw object is kept whole, so it is written back out unchanged.
+    pub fn allow_unknown_geometries(mut self, allow_unknown_geometries: bool) -> Self {
+        self.allow_unknown_geometries = allow_unknown_geometries;
+        self
+    }
+
+    /// Accept a Feature whose `geometry` is a string holding an encoded geometry object, as
+    /// some APIs (often ones derived from ArcGIS) produce, by parsing the string as a geometry.
+    ///
+    /// This is not conformant GeoJSON, and is only an accommodation for such sources: by
+    /// default a string `geometry` is an `Error::FeatureInvalidGeometryValue`. The geometry is
+    /// written back out as an ordinary object.
+    ///
+    /// ```
+    /// use geojson::{GeoJson, ParseOptions, Value};
+    ///
+    /// let geojson_str = r#"{
+    ///     "type": "Feature",
+    ///     "properties": null,
+    ///     "geometry": "{\"type\": \"Point\", \"coordinates\": [1.0, 2.0]}"
+    /// }"#;
+    /// assert!(geojson_str.parse::<GeoJson>().is_err());
+    ///
+    /// let options = ParseOptions::new().allow_string_geometries(true);
+    /// match options.parse(geojson_str).unwrap() {
+    ///     GeoJson::Feature(feature) => {
+    ///         assert_eq!(feature.geometry.unwrap().value, Value::Point(vec![1.0, 2.0]))
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn allow_string_geometries(mut self, allow_string_geometries: bool) -> Self {
+        self.allow_string_geometries = allow_string_geometries;
+        self
+    }
+
+    /// Require every position to have exactly `expected_dimension` coordinates, e.g. `Some(2)`
+    /// for data known to be 2D, failing with `Error::MixedDimensions` at the first that doesn't.
+    ///
+    /// The error's `path` is relative to the geometry object holding the position, like
+    /// [`Value::dimension`](enum.Value.html#method.dimension)'s. `None`, the default, accepts
+    /// positions of any length, ragged or not.
+    ///
+    /// ```
+    /// use geojson::{Error, ParseOptions};
+    ///
+    /// let geojson_str = r#"{"type": "LineString", "coordinates": [[1.0, 2.0], [3.0, 4.0, 5.0]]}"#;
+    /// assert!(geojson_str.parse::<geojson::GeoJson>().is_ok());
+    ///
+    /// let options = ParseOptions::new().expected_dimension(Some(2));
+    /// match options.parse(geojson_str) {
+    ///     Err(Error::MixedDimensions { expected, found, path }) => {
+    ///         assert_eq!((expected, found), (2, 3));
+    ///         assert_eq!(path, "coordinates[1]");
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn expected_dimension(mut self, expected_dimension: Option<usize>) -> Self {
+        self.expected_dimension = expected_dimension;
+        self
+    }
+
+    /// Accept a 2-element `bbox` of a single position `[x, y]`, as the degenerate box
+    /// `[x, y, x, y]`, instead of failing with `Error::BboxUnexpectedLength`.
+    ///
+    /// A `bbox` of any other length than 4 or 6 is always rejected.
+    ///
+    /// ```
+    /// use geojson::{GeoJson, ParseOptions};
+    ///
+    /// let geojson_str = r#"{"type": "Point", "coordinates": [1.0, 2.0], "bbox": [1.0, 2.0]}"#;
+    /// assert!(geojson_str.parse::<GeoJson>().is_err());
+    ///
+    /// let options = ParseOptions::new().allow_point_bboxes(true);
+    /// match options.parse(geojson_str).unwrap() {
+    ///     GeoJson::Geometry(geometry) => assert_eq!(geometry.bbox, Some(vec![1.0, 2.0, 1.0, 2.0])),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn allow_point_bboxes(mut self, allow_point_bboxes: bool) -> Self {
+        self.allow_point_bboxes = allow_point_bboxes;
+        self
+    }
+
+    /// Fail with `Error::CoordinatePrecisionLoss` for an integer coordinate too large to be
+    /// represented exactly as an `f64`, rather than rounding it to the nearest `f64` as
+    /// happens by default.
+    ///
+    /// Integers of up to 2<sup>53</sup> in magnitude are always exact. Decimal coordinates are
+    /// rounded as usual either way. Integers beyond the range of a 64-bit integer are only caught
+    /// with the `arbitrary_precision` feature: without it, serde_json has already read them as
+    /// `f64`s, indistinguishable from decimals, by the time they can be checked. As with every
+    /// option, the `Deserialize` impls don't apply this.
+    ///
+    /// ```
+    /// use geojson::{Error, GeoJson, ParseOptions};
+    ///
+    /// let geojson_str = r#"{"type": "Point", "coordinates": [9007199254740993, 2]}"#;
+    /// assert!(geojson_str.parse::<GeoJson>().is_ok());
+    ///
+    /// let options = ParseOptions::new().reject_inexact_coordinates(true);
+    /// assert!(matches!(
+    ///     options.parse(geojson_str),
+    ///     Err(Error::CoordinatePrecisionLoss(_))
+    /// ));
+    /// ```
+    pub fn reject_inexact_coordinates(mut self, reject_inexact_coordinates: bool) -> Self {
+        self.reject_inexact_coordinates = reject_inexact_coordinates;
+        self
+    }
+
+    /// Fail with `Error::MaxDepthExceeded` for a `GeometryCollection` nested within more than
+    /// `max_depth` - 1 others, rather than parsing it recursively, so that crafted input can't
+    /// overflow the stack. The default is 32.
+    ///
+    /// A `max_depth` of 0 rejects every `GeometryCollection`, and of 1 only nested ones.
+    ///
+    /// ```
+    /// use geojson::{Error, ParseOptions};
+    ///
+    /// let geojson_str = r#"{"type": "GeometryCollection", "geometries": [
+    ///     {"type": "GeometryCollection", "geometries": []}
+    /// ]}"#;
+    /// assert!(ParseOptions::new().parse(geojson_str).is_ok());
+    ///
+    /// let options = ParseOptions::new().max_depth(1);
+    /// assert!(matches!(options.parse(geojson_str), Err(Error::MaxDepthExceeded)));
+    /// ```
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Parse a GeoJSON string using these options
+    pub fn parse(&self, s: &str) -> Result<GeoJson, Error> {
+        let s = util::strip_bom_str(s);
+        util::expect_non_empty(s.as_bytes())?;
+        match serde_json::from_str(s) {
+            Ok(JsonValue::Object(object)) => self.from_json_object(object),
+            Ok(other) => Err(Error::ExpectedObjectValue(other)),
+            Err(serde_error) => Err(Error::MalformedJson(serde_error)),
+        }
+    }
+
+    /// Convert a JSON value to GeoJSON using these options
+    pub fn from_json_value(&self, value: JsonValue) -> Result<GeoJson, Error> {
+        match value {
+            JsonValue::Object(object) => self.from_json_object(object),
+            other => Err(Error::GeoJsonExpectedObject(other)),
+        }
+    }
+
+    /// Convert a JSON object to GeoJSON using these options
+    pub fn from_json_object(&self, object: JsonObject) -> Result<GeoJson, Error> {
+        GeoJson::from_json_object_with(object, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseOptions;
+    use crate::{Error, GeoJson, Value};
+
+    const UNKNOWN: &str = r#"{"geometry":{"geometries":[{"coordinates":[1.0,2.0],"type":"Point"},{"center":[0.0,0.0],"radius":10.0,"type":"Circle"}],"type":"GeometryCollection"},"properties":{},"type":"Feature"}"#;
+
+    #[test]
+    fn strict_by_default() {
+        match ParseOptions::new().parse(UNKNOWN) {
+            Err(Error::GeometryUnknownType(type_)) => assert_eq!(type_, "Circle"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unknown_geometries_pass_through() {
+        let options = ParseOptions::new().allow_unknown_geometries(true);
+        let geojson = options.parse(UNKNOWN).unwrap();
+        let feature = match &geojson {
+            GeoJson::Feature(feature) => feature,
+            _ => unreachable!(),
+        };
+        match &feature.geometry.as_ref().unwrap().value {
+            Value::GeometryCollection(geometries) => {
+                assert_eq!(geometries[0].value, Value::Point(vec![1.0, 2.0]));
+                assert!(matches!(geometries[1].value, Value::Unknown(_)));
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(geojson.to_string(), UNKNOWN);
+    }
+
+    #[test]
+    fn string_geometries() {
+        let feature = |geometry: &str| {
+            format!(
+                r#"{{"type": "Feature", "properties": null, "geometry": {}}}"#,
+                serde_json::to_string(geometry).unwrap()
+            )
+        };
+        let options = ParseOptions::new().allow_string_geometries(true);
+
+        let collection = format!(
+            r#"{{"type": "FeatureCollection", "features": [{}]}}"#,
+            feature(r#"{"type": "LineString", "coordinates": [[0, 0], [1, 1]]}"#)
+        );
+        match options.parse(&collection).unwrap() {
+            GeoJson::FeatureCollection(collection) => assert_eq!(
+                collection.features[0].geometry.as_ref().unwrap().value,
+                Value::LineString(vec![vec![0., 0.], vec![1., 1.]])
+            ),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        assert!(matches!(
+            ParseOptions::new().parse(&collection),
+            Err(Error::FeatureInvalidGeometryValue(_))
+        ));
+        assert!(matches!(
+            options.parse(&feature("[1, 2]")),
+            Err(Error::FeatureInvalidGeometryValue(_))
+        ));
+        assert!(matches!(
+            options.parse(&feature("{")),
+            Err(Error::MalformedJson(_))
+        ));
+    }
+
+    #[test]
+    fn expected_dimension() {
+        let collection = r#"{"type": "GeometryCollection", "geometries": [
+            {"type": "Point", "coordinates": [0, 0, 0]},
+            {"type": "GeometryCollection", "geometries": [
+                {"type": "Polygon", "coordinates": [[[0, 0, 0], [1, 0, 0], [1, 1], [0, 0, 0]]]}
+            ]}
+        ]}"#;
+        assert!(ParseOptions::new().parse(collection).is_ok());
+        assert!(ParseOptions::new()
+            .expected_dimension(Some(3))
+            .parse(r#"{"type": "Point", "coordinates": [0, 0, 0]}"#)
+            .is_ok());
+        match ParseOptions::new()
+            .expected_dimension(Some(3))
+            .parse(collection)
+        {
+            Err(Error::MixedDimensions {
+                expected,
+                found,
+                path,
+            }) => {
+                assert_eq!((expected, found), (3, 2));
+                assert_eq!(path, "geometries[1].geometries[0].coordinates[0][2]");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn bbox_lengths() {
+        let point = |bbox: &str| {
+            format!(
+                r#"{{"type": "Point", "coordinates": [1, 2], "bbox": {}}}"#,
+                bbox
+            )
+        };
+        let lenient = ParseOptions::new().allow_point_bboxes(true);
+        for length in 0..=8 {
+            let bbox = format!("{:?}", vec![1.0; length]);
+            let strict = ParseOptions::new().parse(&point(&bbox));
+            let deserialized = serde_json::from_str::<crate::Geometry>(&point(&bbox));
+            match length {
+                4 | 6 => {
+                    assert!(strict.is_ok());
+                    assert!(deserialized.is_ok());
+                }
+                _ => {
+                    match strict {
+                        Err(Error::BboxUnexpectedLength { expected, actual }) => {
+                            assert_eq!((expected, actual), ("4 or 6", length))
+                        }
+                        other => panic!("unexpected result: {:?}", other),
+                    }
+                    assert!(deserialized
+                        .unwrap_err()
+                        .to_string()
+                        .contains("4 or 6 elements"));
+                }
+            }
+            assert_eq!(
+                lenient.parse(&point(&bbox)).is_ok(),
+                [2, 4, 6].contains(&length)
+            );
+        }
+
+        match lenient.parse(&point("[1, 2]")).unwrap() {
+            GeoJson::Geometry(geometry) => assert_eq!(geometry.bbox, Some(vec![1., 2., 1., 2.])),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn inexact_coordinates() {
+        let point =
+            |coordinates: &str| format!(r#"{{"type": "Point", "coordinates": {}}}"#, coordinates);
+        let strict = ParseOptions::new().reject_inexact_coordinates(true);
+        for exact in [
+            "[1, 2.5]",
+            "[9007199254740992, -9007199254740992]",
+            "[18446744073709549568, -9223372036854775808]",
+            "[1e300, 0.1]",
+        ] {
+            assert!(strict.parse(&point(exact)).is_ok(), "{}", exact);
+        }
+        for inexact in [
+            "[9007199254740993, 0]",
+            "[0, -9007199254740993]",
+            "[18446744073709551615, 0]",
+        ] {
+            assert!(
+                matches!(
+                    strict.parse(&point(inexact)),
+                    Err(Error::CoordinatePrecisionLoss(_))
+                ),
+                "{}",
+                inexact
+            );
+            assert!(ParseOptions::new().parse(&point(inexact)).is_ok());
+        }
+
+        // Integers too large for a `u64` are only seen as integers with `arbitrary_precision`
+        let wide = point("[18446744073709551617, 2]");
+        assert_eq!(
+            matches!(strict.parse(&wide), Err(Error::CoordinatePrecisionLoss(_))),
+            cfg!(feature = "arbitrary_precision")
+        );
+        for exact in ["[36893488147419103232, 0]", "[-0, 0]", "[1e20, 2.0e1]"] {
+            assert!(strict.parse(&point(exact)).is_ok(), "{}", exact);
+        }
+    }
+
+    #[test]
+    fn max_depth() {
+        let nested = |depth: usize| {
+            let mut geometry = serde_json::json!({"type": "Point", "coordinates": [0, 0]});
+            for _ in 0..depth {
+                geometry =
+                    serde_json::json!({"type": "GeometryCollection", "geometries": [geometry]});
+            }
+            geometry
+        };
+        assert!(ParseOptions::new().from_json_value(nested(32)).is_ok());
+        assert!(matches!(
+            ParseOptions::new().from_json_value(nested(33)),
+            Err(Error::MaxDepthExceeded)
+        ));
+        // Well past the recursion limit `serde_json` applies when parsing text
+        assert!(matches!(
+            GeoJson::from_json_value(nested(200)),
+            Err(Error::MaxDepthExceeded)
+        ));
+        let feature =
+            serde_json::json!({"type": "Feature", "properties": null, "geometry": nested(3)});
+        assert!(ParseOptions::new().from_json_value(feature.clone()).is_ok());
+        assert!(matches!(
+            ParseOptions::new().max_depth(2).from_json_value(feature),
+            Err(Error::MaxDepthExceeded)
+        ));
+        assert!(ParseOptions::new()
+            .max_depth(0)
+            .from_json_value(nested(0))
+            .is_ok());
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::json::JsonValue;
+use crate::{Feature, FeatureCollection};
+
+/// The key of the geometry column in [`Feature::to_record`](struct.Feature.html#method.to_record)
+const GEOMETRY_COLUMN: &str = "wkt";
+
+impl Feature {
+    /// Flatten this feature into a record of strings, e.g. for writing a row of a CSV file
+    ///
+    /// Each property becomes an entry: strings as they are, `null` as an empty string, and
+    /// other values as JSON text. The geometry is added under the `wkt` key as
+    /// [Well-Known Text](enum.Value.html#method.to_wkt), replacing any property of that name,
+    /// and is an empty string if the geometry is `null` or has no WKT equivalent.
+    ///
+    /// ```
+    /// use geojson::{Feature, Geometry, Value};
+    ///
+    /// let mut feature = Feature::with_computed_bbox(Geometry::new(Value::Point(vec![1., 2.])));
+    /// feature.set_property("name", "depot");
+    /// feature.set_property("docks", 12);
+    ///
+    /// let record = feature.to_record();
+    /// assert_eq!(record["name"], "depot");
+    /// assert_eq!(record["docks"], "12");
+    /// assert_eq!(record["wkt"], "POINT (1 2)");
+    /// ```
+    pub fn to_record(&self) -> BTreeMap<String, String> {
+        let mut record: BTreeMap<_, _> = self
+            .properties_iter()
+            .map(|(key, value)| (key.clone(), flatten(value)))
+            .collect();
+        let wkt = self
+            .geometry
+            .as_ref()
+            .and_then(|geometry| geometry.value.to_wkt().ok())
+            .unwrap_or_default();
+        record.insert(GEOMETRY_COLUMN.to_string(), wkt);
+        record
+    }
+}
+
+impl FeatureCollection {
+    /// Flatten every feature with [`Feature::to_record`](struct.Feature.html#method.to_record),
+    /// giving every record the same columns: the union of all features' keys, with empty
+    /// strings for the properties a feature doesn't have
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection};
+    ///
+    /// let mut a = Feature::with_null_geometry();
+    /// a.set_property("name", "a");
+    /// let mut b = Feature::with_null_geometry();
+    /// b.set_property("height", 3.5);
+    ///
+    /// let records = FeatureCollection::from(vec![a, b]).to_records();
+    /// let columns: Vec<_> = records[0].keys().collect();
+    /// assert_eq!(columns, vec!["height", "name", "wkt"]);
+    /// assert_eq!(records[0]["height"], "");
+    /// assert_eq!(records[1]["height"], "3.5");
+    /// ```
+    pub fn to_records(&self) -> Vec<BTreeMap<String, String>> {
+        let mut records: Vec<_> = self.features.iter().map(Feature::to_record).collect();
+        let columns: BTreeSet<String> = records
+            .iter()
+            .flat_map(|record| record.keys().cloned())
+            .collect();
+        for record in &mut records {
+            for column in &columns {
+                if !record.contains_key(column) {
+                    record.insert(column.clone(), String::new());
+                }
+            }
+        }
+        records
+    }
+}
+
+fn flatten(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Null => String::new(),
+        JsonValue::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Feature, FeatureCollection, Geometry, Value};
+    use serde_json::json;
+
+    #[test]
+    fn flattened_values() {
+        let mut feature = Feature::with_computed_bbox(Geometry::new(Value::LineString(vec![
+            vec![0., 0.],
+            vec![1., 1.],
+        ])));
+        feature.set_property("flag", true);
+        feature.set_property("note", json!(null));
+        feature.set_property("tags", json!(["a", "b"]));
+        feature.set_property("wkt", "replaced");
+
+        let record = feature.to_record();
+        assert_eq!(record["flag"], "true");
+        assert_eq!(record["note"], "");
+        assert_eq!(record["tags"], r#"["a","b"]"#);
+        assert_eq!(record["wkt"], "LINESTRING (0 0, 1 1)");
+        assert_eq!(record.len(), 4);
+
+        let record = Feature::with_null_geometry().to_record();
+        assert_eq!(
+            record.into_iter().collect::<Vec<_>>(),
+            vec![("wkt".to_string(), String::new())]
+        );
+    }
+
+    #[test]
+    fn empty_collection_has_no_records() {
+        assert!(FeatureCollection::from(vec![]).to_records().is_empty());
+    }
+}
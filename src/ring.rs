@@ -0,0 +1,268 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for working with polygon rings directly
+//!
+//! A ring is a sequence of positions, and is closed when its last position repeats its first,
+//! as [RFC 7946 § 3.1.6](https://tools.ietf.org/html/rfc7946#section-3.1.6) requires of
+//! polygon rings. Only the first two coordinates of each position are used for orientation.
+
+use crate::algorithm::area::ring_signed_area;
+use crate::{Geometry, Position, Value};
+
+/// Whether `ring` has at least two positions and its last is equal to its first
+///
+/// An empty ring, or one with a single position, is not closed.
+///
+/// ```
+/// use geojson::ring::is_closed_ring;
+///
+/// assert!(is_closed_ring(&[vec![0., 0.], vec![1., 0.], vec![0., 1.], vec![0., 0.]]));
+/// assert!(!is_closed_ring(&[vec![0., 0.], vec![1., 0.], vec![0., 1.]]));
+/// assert!(!is_closed_ring(&[vec![0., 0.]]));
+/// ```
+pub fn is_closed_ring(ring: &[Position]) -> bool {
+    ring.len() >= 2 && ring.first() == ring.last()
+}
+
+/// Whether `ring` winds counter-clockwise, i.e. its signed area is positive
+///
+/// The ring is treated as closed whether it is or not. Rings with no area, including empty
+/// rings and those whose positions are all collinear, are neither clockwise nor
+/// counter-clockwise and return `false`. Positions with fewer than two coordinates are skipped.
+///
+/// ```
+/// use geojson::ring::ring_is_ccw;
+///
+/// let ring = vec![vec![0., 0.], vec![1., 0.], vec![0., 1.], vec![0., 0.]];
+/// assert!(ring_is_ccw(&ring));
+/// let reversed: Vec<_> = ring.into_iter().rev().collect();
+/// assert!(!ring_is_ccw(&reversed));
+/// ```
+pub fn ring_is_ccw(ring: &[Position]) -> bool {
+    ring_signed_area(ring) > 0.
+}
+
+/// Close `ring` by appending a copy of its first position, unless it is already closed
+///
+/// An empty ring is left empty, and a single position is repeated.
+///
+/// ```
+/// use geojson::ring::close_ring;
+///
+/// let mut ring = vec![vec![0., 0.], vec![1., 0.], vec![0., 1.]];
+/// close_ring(&mut ring);
+/// assert_eq!(ring, vec![vec![0., 0.], vec![1., 0.], vec![0., 1.], vec![0., 0.]]);
+/// ```
+pub fn close_ring(ring: &mut Vec<Position>) {
+    if let Some(first) = ring.first() {
+        if !is_closed_ring(ring) {
+            ring.push(first.clone());
+        }
+    }
+}
+
+/// The direction in which a ring winds, by the sign of its area
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Winding {
+    CounterClockwise,
+    Clockwise,
+    /// The ring has no area, or a position with fewer than two coordinates, so winds neither way
+    Degenerate,
+}
+
+/// The winding of one polygon ring, as reported by
+/// [`Geometry::winding_report`](../struct.Geometry.html#method.winding_report)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RingWinding {
+    /// The index of the ring's polygon among every polygon of the geometry, counting the parts
+    /// of `MultiPolygon`s and the members of `GeometryCollection`s in order
+    pub polygon: usize,
+    /// The index of the ring within its polygon
+    pub ring: usize,
+    /// Whether this is the polygon's first ring, its exterior, rather than a hole
+    pub exterior: bool,
+    pub winding: Winding,
+}
+
+impl RingWinding {
+    /// Whether the ring follows the RFC 7946 winding order: counter-clockwise for an exterior,
+    /// clockwise for a hole
+    pub fn follows_rfc7946(&self) -> bool {
+        self.winding
+            == if self.exterior {
+                Winding::CounterClockwise
+            } else {
+                Winding::Clockwise
+            }
+    }
+}
+
+impl Geometry {
+    /// Describe the winding of every ring of every polygon in the geometry, recursing into
+    /// `GeometryCollection`s, in order
+    ///
+    /// Geometries without polygons give an empty `Vec`. A ring with a position of fewer than two
+    /// coordinates is `Winding::Degenerate`, like one with no area.
+    /// [`Value::make_valid`](../enum.Value.html#method.make_valid) rewinds rings into the RFC 7946
+    /// order.
+    ///
+    /// ```
+    /// use geojson::ring::{RingWinding, Winding};
+    /// use geojson::{Geometry, Value};
+    ///
+    /// let square = vec![vec![0., 0.], vec![4., 0.], vec![4., 4.], vec![0., 4.], vec![0., 0.]];
+    /// let geometry = Geometry::new(Value::Polygon(vec![square.clone(), square]));
+    /// let report = geometry.winding_report();
+    /// assert_eq!(
+    ///     report[1],
+    ///     RingWinding { polygon: 0, ring: 1, exterior: false, winding: Winding::CounterClockwise }
+    /// );
+    /// assert!(report[0].follows_rfc7946() && !report[1].follows_rfc7946());
+    /// ```
+    pub fn winding_report(&self) -> Vec<RingWinding> {
+        let mut report = vec![];
+        add_windings(&self.value, &mut 0, &mut report);
+        report
+    }
+}
+
+/// Append the winding of each ring of `value`'s polygons to `report`, numbering the polygons
+/// from `polygons`, which is left as the count so far
+fn add_windings(value: &Value, polygons: &mut usize, report: &mut Vec<RingWinding>) {
+    match value {
+        Value::Polygon(rings) => add_polygon_windings(rings, polygons, report),
+        Value::MultiPolygon(parts) => {
+            for rings in parts {
+                add_polygon_windings(rings, polygons, report);
+            }
+        }
+        Value::GeometryCollection(geometries) => {
+            for geometry in geometries {
+                add_windings(&geometry.value, polygons, report);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn add_polygon_windings(
+    rings: &[Vec<Position>],
+    polygons: &mut usize,
+    report: &mut Vec<RingWinding>,
+) {
+    let polygon = *polygons;
+    *polygons += 1;
+    report.extend(rings.iter().enumerate().map(|(i, ring)| {
+        let area = if ring.iter().any(|position| position.len() < 2) {
+            0.
+        } else {
+            ring_signed_area(ring)
+        };
+        RingWinding {
+            polygon,
+            ring: i,
+            exterior: i == 0,
+            winding: if area > 0. {
+                Winding::CounterClockwise
+            } else if area < 0. {
+                Winding::Clockwise
+            } else {
+                Winding::Degenerate
+            },
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edge_cases() {
+        let mut empty: Vec<Position> = vec![];
+        assert!(!is_closed_ring(&empty));
+        assert!(!ring_is_ccw(&empty));
+        close_ring(&mut empty);
+        assert!(empty.is_empty());
+
+        let mut single = vec![vec![1., 2.]];
+        close_ring(&mut single);
+        assert_eq!(single, vec![vec![1., 2.], vec![1., 2.]]);
+        assert!(is_closed_ring(&single));
+
+        let collinear = vec![vec![0., 0.], vec![1., 1.], vec![2., 2.], vec![0., 0.]];
+        assert!(!ring_is_ccw(&collinear));
+        assert!(!ring_is_ccw(&[vec![0.], vec![1.], vec![2.]]));
+        assert!(ring_is_ccw(&[
+            vec![0., 0.],
+            vec![1.],
+            vec![1., 0.],
+            vec![0., 1.]
+        ]));
+
+        // Equality includes every coordinate, not only `x` and `y`
+        let mut elevated = vec![vec![0., 0., 1.], vec![1., 0.], vec![0., 1.], vec![0., 0.]];
+        assert!(!is_closed_ring(&elevated));
+        close_ring(&mut elevated);
+        assert_eq!(elevated.last(), Some(&vec![0., 0., 1.]));
+    }
+
+    #[test]
+    fn winding_report() {
+        let ccw = vec![vec![0., 0.], vec![2., 0.], vec![0., 2.], vec![0., 0.]];
+        let cw: Vec<_> = ccw.iter().rev().cloned().collect();
+        let flat = vec![vec![0., 0.], vec![1., 1.], vec![0., 0.]];
+        let geometry = Geometry::new(Value::GeometryCollection(vec![
+            Geometry::new(Value::LineString(ccw.clone())),
+            Geometry::new(Value::MultiPolygon(vec![
+                vec![cw.clone(), ccw.clone()],
+                vec![ccw.clone(), cw],
+            ])),
+            // Still counted, though it has no rings to report
+            Geometry::new(Value::Polygon(vec![])),
+            Geometry::new(Value::GeometryCollection(vec![Geometry::new(
+                Value::Polygon(vec![flat, vec![vec![0., 0.], vec![1.], vec![0., 1.]]]),
+            )])),
+        ]));
+        let report: Vec<_> = geometry
+            .winding_report()
+            .iter()
+            .map(|r| {
+                (
+                    r.polygon,
+                    r.ring,
+                    r.exterior,
+                    r.winding,
+                    r.follows_rfc7946(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            report,
+            vec![
+                (0, 0, true, Winding::Clockwise, false),
+                (0, 1, false, Winding::CounterClockwise, false),
+                (1, 0, true, Winding::CounterClockwise, true),
+                (1, 1, false, Winding::Clockwise, true),
+                (3, 0, true, Winding::Degenerate, false),
+                (3, 1, false, Winding::Degenerate, false),
+            ]
+        );
+
+        assert!(Geometry::new(Value::LineString(ccw))
+            .winding_report()
+            .is_empty());
+    }
+}
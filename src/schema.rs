@@ -0,0 +1,163 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `JsonSchema` impls describing the GeoJSON objects this crate reads and writes.
+//!
+//! The schemas are written by hand rather than derived, since the types serialize through
+//! custom impls: a derived schema would describe the Rust structs, not the GeoJSON.
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde_json::json;
+
+use crate::json::JsonValue;
+use crate::{Feature, FeatureCollection, Geometry, Value};
+
+fn schema_from(value: JsonValue) -> Schema {
+    serde_json::from_value(value).expect("hand-written schemas are valid")
+}
+
+fn reference<T: JsonSchema>(gen: &mut SchemaGenerator) -> JsonValue {
+    serde_json::to_value(gen.subschema_for::<T>()).unwrap()
+}
+
+fn position() -> JsonValue {
+    json!({"type": "array", "items": {"type": "number"}, "minItems": 2})
+}
+
+fn bbox() -> JsonValue {
+    json!({"type": "array", "items": {"type": "number"}, "minItems": 4})
+}
+
+fn nested(depth: usize) -> JsonValue {
+    (0..depth).fold(
+        position(),
+        |items, _| json!({"type": "array", "items": items}),
+    )
+}
+
+fn geometry_variant(type_: &str, member: &str, schema: JsonValue) -> JsonValue {
+    json!({
+        "type": "object",
+        "required": ["type", member],
+        "properties": {
+            "type": {"enum": [type_]},
+            member: schema,
+        },
+    })
+}
+
+/// Describes a geometry object, which is how a `Value` is read on its own
+#[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+impl JsonSchema for Value {
+    fn schema_name() -> String {
+        "Value".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let geometries = json!({"type": "array", "items": reference::<Geometry>(gen)});
+        schema_from(json!({
+            "oneOf": [
+                geometry_variant("Point", "coordinates", nested(0)),
+                geometry_variant("MultiPoint", "coordinates", nested(1)),
+                geometry_variant("LineString", "coordinates", nested(1)),
+                geometry_variant("MultiLineString", "coordinates", nested(2)),
+                geometry_variant("Polygon", "coordinates", nested(2)),
+                geometry_variant("MultiPolygon", "coordinates", nested(3)),
+                geometry_variant("GeometryCollection", "geometries", geometries),
+            ]
+        }))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+impl JsonSchema for Geometry {
+    fn schema_name() -> String {
+        "Geometry".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        schema_from(json!({
+            "allOf": [
+                reference::<Value>(gen),
+                {"type": "object", "properties": {"bbox": bbox()}},
+            ]
+        }))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+impl JsonSchema for Feature {
+    fn schema_name() -> String {
+        "Feature".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        schema_from(json!({
+            "type": "object",
+            "required": ["type", "geometry", "properties"],
+            "properties": {
+                "type": {"enum": ["Feature"]},
+                "bbox": bbox(),
+                "geometry": {"oneOf": [{"type": "null"}, reference::<Geometry>(gen)]},
+                "id": {"type": ["string", "number"]},
+                "properties": {"type": ["object", "null"]},
+            },
+        }))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+impl JsonSchema for FeatureCollection {
+    fn schema_name() -> String {
+        "FeatureCollection".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        schema_from(json!({
+            "type": "object",
+            "required": ["type", "features"],
+            "properties": {
+                "type": {"enum": ["FeatureCollection"]},
+                "bbox": bbox(),
+                "features": {"type": "array", "items": reference::<Feature>(gen)},
+            },
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::FeatureCollection;
+    use schemars::schema_for;
+    use serde_json::json;
+
+    #[test]
+    fn feature_collection_schema() {
+        let schema = serde_json::to_value(schema_for!(FeatureCollection)).unwrap();
+        assert_eq!(schema["required"], json!(["features", "type"]));
+        assert_eq!(
+            schema["properties"]["features"]["items"],
+            json!({"$ref": "#/definitions/Feature"})
+        );
+
+        let value = &schema["definitions"]["Value"];
+        assert_eq!(value["oneOf"].as_array().unwrap().len(), 7);
+        assert_eq!(
+            value["oneOf"][4]["properties"]["coordinates"]["items"]["items"]["items"],
+            json!({"type": "number"})
+        );
+    }
+}
@@ -0,0 +1,1030 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::Hasher;
+use std::io;
+
+use serde::ser::{SerializeMap, SerializeSeq};
+
+use crate::bbox::Extent;
+use crate::json::{JsonObject, JsonValue, Serialize, Serializer};
+use crate::{BboxDimension, Feature, Value};
+
+/// Options controlling how GeoJSON objects are written.
+///
+/// The defaults produce the same output as the `Serialize` and `Display` impls.
+///
+/// # Example
+///
+/// ```
+/// use geojson::{Feature, Geometry, SerializeOptions, Value};
+///
+/// let mut feature = Feature {
+///     bbox: None,
+///     geometry: Some(Geometry::new(Value::Point(vec![1.0, 2.0]))),
+///     id: None,
+///     properties: None,
+///     foreign_members: None,
+/// };
+/// feature.set_property("b", 1);
+/// feature.set_property("a", 2);
+///
+/// let options = SerializeOptions::new().sort_keys(true);
+/// assert_eq!(
+///     options.to_string(&feature),
+///     r#"{"geometry":{"coordinates":[1.0,2.0],"type":"Point"},"properties":{"a":2,"b":1},"type":"Feature"}"#
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SerializeOptions {
+    sort_keys: bool,
+    feature_bboxes: bool,
+    precision: Option<u32>,
+    plain_decimals: bool,
+    empty_geometries: EmptyGeometries,
+    member_order: MemberOrder,
+}
+
+/// How [`SerializeOptions`](struct.SerializeOptions.html) writes features whose geometry is
+/// [empty](enum.Value.html#method.is_empty)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmptyGeometries {
+    /// Write the empty geometry as it is
+    #[default]
+    Keep,
+    /// Leave the feature out of a `FeatureCollection`, or of newline-delimited output. A
+    /// `Feature` written on its own is written as it is.
+    Skip,
+    /// Write the feature with a `null` geometry instead
+    Null,
+}
+
+/// Where [`SerializeOptions`](struct.SerializeOptions.html) writes particular members of each
+/// `Feature`, for consumers which compare the serialized text
+///
+/// The member moved keeps its value, and the others keep their order relative to each other.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MemberOrder {
+    /// Write the members in the order they would be written anyway
+    #[default]
+    Keep,
+    /// Write `type` before any other member
+    TypeFirst,
+    /// Write `geometry` after every other member
+    GeometryLast,
+    /// Write `properties` after every other member
+    PropertiesLast,
+}
+
+impl MemberOrder {
+    /// Where `key` sorts relative to the members this order doesn't move
+    fn rank(self, key: &str) -> i8 {
+        match (self, key) {
+            (MemberOrder::TypeFirst, "type") => -1,
+            (MemberOrder::GeometryLast, "geometry")
+            | (MemberOrder::PropertiesLast, "properties") => 1,
+            _ => 0,
+        }
+    }
+}
+
+impl SerializeOptions {
+    /// Returns the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write the members of every object, including `properties`, foreign members and any
+    /// objects nested within them, in alphabetical order.
+    ///
+    /// Without this the order depends on the map backing `serde_json::Map`, which is
+    /// alphabetical by default but follows insertion order if `serde_json`'s `preserve_order`
+    /// feature is enabled anywhere in the dependency graph.
+    pub fn sort_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+
+    /// When writing newline-delimited features with
+    /// [`to_ndjson_writer`](#method.to_ndjson_writer), give each line a `bbox` calculated from
+    /// its geometry, as tools such as tippecanoe use for indexing.
+    ///
+    /// The box is 6-element if every position has a z coordinate and 4-element otherwise, and
+    /// replaces any `bbox` the feature already has. Features without positions keep their own.
+    /// The features themselves aren't modified.
+    pub fn feature_bboxes(mut self, feature_bboxes: bool) -> Self {
+        self.feature_bboxes = feature_bboxes;
+        self
+    }
+
+    /// Round every position and `bbox` to `decimals` decimal places, as
+    /// [`serialize_rounded`](fn.serialize_rounded.html) does. Properties and foreign members
+    /// are written unchanged.
+    pub fn precision(mut self, decimals: u32) -> Self {
+        self.precision = Some(decimals);
+        self
+    }
+
+    /// Write every floating point number, including those in properties and foreign members, in
+    /// plain decimal notation, for consumers which can't parse exponents.
+    ///
+    /// By default very small or large numbers are written in scientific notation, such as
+    /// `1e-7`. Numbers are still written with the fewest digits that parse back to the same
+    /// value, and keep a fractional part, so `1e21` is written as `1000000000000000000000.0`.
+    ///
+    /// ```
+    /// use geojson::{Geometry, SerializeOptions, Value};
+    ///
+    /// let geometry = Geometry::new(Value::Point(vec![1.23e-7, 45.0]));
+    /// let options = SerializeOptions::new().plain_decimals(true);
+    /// assert_eq!(
+    ///     options.to_string(&geometry),
+    ///     r#"{"coordinates":[0.000000123,45.0],"type":"Point"}"#
+    /// );
+    /// assert_eq!(
+    ///     options.precision(7).to_string(&geometry),
+    ///     r#"{"coordinates":[0.0000001,45.0],"type":"Point"}"#
+    /// );
+    /// ```
+    pub fn plain_decimals(mut self, plain_decimals: bool) -> Self {
+        self.plain_decimals = plain_decimals;
+        self
+    }
+
+    /// Choose how to write features with empty geometries, for consumers which reject them.
+    /// The features themselves aren't modified.
+    ///
+    /// ```
+    /// use geojson::{EmptyGeometries, Feature, FeatureCollection, Geometry, SerializeOptions, Value};
+    ///
+    /// let collection = FeatureCollection::from(vec![
+    ///     Feature::with_computed_bbox(Geometry::new(Value::LineString(vec![]))),
+    ///     Feature::with_computed_bbox(Geometry::new(Value::Point(vec![1.0, 2.0]))),
+    /// ]);
+    ///
+    /// let skipped = SerializeOptions::new().empty_geometries(EmptyGeometries::Skip);
+    /// assert_eq!(
+    ///     skipped.to_string(&collection),
+    ///     r#"{"features":[{"bbox":[1.0,2.0,1.0,2.0],"geometry":{"coordinates":[1.0,2.0],"type":"Point"},"properties":{},"type":"Feature"}],"type":"FeatureCollection"}"#
+    /// );
+    ///
+    /// let nulled = SerializeOptions::new().empty_geometries(EmptyGeometries::Null);
+    /// assert!(nulled.to_string(&collection).starts_with(r#"{"features":[{"geometry":null,"#));
+    /// ```
+    pub fn empty_geometries(mut self, empty_geometries: EmptyGeometries) -> Self {
+        self.empty_geometries = empty_geometries;
+        self
+    }
+
+    /// Reorder the members of a top-level `Feature`, and of each in a `FeatureCollection`'s
+    /// `features`, as `member_order` describes. Objects typed `Feature` elsewhere, such as
+    /// within `properties` or foreign members, are left alone. This is applied after
+    /// [`sort_keys`](#method.sort_keys).
+    ///
+    /// ```
+    /// use geojson::{Feature, Geometry, MemberOrder, SerializeOptions, Value};
+    ///
+    /// let mut feature = Feature::with_null_geometry();
+    /// feature.geometry = Some(Geometry::new(Value::Point(vec![1.0, 2.0])));
+    /// let options = SerializeOptions::new().member_order(MemberOrder::TypeFirst);
+    /// assert_eq!(
+    ///     options.to_string(&feature),
+    ///     r#"{"type":"Feature","geometry":{"coordinates":[1.0,2.0],"type":"Point"},"properties":{}}"#
+    /// );
+    /// ```
+    pub fn member_order(mut self, member_order: MemberOrder) -> Self {
+        self.member_order = member_order;
+        self
+    }
+
+    /// Whether `feature` is left out of collections and newline-delimited output
+    pub(crate) fn skips(&self, feature: &Feature) -> bool {
+        self.empty_geometries == EmptyGeometries::Skip
+            && matches!(&feature.geometry, Some(geometry) if geometry.value.is_empty())
+    }
+
+    /// Serialize a GeoJSON object to a `String` using these options
+    pub fn to_string<'a, T>(&self, object: &'a T) -> String
+    where
+        JsonObject: From<&'a T>,
+    {
+        let mut written = Vec::new();
+        self.write_value(&mut written, JsonValue::Object(JsonObject::from(object)))
+            .unwrap();
+        String::from_utf8(written).unwrap()
+    }
+
+    /// Serialize a GeoJSON object to an IO stream using these options
+    pub fn to_writer<'a, T, W>(&self, writer: W, object: &'a T) -> Result<(), serde_json::Error>
+    where
+        JsonObject: From<&'a T>,
+        W: io::Write,
+    {
+        self.write_value(writer, JsonValue::Object(JsonObject::from(object)))
+    }
+
+    /// Write `features` as newline-delimited GeoJSON, one feature per line, using these options
+    ///
+    /// ```
+    /// use geojson::{Feature, Geometry, SerializeOptions, Value};
+    ///
+    /// let mut feature = Feature {
+    ///     bbox: None,
+    ///     geometry: Some(Geometry::new(Value::LineString(vec![vec![0., 0.], vec![2., 1.]]))),
+    ///     id: None,
+    ///     properties: None,
+    ///     foreign_members: None,
+    /// };
+    ///
+    /// let mut written = vec![];
+    /// let options = SerializeOptions::new().feature_bboxes(true);
+    /// options.to_ndjson_writer(&mut written, &[feature.clone(), feature]).unwrap();
+    ///
+    /// let line = r#"{"bbox":[0.0,0.0,2.0,1.0],"geometry":{"coordinates":[[0.0,0.0],[2.0,1.0]],"type":"LineString"},"properties":{},"type":"Feature"}"#;
+    /// assert_eq!(String::from_utf8(written).unwrap(), format!("{}\n{}\n", line, line));
+    /// ```
+    pub fn to_ndjson_writer<'a, W>(
+        &self,
+        mut writer: W,
+        features: impl IntoIterator<Item = &'a Feature>,
+    ) -> Result<(), serde_json::Error>
+    where
+        W: io::Write,
+    {
+        for feature in features {
+            if self.skips(feature) {
+                continue;
+            }
+            let mut object = JsonObject::from(feature);
+            if self.feature_bboxes {
+                if let Some(bbox) = feature.bbox_calculated() {
+                    object.insert("bbox".to_owned(), bbox.into());
+                }
+            }
+            self.write_value(&mut writer, JsonValue::Object(object))?;
+            writer.write_all(b"\n").map_err(serde_json::Error::io)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_value<W: io::Write>(
+        &self,
+        writer: W,
+        mut value: JsonValue,
+    ) -> Result<(), serde_json::Error> {
+        if self.empty_geometries != EmptyGeometries::Keep {
+            self.replace_empty_geometries(&mut value);
+        }
+        if let Some(decimals) = self.precision {
+            round_coordinates(&mut value, decimals as usize, false);
+        }
+        if self.plain_decimals {
+            let mut serializer = serde_json::Serializer::with_formatter(writer, PlainFormatter);
+            self.wrap(&value).serialize(&mut serializer)
+        } else {
+            serde_json::to_writer(writer, &self.wrap(&value))
+        }
+    }
+
+    /// Apply `empty_geometries` to a serialized `Feature` or `FeatureCollection`
+    fn replace_empty_geometries(&self, value: &mut JsonValue) {
+        let has_empty_geometry = |feature: &JsonValue| matches!(feature.get("geometry"), Some(g) if is_empty_geometry(g));
+        match value.get("type").and_then(JsonValue::as_str) {
+            Some("FeatureCollection") => {
+                if let Some(JsonValue::Array(features)) = value.get_mut("features") {
+                    match self.empty_geometries {
+                        EmptyGeometries::Skip => {
+                            features.retain(|feature| !has_empty_geometry(feature))
+                        }
+                        _ => features
+                            .iter_mut()
+                            .for_each(|feature| self.replace_empty_geometries(feature)),
+                    }
+                }
+            }
+            Some("Feature")
+                if self.empty_geometries == EmptyGeometries::Null && has_empty_geometry(value) =>
+            {
+                value["geometry"] = JsonValue::Null;
+            }
+            _ => {}
+        }
+    }
+
+    fn wrap<'a>(&'a self, value: &'a JsonValue) -> WithOptions<'a> {
+        WithOptions {
+            value,
+            options: self,
+            placement: Placement::Root,
+        }
+    }
+}
+
+/// Serialize any GeoJSON object with its coordinates rounded to `DECIMALS` decimal places, for
+/// use with `#[serde(serialize_with = "...")]` on fields of your own types.
+///
+/// Positions and `bbox`es are rounded, including those of nested geometries and features, but
+/// properties and foreign members are written unchanged. `T` may also be an `Option` or `Vec`
+/// of GeoJSON objects.
+///
+/// A `bbox` which bounded the original coordinates still bounds the rounded ones. Use
+/// [`serialize_rounded_with_bbox`](fn.serialize_rounded_with_bbox.html) to have each `bbox`
+/// fit them exactly.
+///
+/// ```
+/// use geojson::{Geometry, Value};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Site {
+///     name: String,
+///     #[serde(serialize_with = "geojson::serialize_rounded::<2, _, _>")]
+///     location: Geometry,
+/// }
+///
+/// let site = Site {
+///     name: "Depot".to_string(),
+///     location: Geometry::new(Value::Point(vec![-0.12345, 51.50349])),
+/// };
+/// assert_eq!(
+///     serde_json::to_string(&site).unwrap(),
+///     r#"{"name":"Depot","location":{"coordinates":[-0.12,51.5],"type":"Point"}}"#
+/// );
+/// ```
+pub fn serialize_rounded<const DECIMALS: u32, T, S>(
+    object: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    let mut value = serde_json::to_value(object).map_err(serde::ser::Error::custom)?;
+    round_coordinates(&mut value, DECIMALS as usize, false);
+    value.serialize(serializer)
+}
+
+/// Like [`serialize_rounded`](fn.serialize_rounded.html), but every `bbox` present is
+/// recalculated from the rounded coordinates it covers instead of being rounded itself.
+///
+/// A 4-element `bbox` stays 4-element; any other is replaced with a 6-element box if every
+/// position it covers has a z coordinate. Boxes of objects without positions are rounded.
+///
+/// ```
+/// use geojson::{Geometry, Value};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Route {
+///     #[serde(serialize_with = "geojson::serialize_rounded_with_bbox::<1, _, _>")]
+///     path: Geometry,
+/// }
+///
+/// let mut path = Geometry::new(Value::LineString(vec![vec![0.04, 0.], vec![1.26, 2.]]));
+/// path.bbox = Some(vec![-5., -5., 5., 5.]);
+/// assert_eq!(
+///     serde_json::to_string(&Route { path }).unwrap(),
+///     r#"{"path":{"bbox":[0.0,0.0,1.3,2.0],"coordinates":[[0.0,0.0],[1.3,2.0]],"type":"LineString"}}"#
+/// );
+/// ```
+pub fn serialize_rounded_with_bbox<const DECIMALS: u32, T, S>(
+    object: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    let mut value = serde_json::to_value(object).map_err(serde::ser::Error::custom)?;
+    round_coordinates(&mut value, DECIMALS as usize, true);
+    value.serialize(serializer)
+}
+
+/// Whether a serialized geometry is empty, by parsing it back and asking
+/// [`Value::is_empty`](enum.Value.html#method.is_empty), so this always agrees with
+/// [`SerializeOptions::skips`]. Geometries which don't parse are never empty.
+fn is_empty_geometry(geometry: &JsonValue) -> bool {
+    matches!(Value::from_json_value(geometry.clone()), Ok(value) if value.is_empty())
+}
+
+/// The members of GeoJSON objects which hold coordinates or other GeoJSON objects
+const NESTED_MEMBERS: [&str; 4] = ["coordinates", "geometry", "geometries", "features"];
+
+/// Round `value` to `decimals` decimal places, as the precision options of serialization do
+///
+/// The value is scaled by `10^decimals` and rounded to the nearest whole number, with halves
+/// rounding away from zero. Rounding applies to the stored binary value, so `1.005`, which is
+/// stored as slightly less, rounds down to `1.0`. A result of zero is always `0.0`, never
+/// `-0.0`. Values too large to have a fractional part at that precision, and non-finite values,
+/// are returned unchanged.
+///
+/// ```
+/// use geojson::round_coordinate;
+///
+/// assert_eq!(round_coordinate(-0.12345, 2), -0.12);
+/// assert_eq!(round_coordinate(2.5, 0), 3.0);
+/// assert_eq!(round_coordinate(-0.001, 2).to_string(), "0");
+/// ```
+pub fn round_coordinate(value: f64, decimals: usize) -> f64 {
+    let factor = 10f64.powi(decimals.min(i32::MAX as usize) as i32);
+    let scaled = value * factor;
+    // Beyond 2^52 every float is a whole number, so scaling could only lose precision
+    let rounded = if scaled.is_nan() || scaled.abs() >= 4_503_599_627_370_496.0 {
+        value
+    } else {
+        scaled.round() / factor
+    };
+    // `-0.0` would be written with its sign
+    if rounded == 0. {
+        0.
+    } else {
+        rounded
+    }
+}
+
+/// Round every number in `value` to `decimals` decimal places, only descending into the members
+/// of objects which hold coordinates or other GeoJSON objects, and optionally replacing each
+/// `bbox` with one calculated from the rounded positions
+fn round_coordinates(value: &mut JsonValue, decimals: usize, recalculate_bbox: bool) {
+    match value {
+        JsonValue::Number(number) => {
+            if let Some(rounded) = number
+                .as_f64()
+                .and_then(|x| serde_json::Number::from_f64(round_coordinate(x, decimals)))
+            {
+                *number = rounded;
+            }
+        }
+        JsonValue::Array(values) => values
+            .iter_mut()
+            .for_each(|value| round_coordinates(value, decimals, recalculate_bbox)),
+        JsonValue::Object(object) => {
+            for key in &NESTED_MEMBERS {
+                if let Some(member) = object.get_mut(*key) {
+                    round_coordinates(member, decimals, recalculate_bbox);
+                }
+            }
+            let calculated = match object.get("bbox") {
+                Some(JsonValue::Array(bbox)) if recalculate_bbox => {
+                    let mut extent = Extent::default();
+                    for key in &NESTED_MEMBERS {
+                        if let Some(member) = object.get(*key) {
+                            add_positions(member, &mut extent);
+                        }
+                    }
+                    let dimension = match bbox.len() {
+                        4 => BboxDimension::Force2D,
+                        _ => BboxDimension::Auto,
+                    };
+                    extent.bbox(dimension)
+                }
+                _ => None,
+            };
+            match (calculated, object.get_mut("bbox")) {
+                (Some(calculated), Some(bbox)) => *bbox = calculated.into(),
+                (None, Some(bbox)) => round_coordinates(bbox, decimals, false),
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Add every position within the coordinates or nested GeoJSON objects of `value` to `extent`
+fn add_positions(value: &JsonValue, extent: &mut Extent) {
+    match value {
+        JsonValue::Array(values)
+            if !values.is_empty() && values.iter().all(JsonValue::is_number) =>
+        {
+            let position = values.iter().filter_map(JsonValue::as_f64).collect();
+            extent.add(&position);
+        }
+        JsonValue::Array(values) => values.iter().for_each(|value| add_positions(value, extent)),
+        JsonValue::Object(object) => {
+            for key in &NESTED_MEMBERS {
+                if let Some(member) = object.get(*key) {
+                    add_positions(member, extent);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Serialize `value` as compact JSON straight into `f`, without building a `String` first
+pub(crate) fn write_to_formatter<T: Serialize + ?Sized>(
+    f: &mut fmt::Formatter<'_>,
+    value: &T,
+) -> fmt::Result {
+    struct FormatterWriter<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+    impl io::Write for FormatterWriter<'_, '_> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            // serde_json only splits its output between characters, so each write is valid
+            // UTF-8 on its own
+            let s = std::str::from_utf8(buf)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            self.0.write_str(s).map_err(io::Error::other)?;
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    serde_json::to_writer(FormatterWriter(f), value).map_err(|_| fmt::Error)
+}
+
+/// A hash of the key-sorted serialization of `value`
+///
+/// Values which serialize to the same bytes, regardless of the order of their members, hash
+/// equally.
+pub(crate) fn content_hash(value: &JsonValue) -> u64 {
+    struct HashWriter(DefaultHasher);
+
+    impl io::Write for HashWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.write(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let options = SerializeOptions::new().sort_keys(true);
+    let mut writer = HashWriter(DefaultHasher::new());
+    serde_json::to_writer(&mut writer, &options.wrap(value)).unwrap();
+    writer.0.finish()
+}
+
+/// Writes compact JSON with floating point numbers in plain decimal notation
+struct PlainFormatter;
+
+impl serde_json::ser::Formatter for PlainFormatter {
+    fn write_f32<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: f32) -> io::Result<()> {
+        write_plain(writer, value.to_string())
+    }
+
+    fn write_f64<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: f64) -> io::Result<()> {
+        write_plain(writer, value.to_string())
+    }
+
+    // Numbers are written from their text with serde_json's `arbitrary_precision` feature,
+    // keeping every digit unless they use an exponent
+    fn write_number_str<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        value: &str,
+    ) -> io::Result<()> {
+        match value.parse::<f64>() {
+            Ok(float) if value.contains(['e', 'E']) => write_plain(writer, float.to_string()),
+            _ => writer.write_all(value.as_bytes()),
+        }
+    }
+}
+
+/// Write the `Display` form of a float, which never uses exponents, keeping a fractional part
+fn write_plain<W: ?Sized + io::Write>(writer: &mut W, formatted: String) -> io::Result<()> {
+    writer.write_all(formatted.as_bytes())?;
+    if formatted.contains(|c: char| c.is_ascii_digit()) && !formatted.contains('.') {
+        writer.write_all(b".0")?;
+    }
+    Ok(())
+}
+
+/// Where a JSON value sits within the object being serialized
+#[derive(Clone, Copy, PartialEq)]
+enum Placement {
+    /// The object itself
+    Root,
+    /// The `features` of a root `FeatureCollection`
+    Features,
+    /// An entry of those `features`
+    Feature,
+    /// Anywhere else
+    Other,
+}
+
+/// Serializes a JSON value according to `options`
+struct WithOptions<'a> {
+    value: &'a JsonValue,
+    options: &'a SerializeOptions,
+    placement: Placement,
+}
+
+impl<'a> WithOptions<'a> {
+    fn nested(&self, value: &'a JsonValue, placement: Placement) -> Self {
+        WithOptions {
+            value,
+            options: self.options,
+            placement,
+        }
+    }
+}
+
+impl<'a> Serialize for WithOptions<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.value {
+            JsonValue::Array(values) => {
+                let placement = match self.placement {
+                    Placement::Features => Placement::Feature,
+                    _ => Placement::Other,
+                };
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(&self.nested(value, placement))?;
+                }
+                seq.end()
+            }
+            JsonValue::Object(object) => {
+                let mut entries: Vec<(&String, &JsonValue)> = object.iter().collect();
+                if self.options.sort_keys {
+                    entries.sort_by(|a, b| a.0.cmp(b.0));
+                }
+                let order = self.options.member_order;
+                let top_level = matches!(self.placement, Placement::Root | Placement::Feature);
+                if order != MemberOrder::Keep && top_level && self.value["type"] == "Feature" {
+                    entries.sort_by_key(|(key, _)| order.rank(key));
+                }
+                let collection =
+                    self.placement == Placement::Root && self.value["type"] == "FeatureCollection";
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    let placement = if collection && key == "features" {
+                        Placement::Features
+                    } else {
+                        Placement::Other
+                    };
+                    map.serialize_entry(key, &self.nested(value, placement))?;
+                }
+                map.end()
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{content_hash, round_coordinate, EmptyGeometries, MemberOrder, SerializeOptions};
+    use crate::json::JsonObject;
+    use crate::{Feature, FeatureCollection, GeoJson, Geometry, Value};
+    use serde_json::json;
+
+    fn feature() -> Feature {
+        let mut foreign_members = JsonObject::new();
+        foreign_members.insert("z".to_string(), json!({"y": 1, "x": 2}));
+        foreign_members.insert("a".to_string(), json!(true));
+        Feature {
+            bbox: None,
+            geometry: Some(Geometry::new(Value::Point(vec![1.0, 2.0]))),
+            id: None,
+            properties: json!({"name": "b", "area": 1}).as_object().cloned(),
+            foreign_members: Some(foreign_members),
+        }
+    }
+
+    #[test]
+    fn default_options_match_display() {
+        let feature = feature();
+        assert_eq!(
+            SerializeOptions::new().to_string(&feature),
+            feature.to_string()
+        );
+    }
+
+    #[test]
+    fn sort_keys() {
+        let feature_collection = FeatureCollection {
+            bbox: None,
+            features: vec![feature()],
+            foreign_members: None,
+        };
+        let geojson = GeoJson::from(feature_collection);
+        let options = SerializeOptions::new().sort_keys(true);
+        assert_eq!(
+            options.to_string(&geojson),
+            r#"{"features":[{"a":true,"geometry":{"coordinates":[1.0,2.0],"type":"Point"},"properties":{"area":1,"name":"b"},"type":"Feature","z":{"x":2,"y":1}}],"type":"FeatureCollection"}"#
+        );
+
+        let mut written = vec![];
+        options.to_writer(&mut written, &geojson).unwrap();
+        assert_eq!(written, options.to_string(&geojson).into_bytes());
+    }
+
+    #[test]
+    fn ndjson_feature_bboxes() {
+        let mut line = feature();
+        line.foreign_members = None;
+        line.geometry = Some(Geometry::new(Value::LineString(vec![
+            vec![0.0, 0.0, 5.0],
+            vec![2.0, 1.0, 7.0],
+        ])));
+        line.bbox = Some(vec![-1.0, -1.0, 1.0, 1.0]);
+        let mut null = line.clone();
+        null.geometry = None;
+        let features = vec![line, null];
+
+        let mut plain = vec![];
+        SerializeOptions::new()
+            .to_ndjson_writer(&mut plain, &features)
+            .unwrap();
+        let mut with_bboxes = vec![];
+        SerializeOptions::new()
+            .feature_bboxes(true)
+            .to_ndjson_writer(&mut with_bboxes, &features)
+            .unwrap();
+
+        let bboxes = |written: &[u8]| -> Vec<serde_json::Value> {
+            std::str::from_utf8(written)
+                .unwrap()
+                .lines()
+                .map(|line| {
+                    serde_json::from_str::<serde_json::Value>(line).unwrap()["bbox"].clone()
+                })
+                .collect()
+        };
+        assert_eq!(
+            bboxes(&plain),
+            vec![json!([-1.0, -1.0, 1.0, 1.0]), json!([-1.0, -1.0, 1.0, 1.0])]
+        );
+        assert_eq!(
+            bboxes(&with_bboxes),
+            vec![
+                json!([0.0, 0.0, 5.0, 2.0, 1.0, 7.0]),
+                json!([-1.0, -1.0, 1.0, 1.0])
+            ]
+        );
+        assert_eq!(features[0].bbox, Some(vec![-1.0, -1.0, 1.0, 1.0]));
+    }
+
+    #[test]
+    fn serialize_rounded() {
+        let mut feature = feature();
+        feature.geometry = Some(Geometry::new(Value::GeometryCollection(vec![
+            Geometry::new(Value::LineString(vec![
+                vec![1.23456, 2.0],
+                vec![3.99999, 4.5],
+            ])),
+        ])));
+        feature.bbox = Some(vec![1.23456, 2.0, 3.99999, 4.5]);
+        feature.set_property("area", 1.23456);
+
+        let mut written = vec![];
+        super::serialize_rounded::<3, _, _>(
+            &Some(&feature),
+            &mut serde_json::Serializer::new(&mut written),
+        )
+        .unwrap();
+        let rounded: serde_json::Value = serde_json::from_slice(&written).unwrap();
+        assert_eq!(rounded["bbox"], json!([1.235, 2.0, 4.0, 4.5]));
+        assert_eq!(
+            rounded["geometry"]["geometries"][0]["coordinates"],
+            json!([[1.235, 2.0], [4.0, 4.5]])
+        );
+        assert_eq!(rounded["properties"]["area"], json!(1.23456));
+        assert_eq!(rounded["z"], json!({"y": 1, "x": 2}));
+    }
+
+    #[test]
+    fn serialize_rounded_with_bbox() {
+        let mut point = Geometry::new(Value::Point(vec![0.96, 0.5, 3.]));
+        point.bbox = Some(vec![0.96, 0.5, 3., 0.96, 0.5, 3.]);
+        let mut collection = Geometry::new(Value::GeometryCollection(vec![
+            point,
+            Geometry::new(Value::LineString(vec![vec![2., 2.04], vec![1., 1.]])),
+        ]));
+        collection.bbox = Some(vec![0., 0., 10., 10.]);
+        let mut feature = feature();
+        feature.geometry = Some(collection);
+        feature.bbox = Some(vec![0., 0., 0., 10., 10., 10.]);
+        let mut null = Feature::with_null_geometry();
+        null.bbox = Some(vec![0.04, 0.04, 0.96, 0.96]);
+
+        let mut written = vec![];
+        super::serialize_rounded_with_bbox::<1, _, _>(
+            &vec![feature, null],
+            &mut serde_json::Serializer::new(&mut written),
+        )
+        .unwrap();
+        let rounded: serde_json::Value = serde_json::from_slice(&written).unwrap();
+        let geometry = &rounded[0]["geometry"];
+        assert_eq!(
+            geometry["geometries"][0]["bbox"],
+            json!([1.0, 0.5, 3.0, 1.0, 0.5, 3.0])
+        );
+        assert_eq!(geometry["bbox"], json!([1.0, 0.5, 2.0, 2.0]));
+        assert_eq!(rounded[0]["bbox"], json!([1.0, 0.5, 2.0, 2.0]));
+        assert_eq!(rounded[1]["bbox"], json!([0.0, 0.0, 1.0, 1.0]));
+    }
+
+    #[test]
+    fn empty_geometries_compose_with_other_options() {
+        let empty = Feature::with_computed_bbox(Geometry::new(Value::GeometryCollection(vec![
+            Geometry::new(Value::MultiPolygon(vec![vec![vec![]]])),
+        ])));
+        let point = Feature::with_computed_bbox(Geometry::new(Value::Point(vec![1.2345, 2.])));
+        let features = [empty.clone(), point.clone(), Feature::with_null_geometry()];
+
+        let options = SerializeOptions::new()
+            .precision(1)
+            .empty_geometries(EmptyGeometries::Skip);
+        let mut written = vec![];
+        options.to_ndjson_writer(&mut written, &features).unwrap();
+        let lines: Vec<_> = std::str::from_utf8(&written).unwrap().lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("[1.2,2.0]"));
+        assert!(lines[1].contains(r#""geometry":null"#));
+
+        let options = options.empty_geometries(EmptyGeometries::Null);
+        let written = options.to_string(&empty);
+        assert!(written.contains(r#""geometry":null"#));
+        // The source is untouched
+        assert!(empty.geometry.as_ref().unwrap().value.is_empty());
+
+        let collection = FeatureCollection::from(features.to_vec());
+        let written = options.to_string(&collection);
+        let parsed: FeatureCollection = serde_json::from_str(&written).unwrap();
+        assert_eq!(parsed.features.len(), 3);
+        assert_eq!(parsed.count_null_geometries(), 2);
+    }
+
+    #[test]
+    fn empty_geometries_agree_with_value_is_empty() {
+        let options = SerializeOptions::new().empty_geometries(EmptyGeometries::Skip);
+        let unknown = match json!({"type": "Curve", "coordinates": []}) {
+            serde_json::Value::Object(raw) => Value::Unknown(raw),
+            _ => unreachable!(),
+        };
+        for geometry in [
+            Value::Point(vec![]),
+            Value::LineString(vec![vec![1.0, 2.0]]),
+            Value::GeometryCollection(vec![]),
+            // Unknown values are never empty, even without coordinates
+            unknown,
+        ] {
+            let feature = Feature {
+                geometry: Some(Geometry::new(geometry.clone())),
+                ..Feature::with_null_geometry()
+            };
+            let collection = options.to_string(&FeatureCollection::from(vec![feature.clone()]));
+            assert_eq!(
+                collection.contains(r#""features":[]"#),
+                options.skips(&feature),
+                "{:?}",
+                geometry
+            );
+        }
+    }
+
+    #[test]
+    fn member_order() {
+        let mut feature = Feature::with_null_geometry();
+        feature.id = Some(crate::feature::Id::Number(1.into()));
+        feature.set_property("name", "a");
+        let collection = FeatureCollection::from(vec![feature]);
+        let written = |order: MemberOrder| {
+            SerializeOptions::new()
+                .sort_keys(true)
+                .member_order(order)
+                .to_string(&collection)
+        };
+        assert_eq!(
+            written(MemberOrder::Keep),
+            r#"{"features":[{"geometry":null,"id":1,"properties":{"name":"a"},"type":"Feature"}],"type":"FeatureCollection"}"#
+        );
+        assert_eq!(
+            written(MemberOrder::TypeFirst),
+            r#"{"features":[{"type":"Feature","geometry":null,"id":1,"properties":{"name":"a"}}],"type":"FeatureCollection"}"#
+        );
+        assert_eq!(
+            written(MemberOrder::GeometryLast),
+            r#"{"features":[{"id":1,"properties":{"name":"a"},"type":"Feature","geometry":null}],"type":"FeatureCollection"}"#
+        );
+        assert_eq!(
+            written(MemberOrder::PropertiesLast),
+            r#"{"features":[{"geometry":null,"id":1,"type":"Feature","properties":{"name":"a"}}],"type":"FeatureCollection"}"#
+        );
+
+        // Objects typed `Feature` outside of the features themselves are left alone
+        let mut feature = Feature::with_null_geometry();
+        feature.set_property("nested", json!({"properties": {}, "type": "Feature"}));
+        let mut foreign = JsonObject::new();
+        foreign.insert(
+            "extra".to_owned(),
+            json!({"geometry": null, "type": "Feature"}),
+        );
+        feature.foreign_members = Some(foreign);
+        let collection = FeatureCollection::from(vec![feature]);
+        assert_eq!(
+            SerializeOptions::new()
+                .sort_keys(true)
+                .member_order(MemberOrder::TypeFirst)
+                .to_string(&collection),
+            r#"{"features":[{"type":"Feature","extra":{"geometry":null,"type":"Feature"},"geometry":null,"properties":{"nested":{"properties":{},"type":"Feature"}}}],"type":"FeatureCollection"}"#
+        );
+
+        // Only features are reordered
+        let geometry = Geometry::new(Value::Point(vec![1.0, 2.0]));
+        assert_eq!(
+            SerializeOptions::new()
+                .member_order(MemberOrder::TypeFirst)
+                .to_string(&geometry),
+            geometry.to_string()
+        );
+    }
+
+    #[test]
+    fn plain_decimals() {
+        let mut feature = Feature::with_null_geometry();
+        feature.geometry = Some(Geometry::new(Value::Point(vec![-1e-10, 1e21, 2.5e-3])));
+        feature.set_property("tiny", 1e-9);
+        feature.set_property("count", 3);
+
+        let options = SerializeOptions::new().sort_keys(true);
+        let default = options.to_string(&feature);
+        assert!(default.contains("[-1e-10,1e+21,0.0025]"));
+
+        let plain = options.clone().plain_decimals(true).to_string(&feature);
+        assert_eq!(
+            plain,
+            r#"{"geometry":{"coordinates":[-0.0000000001,1000000000000000000000.0,0.0025],"type":"Point"},"properties":{"count":3,"tiny":0.000000001},"type":"Feature"}"#
+        );
+        // With `arbitrary_precision`, numbers compare by their text rather than their value
+        #[cfg(not(feature = "arbitrary_precision"))]
+        {
+            let parsed: serde_json::Value = serde_json::from_str(&plain).unwrap();
+            assert_eq!(
+                parsed,
+                serde_json::from_str::<serde_json::Value>(&default).unwrap()
+            );
+        }
+
+        let rounded = options
+            .plain_decimals(true)
+            .precision(2)
+            .to_string(&feature);
+        assert!(rounded.contains("[0.0,1000000000000000000000.0,0.0]"));
+        assert!(rounded.contains(r#""tiny":0.000000001"#));
+    }
+
+    #[test]
+    fn content_hash_ignores_member_order() {
+        let a = json!({"b": 1, "a": [1.0, {"y": 2, "x": 3}]});
+        let mut b = JsonObject::new();
+        b.insert("a".to_string(), json!([1.0, {"x": 3, "y": 2}]));
+        b.insert("b".to_string(), json!(1));
+        assert_eq!(content_hash(&a), content_hash(&b.into()));
+        assert_ne!(content_hash(&a), content_hash(&json!({"b": 2})));
+    }
+
+    #[test]
+    fn round_coordinate_edge_cases() {
+        let is_positive_zero = |x: f64| x == 0. && x.is_sign_positive();
+
+        // Halves round away from zero
+        assert_eq!(round_coordinate(0.5, 0), 1.);
+        assert_eq!(round_coordinate(-0.5, 0), -1.);
+        assert_eq!(round_coordinate(0.125, 2), 0.13);
+        assert_eq!(round_coordinate(-0.125, 2), -0.13);
+        // 1.005 is stored as 1.00499999999999989...
+        assert_eq!(round_coordinate(1.005, 2), 1.);
+
+        assert!(is_positive_zero(round_coordinate(-0.004, 2)));
+        assert!(is_positive_zero(round_coordinate(-0.4, 0)));
+        assert!(is_positive_zero(round_coordinate(-0., 3)));
+        assert!(is_positive_zero(round_coordinate(-0., 400)));
+        assert_eq!(round_coordinate(-1e-300, 7).to_string(), "0");
+
+        assert_eq!(round_coordinate(-179.999_999_9, 6), -180.);
+        assert_eq!(round_coordinate(1e20 + 0.5, 2), 1e20 + 0.5);
+        assert_eq!(round_coordinate(1.5, 400), 1.5);
+        assert!(round_coordinate(f64::NAN, 2).is_nan());
+        assert_eq!(round_coordinate(f64::NEG_INFINITY, 2), f64::NEG_INFINITY);
+
+        let point = Geometry::new(Value::Point(vec![-0.0001, 0.0001]));
+        assert_eq!(
+            SerializeOptions::new().precision(2).to_string(&point),
+            r#"{"coordinates":[0.0,0.0],"type":"Point"}"#
+        );
+    }
+}
@@ -0,0 +1,288 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::bbox::Extent;
+use crate::json::JsonValue;
+use crate::{util, Bbox, BboxDimension, FeatureCollection};
+
+/// A summary of the contents of a `FeatureCollection`
+///
+/// Returned by [`FeatureCollection::stats`](struct.FeatureCollection.html#method.stats).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CollectionStats {
+    /// The number of features
+    pub feature_count: usize,
+    /// The number of features with each geometry `type`, such as `"Point"`. A
+    /// `GeometryCollection` is counted once, under its own type.
+    pub geometry_type_counts: BTreeMap<String, usize>,
+    /// The number of features whose geometry is `null`
+    pub null_geometry_count: usize,
+    /// The number of positions in all of the features' geometries
+    pub vertex_count: usize,
+    /// The calculated bounding box of all of the features' geometries, as from
+    /// [`FeatureCollection::bbox_calculated`](struct.FeatureCollection.html#method.bbox_calculated)
+    pub bbox: Option<Bbox>,
+    /// Every property key used by any feature, with the JSON types of the values seen for it:
+    /// `"null"`, `"boolean"`, `"number"`, `"string"`, `"array"` or `"object"`
+    pub property_types: BTreeMap<String, BTreeSet<&'static str>>,
+}
+
+/// The property types of a `FeatureCollection`, as inferred by
+/// [`FeatureCollection::infer_schema`](struct.FeatureCollection.html#method.infer_schema)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Schema {
+    /// Every property key used by any feature
+    pub fields: BTreeMap<String, FieldSchema>,
+}
+
+/// The inferred type of one property
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldSchema {
+    pub field_type: FieldType,
+    /// Whether some features lack the property, or have it set to `null`
+    pub nullable: bool,
+}
+
+/// The type of a property's non-`null` values
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    /// Every value is `null`
+    Null,
+    Bool,
+    /// Every value is an integer
+    Int,
+    /// Every value is a number, and at least one isn't an integer
+    Float,
+    String,
+    /// The values have more than one of the above types, or include arrays or objects
+    Mixed,
+}
+
+impl FieldType {
+    fn of(value: &JsonValue) -> Self {
+        match value {
+            JsonValue::Null => FieldType::Null,
+            JsonValue::Bool(_) => FieldType::Bool,
+            JsonValue::Number(number) if number.is_f64() => FieldType::Float,
+            JsonValue::Number(_) => FieldType::Int,
+            JsonValue::String(_) => FieldType::String,
+            JsonValue::Array(_) | JsonValue::Object(_) => FieldType::Mixed,
+        }
+    }
+
+    /// The narrowest type covering values of both `self` and `other`
+    fn merge(self, other: FieldType) -> Self {
+        match (self, other) {
+            (FieldType::Null, other) | (other, FieldType::Null) => other,
+            (FieldType::Int, FieldType::Float) | (FieldType::Float, FieldType::Int) => {
+                FieldType::Float
+            }
+            (a, b) if a == b => a,
+            _ => FieldType::Mixed,
+        }
+    }
+}
+
+impl FeatureCollection {
+    /// Summarise this collection's features in a single pass over them
+    ///
+    /// ```
+    /// use geojson::FeatureCollection;
+    ///
+    /// let collection: FeatureCollection = r#"{
+    ///     "type": "FeatureCollection",
+    ///     "features": [
+    ///         {"type": "Feature", "geometry": {"type": "Point", "coordinates": [1.0, 2.0]},
+    ///          "properties": {"name": "a"}},
+    ///         {"type": "Feature", "geometry": null, "properties": {"name": null}}
+    ///     ]
+    /// }"#
+    /// .parse::<geojson::GeoJson>()
+    /// .unwrap()
+    /// .into_feature_collection_opt()
+    /// .unwrap();
+    ///
+    /// let stats = collection.stats();
+    /// assert_eq!(stats.feature_count, 2);
+    /// assert_eq!(stats.null_geometry_count, 1);
+    /// assert_eq!(stats.geometry_type_counts["Point"], 1);
+    /// assert_eq!(stats.bbox, Some(vec![1.0, 2.0, 1.0, 2.0]));
+    /// assert_eq!(
+    ///     stats.property_types["name"].iter().collect::<Vec<_>>(),
+    ///     vec![&"null", &"string"]
+    /// );
+    /// ```
+    pub fn stats(&self) -> CollectionStats {
+        let mut stats = CollectionStats {
+            feature_count: self.features.len(),
+            ..CollectionStats::default()
+        };
+        let mut extent = Extent::default();
+        for feature in &self.features {
+            match &feature.geometry {
+                None => stats.null_geometry_count += 1,
+                Some(geometry) => {
+                    *stats
+                        .geometry_type_counts
+                        .entry(geometry.value.type_name().to_owned())
+                        .or_default() += 1;
+                    for position in geometry.value.coords_iter() {
+                        stats.vertex_count += 1;
+                        extent.add(position);
+                    }
+                }
+            }
+            for (key, value) in feature.properties_iter() {
+                stats
+                    .property_types
+                    .entry(key.clone())
+                    .or_default()
+                    .insert(util::json_type_name(value));
+            }
+        }
+        stats.bbox = extent.bbox(BboxDimension::Auto);
+        stats
+    }
+
+    /// Infer the type of every property from the values the features have for it
+    ///
+    /// Integers and other numbers together make a `Float` field, and any other combination of
+    /// types makes a `Mixed` one. `null` values don't count towards the type, but make the field
+    /// nullable, as does any feature without the property.
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection, FieldType};
+    /// use serde_json::json;
+    ///
+    /// let features = vec![json!({"pop": 10, "name": "a"}), json!({"pop": 2.5})]
+    ///     .into_iter()
+    ///     .map(|properties| {
+    ///         let mut feature = Feature::with_null_geometry();
+    ///         feature.properties = properties.as_object().cloned();
+    ///         feature
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let schema = FeatureCollection::from(features).infer_schema();
+    /// assert_eq!(schema.fields["pop"].field_type, FieldType::Float);
+    /// assert!(!schema.fields["pop"].nullable);
+    /// assert_eq!(schema.fields["name"].field_type, FieldType::String);
+    /// assert!(schema.fields["name"].nullable);
+    /// ```
+    pub fn infer_schema(&self) -> Schema {
+        let mut fields: BTreeMap<String, (FieldSchema, usize)> = BTreeMap::new();
+        for feature in &self.features {
+            for (key, value) in feature.properties_iter() {
+                let value_type = FieldType::of(value);
+                let (field, count) = fields.entry(key.clone()).or_insert((
+                    FieldSchema {
+                        field_type: FieldType::Null,
+                        nullable: false,
+                    },
+                    0,
+                ));
+                field.field_type = field.field_type.merge(value_type);
+                field.nullable |= value.is_null();
+                *count += 1;
+            }
+        }
+        let feature_count = self.features.len();
+        Schema {
+            fields: fields
+                .into_iter()
+                .map(|(key, (mut field, count))| {
+                    field.nullable |= count < feature_count;
+                    (key, field)
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Feature, FeatureCollection, Geometry, Value};
+    use serde_json::json;
+
+    #[test]
+    fn infer_schema() {
+        use crate::{FieldSchema, FieldType};
+
+        let features = vec![
+            json!({"id": 1, "flag": true, "tag": "a", "note": null, "extra": [1]}),
+            json!({"id": 2, "flag": false, "tag": 3, "note": null, "extra": [2]}),
+        ]
+        .into_iter()
+        .map(|properties| {
+            let mut feature = Feature::with_null_geometry();
+            feature.properties = properties.as_object().cloned();
+            feature
+        })
+        .collect::<Vec<_>>();
+        let schema = FeatureCollection::from(features).infer_schema();
+        let field = |field_type, nullable| FieldSchema {
+            field_type,
+            nullable,
+        };
+        assert_eq!(schema.fields["id"], field(FieldType::Int, false));
+        assert_eq!(schema.fields["flag"], field(FieldType::Bool, false));
+        assert_eq!(schema.fields["tag"], field(FieldType::Mixed, false));
+        assert_eq!(schema.fields["note"], field(FieldType::Null, true));
+        assert_eq!(schema.fields["extra"], field(FieldType::Mixed, false));
+    }
+
+    #[test]
+    fn stats() {
+        let mut line = Feature::with_null_geometry();
+        line.geometry = Some(Geometry::new(Value::LineString(vec![
+            vec![0., 0.],
+            vec![2., 3.],
+        ])));
+        line.set_property("lanes", 2);
+        let mut collection = Feature::with_null_geometry();
+        collection.geometry = Some(Geometry::new(Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![-1., 5.])),
+        ])));
+        collection.set_property("lanes", "two");
+        collection.set_property("tags", json!(["a"]));
+
+        let stats =
+            FeatureCollection::from(vec![line, collection, Feature::with_null_geometry()]).stats();
+        assert_eq!(stats.feature_count, 3);
+        assert_eq!(stats.null_geometry_count, 1);
+        assert_eq!(stats.vertex_count, 3);
+        assert_eq!(stats.bbox, Some(vec![-1., 0., 2., 5.]));
+        assert_eq!(
+            stats.geometry_type_counts,
+            vec![
+                ("GeometryCollection".to_owned(), 1),
+                ("LineString".to_owned(), 1)
+            ]
+            .into_iter()
+            .collect()
+        );
+        assert_eq!(
+            stats.property_types["lanes"],
+            vec!["number", "string"].into_iter().collect()
+        );
+        assert_eq!(
+            stats.property_types["tags"],
+            vec!["array"].into_iter().collect()
+        );
+
+        assert_eq!(FeatureCollection::from(vec![]).stats().bbox, None);
+    }
+}
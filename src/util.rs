@@ -14,13 +14,37 @@
 
 use crate::errors::Error;
 use crate::json::{JsonObject, JsonValue};
-use crate::{feature, Bbox, Feature, Geometry, Position, Value};
+use crate::{feature, Bbox, Feature, Geometry, ParseOptions, Position, Value};
 
+/// Remove the `type` member from `value`, failing unless it is a string
 pub fn expect_type(value: &mut JsonObject) -> Result<String, Error> {
     let prop = expect_property(value, "type")?;
     expect_string(prop)
 }
 
+/// The UTF-8 encoding of the byte order mark some Windows tools start text files with
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Used by the entry points which parse whole documents, which skip a leading byte order mark
+pub fn strip_bom(input: &[u8]) -> &[u8] {
+    input.strip_prefix(UTF8_BOM).unwrap_or(input)
+}
+
+/// Like `strip_bom`, for documents which are already strings
+pub fn strip_bom_str(input: &str) -> &str {
+    input.strip_prefix('\u{FEFF}').unwrap_or(input)
+}
+
+/// Used by the entry points which parse whole documents, so that empty input has its own error
+pub fn expect_non_empty(input: &[u8]) -> Result<(), Error> {
+    if input.iter().all(|byte| b" \t\n\r".contains(byte)) {
+        Err(Error::EmptyInput)
+    } else {
+        Ok(())
+    }
+}
+
+/// Unwrap a JSON string, failing with `Error::ExpectedStringValue` for anything else
 pub fn expect_string(value: JsonValue) -> Result<String, Error> {
     match value {
         JsonValue::String(s) => Ok(s),
@@ -28,6 +52,10 @@ pub fn expect_string(value: JsonValue) -> Result<String, Error> {
     }
 }
 
+/// Read a JSON number as an `f64`, failing with `Error::ExpectedF64Value` for anything else
+///
+/// Integers are accepted as well as decimals. An `f64` represents integers exactly only up to
+/// 2<sup>53</sup> in magnitude, and larger ones are rounded to the nearest value it can.
 pub fn expect_f64(value: &JsonValue) -> Result<f64, Error> {
     match value.as_f64() {
         Some(v) => Ok(v),
@@ -35,6 +63,8 @@ pub fn expect_f64(value: &JsonValue) -> Result<f64, Error> {
     }
 }
 
+/// Borrow the members of a JSON array, failing with `Error::ExpectedArrayValue` for anything
+/// else
 pub fn expect_array(value: &JsonValue) -> Result<&Vec<JsonValue>, Error> {
     match value.as_array() {
         Some(v) => Ok(v),
@@ -76,20 +106,42 @@ pub fn get_coords_value(object: &mut JsonObject) -> Result<JsonValue, Error> {
 }
 
 /// Used by FeatureCollection, Feature, Geometry
-pub fn get_bbox(object: &mut JsonObject) -> Result<Option<Bbox>, Error> {
-    let bbox_json = match object.remove("bbox") {
-        Some(b) => b,
-        None => return Ok(None),
-    };
+pub fn get_bbox(object: &mut JsonObject, options: &ParseOptions) -> Result<Option<Bbox>, Error> {
+    match object.remove("bbox") {
+        Some(bbox_json) => check_bbox(expect_bbox(bbox_json)?, options).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Fail with `Error::BboxUnexpectedLength` unless `bbox` has 4 or 6 elements, or 2 if
+/// `options` allow point bboxes, which are expanded to 4
+pub(crate) fn check_bbox(bbox: Bbox, options: &ParseOptions) -> Result<Bbox, Error> {
+    match bbox[..] {
+        [_, _, _, _] | [_, _, _, _, _, _] => Ok(bbox),
+        [x, y] if options.allow_point_bboxes => Ok(vec![x, y, x, y]),
+        _ => Err(Error::BboxUnexpectedLength {
+            expected: if options.allow_point_bboxes {
+                "2, 4 or 6"
+            } else {
+                "4 or 6"
+            },
+            actual: bbox.len(),
+        }),
+    }
+}
+
+/// Read a `bbox` from an array of numbers
+///
+/// The number of elements isn't checked.
+pub fn expect_bbox(bbox_json: JsonValue) -> Result<Bbox, Error> {
     let bbox_array = match bbox_json {
         JsonValue::Array(a) => a,
         _ => return Err(Error::BboxExpectedArray(bbox_json)),
     };
-    let bbox = bbox_array
+    bbox_array
         .into_iter()
         .map(|i| i.as_f64().ok_or(Error::BboxExpectedNumericValues(i)))
-        .collect::<Result<Vec<_>, _>>()?;
-    Ok(Some(bbox))
+        .collect::<Result<Vec<_>, _>>()
 }
 
 /// Used by FeatureCollection, Feature, Geometry
@@ -104,6 +156,11 @@ pub fn get_foreign_members(object: JsonObject) -> Result<Option<JsonObject>, Err
 /// Used by Feature
 pub fn get_properties(object: &mut JsonObject) -> Result<Option<JsonObject>, Error> {
     let properties = expect_property(object, "properties")?;
+    expect_properties(properties)
+}
+
+/// Read the value of a feature's `properties` member, which must be an object or `null`
+pub fn expect_properties(properties: JsonValue) -> Result<Option<JsonObject>, Error> {
     match properties {
         JsonValue::Object(x) => Ok(Some(x)),
         JsonValue::Null => Ok(None),
@@ -144,13 +201,25 @@ pub fn get_coords_3d_pos(object: &mut JsonObject) -> Result<Vec<Vec<Vec<Position
 }
 
 /// Used by Value::GeometryCollection
-pub fn get_geometries(object: &mut JsonObject) -> Result<Vec<Geometry>, Error> {
+pub fn get_geometries(
+    object: &mut JsonObject,
+    options: &ParseOptions,
+) -> Result<Vec<Geometry>, Error> {
+    if options.max_depth == 0 {
+        return Err(Error::MaxDepthExceeded);
+    }
     let geometries_json = expect_property(object, "geometries")?;
     let geometries_array = expect_owned_array(geometries_json)?;
     let mut geometries = Vec::with_capacity(geometries_array.len());
+    // The collection's dimension is checked as a whole, so that errors give the full path
+    let options = &ParseOptions {
+        expected_dimension: None,
+        max_depth: options.max_depth - 1,
+        ..options.clone()
+    };
     for json in geometries_array {
         let obj = expect_owned_object(json)?;
-        let geometry = Geometry::from_json_object(obj)?;
+        let geometry = Geometry::from_json_object_with(obj, options)?;
         geometries.push(geometry);
     }
     Ok(geometries)
@@ -159,16 +228,108 @@ pub fn get_geometries(object: &mut JsonObject) -> Result<Vec<Geometry>, Error> {
 /// Used by Feature
 pub fn get_id(object: &mut JsonObject) -> Result<Option<feature::Id>, Error> {
     match object.remove("id") {
-        Some(JsonValue::Number(x)) => Ok(Some(feature::Id::Number(x))),
-        Some(JsonValue::String(s)) => Ok(Some(feature::Id::String(s))),
-        Some(v) => Err(Error::FeatureInvalidIdentifierType(v)),
+        Some(id) => expect_id(id),
         None => Ok(None),
     }
 }
 
+/// Strings and numbers are valid identifiers, and `null` is treated as no identifier
+pub fn expect_id(id: JsonValue) -> Result<Option<feature::Id>, Error> {
+    match id {
+        JsonValue::Number(x) => Ok(Some(feature::Id::Number(x))),
+        JsonValue::String(s) => Ok(Some(feature::Id::String(s))),
+        JsonValue::Null => Ok(None),
+        JsonValue::Bool(_) => Err(Error::InvalidFeatureId {
+            found: "boolean".to_string(),
+        }),
+        JsonValue::Array(_) => Err(Error::InvalidFeatureId {
+            found: "array".to_string(),
+        }),
+        JsonValue::Object(_) => Err(Error::InvalidFeatureId {
+            found: "object".to_string(),
+        }),
+    }
+}
+
+/// The name of a JSON value's type: "null", "boolean", "number", "string", "array" or "object"
+pub fn json_type_name(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "array",
+        JsonValue::Object(_) => "object",
+    }
+}
+
+/// Fails if a geometry of type `type_` lacks the member it needs, but has the one used by the
+/// other kind of geometry
+///
+/// Used by Value, and by the `Deserialize` impls via `ValueMembers`
+pub fn check_geometry_member(
+    type_: &str,
+    has_coordinates: bool,
+    has_geometries: bool,
+) -> Result<(), Error> {
+    let (expected, found) = match type_ {
+        "GeometryCollection" if !has_geometries && has_coordinates => ("geometries", "coordinates"),
+        "Point" | "MultiPoint" | "LineString" | "MultiLineString" | "Polygon" | "MultiPolygon"
+            if !has_coordinates && has_geometries =>
+        {
+            ("coordinates", "geometries")
+        }
+        _ => return Ok(()),
+    };
+    Err(Error::UnexpectedMember {
+        type_: type_.to_string(),
+        found: found.to_string(),
+        expected: expected.to_string(),
+    })
+}
+
+/// Fails if a `Feature` lacks `geometry` but has `features`, or a `FeatureCollection` lacks
+/// `features` but has `geometry`, as when an object has been given the wrong `type`
+///
+/// Used by Feature, FeatureCollection, and by the `Deserialize` impls via `ObjectMembers`
+pub fn check_feature_member(
+    type_: &str,
+    has_geometry: bool,
+    has_features: bool,
+) -> Result<(), Error> {
+    let (expected, found) = match type_ {
+        "Feature" if !has_geometry && has_features => ("geometry", "features"),
+        "FeatureCollection" if !has_features && has_geometry => ("features", "geometry"),
+        _ => return Ok(()),
+    };
+    Err(Error::UnexpectedMember {
+        type_: type_.to_string(),
+        found: found.to_string(),
+        expected: expected.to_string(),
+    })
+}
+
 /// Used by Geometry, Value
-pub fn get_value(object: &mut JsonObject) -> Result<Value, Error> {
+pub fn get_value(object: &mut JsonObject, options: &ParseOptions) -> Result<Value, Error> {
+    if options.reject_inexact_coordinates {
+        if let Some(coordinates) = object.get("coordinates") {
+            check_exact_integers(coordinates)?;
+        }
+    }
+    let value = get_unchecked_value(object, options)?;
+    if let Some(expected) = options.expected_dimension {
+        value.check_dimension("", &mut Some(expected))?;
+    }
+    Ok(value)
+}
+
+fn get_unchecked_value(object: &mut JsonObject, options: &ParseOptions) -> Result<Value, Error> {
     let res = &*expect_type(object)?;
+    check_geometry_member(
+        res,
+        object.contains_key("coordinates"),
+        object.contains_key("geometries"),
+    )?;
     match res {
         "Point" => Ok(Value::Point(get_coords_one_pos(object)?)),
         "MultiPoint" => Ok(Value::MultiPoint(get_coords_1d_pos(object)?)),
@@ -176,38 +337,111 @@ pub fn get_value(object: &mut JsonObject) -> Result<Value, Error> {
         "MultiLineString" => Ok(Value::MultiLineString(get_coords_2d_pos(object)?)),
         "Polygon" => Ok(Value::Polygon(get_coords_2d_pos(object)?)),
         "MultiPolygon" => Ok(Value::MultiPolygon(get_coords_3d_pos(object)?)),
-        "GeometryCollection" => Ok(Value::GeometryCollection(get_geometries(object)?)),
+        "GeometryCollection" => Ok(Value::GeometryCollection(get_geometries(object, options)?)),
+        _ if options.allow_unknown_geometries => {
+            let mut raw = std::mem::take(object);
+            raw.insert("type".to_string(), JsonValue::String(res.to_string()));
+            Ok(Value::Unknown(raw))
+        }
         _ => Err(Error::GeometryUnknownType(res.to_string())),
     }
 }
 
 /// Used by Feature
-pub fn get_geometry(object: &mut JsonObject) -> Result<Option<Geometry>, Error> {
+pub fn get_geometry(
+    object: &mut JsonObject,
+    options: &ParseOptions,
+) -> Result<Option<Geometry>, Error> {
     let geometry = expect_property(object, "geometry")?;
     match geometry {
         JsonValue::Object(x) => {
-            let geometry_object = Geometry::from_json_object(x)?;
+            let geometry_object = Geometry::from_json_object_with(x, options)?;
             Ok(Some(geometry_object))
         }
         JsonValue::Null => Ok(None),
+        JsonValue::String(ref encoded) if options.allow_string_geometries => {
+            match serde_json::from_str(encoded).map_err(Error::MalformedJson)? {
+                JsonValue::Object(x) => Ok(Some(Geometry::from_json_object_with(x, options)?)),
+                _ => Err(Error::FeatureInvalidGeometryValue(geometry)),
+            }
+        }
         _ => Err(Error::FeatureInvalidGeometryValue(geometry)),
     }
 }
 
 /// Used by FeatureCollection
-pub fn get_features(object: &mut JsonObject) -> Result<Vec<Feature>, Error> {
-    let prop = expect_property(object, "features")?;
-    let features_json = expect_owned_array(prop)?;
+pub fn get_features(
+    object: &mut JsonObject,
+    options: &ParseOptions,
+) -> Result<Vec<Feature>, Error> {
+    let features_json = match expect_property(object, "features")? {
+        JsonValue::Array(features) => features,
+        features => return Err(Error::FeaturesExpectedArray(features)),
+    };
     let mut features = Vec::with_capacity(features_json.len());
     for feature in features_json {
         let feature = expect_owned_object(feature)?;
-        let feature: Feature = Feature::from_json_object(feature)?;
+        let feature = Feature::from_json_object_with(feature, options)?;
         features.push(feature);
     }
     Ok(features)
 }
 
-fn json_to_position(json: &JsonValue) -> Result<Position, Error> {
+/// Fail with `Error::CoordinatePrecisionLoss` at the first integer within `json` which an
+/// `f64` can't represent exactly
+fn check_exact_integers(json: &JsonValue) -> Result<(), Error> {
+    // 2^64, the first integer past the range of a `u64`, and exactly representable
+    const TWO_POW_64: f64 = 18_446_744_073_709_551_616.;
+    match json {
+        JsonValue::Array(items) => items.iter().try_for_each(check_exact_integers),
+        JsonValue::Number(number) => {
+            let exact = if let Some(n) = number.as_u64() {
+                let f = n as f64;
+                f < TWO_POW_64 && f as u64 == n
+            } else if let Some(n) = number.as_i64() {
+                n as f64 as i64 == n
+            } else {
+                wide_integer_is_exact(number)
+            };
+            if exact {
+                Ok(())
+            } else {
+                Err(Error::CoordinatePrecisionLoss(json.clone()))
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether `number`, which fits neither a `u64` nor an `i64`, is exactly representable if it's
+/// an integer
+///
+/// Only `arbitrary_precision` keeps the text of such numbers; otherwise serde_json has already
+/// rounded them to an `f64`, and they can't be told apart from decimals.
+#[cfg(feature = "arbitrary_precision")]
+fn wide_integer_is_exact(number: &serde_json::Number) -> bool {
+    let text = number.to_string();
+    let digits = text.strip_prefix('-').unwrap_or(&text);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return true;
+    }
+    let digits = match digits.trim_start_matches('0') {
+        "" => "0",
+        digits => digits,
+    };
+    // An integral `f64` is printed with every digit of its exact value
+    matches!(number.as_f64(), Some(f) if format!("{:.0}", f.abs()) == digits)
+}
+
+#[cfg(not(feature = "arbitrary_precision"))]
+fn wide_integer_is_exact(_: &serde_json::Number) -> bool {
+    true
+}
+
+/// Read a position from an array of numbers
+///
+/// The number of elements isn't checked.
+pub fn json_to_position(json: &JsonValue) -> Result<Position, Error> {
     let coords_array = expect_array(json)?;
     let mut coords = Vec::with_capacity(coords_array.len());
     for position in coords_array {
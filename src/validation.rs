@@ -0,0 +1,241 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Feature, FeatureCollection, Geometry, Position, Value};
+use thiserror::Error;
+
+/// A way in which a GeoJSON object breaks the requirements of
+/// [RFC 7946](https://tools.ietf.org/html/rfc7946)
+///
+/// Only requirements the RFC states with "MUST" are checked, so, for example, rings wound
+/// against the right-hand rule are accepted.
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum ValidationError {
+    #[error("A position needs at least 2 coordinates, but has {len}")]
+    PositionTooShort { len: usize },
+    #[error("A position has a coordinate which is NaN or infinite")]
+    NonFiniteCoordinate,
+    #[error("A LineString needs at least 2 positions, but has {len}")]
+    LineStringTooShort { len: usize },
+    #[error("A linear ring needs at least 4 positions, but has {len}")]
+    RingTooShort { len: usize },
+    #[error("A linear ring's first and last positions differ")]
+    RingNotClosed,
+    /// A `bbox` must hold a minimum and a maximum for each of at least two axes
+    #[error("A 'bbox' needs an even number of at least 4 elements, but has {len}")]
+    BboxInvalidLength { len: usize },
+    /// A `bbox`'s minimum exceeds its maximum along an axis other than the first, which may
+    /// cross the antimeridian
+    #[error("A 'bbox' has a minimum greater than its maximum along axis {axis}")]
+    BboxMinExceedsMax { axis: usize },
+    #[error("Encountered an unknown geometry type: `{0}`")]
+    UnknownGeometryType(String),
+}
+
+impl Value {
+    /// Check the value's positions, lines and rings against the RFC, returning every problem
+    /// found
+    ///
+    /// ```
+    /// use geojson::{ValidationError, Value};
+    ///
+    /// let ring = vec![vec![0., 0.], vec![1., 0.], vec![0., 1.]];
+    /// assert_eq!(
+    ///     Value::Polygon(vec![ring]).validate(),
+    ///     vec![
+    ///         ValidationError::RingTooShort { len: 3 },
+    ///         ValidationError::RingNotClosed
+    ///     ]
+    /// );
+    /// ```
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        self.validate_into(&mut errors);
+        errors
+    }
+
+    fn validate_into(&self, errors: &mut Vec<ValidationError>) {
+        match self {
+            Value::Point(position) => validate_position(position, errors),
+            Value::MultiPoint(positions) => validate_positions(positions, errors),
+            Value::LineString(line) => validate_line(line, errors),
+            Value::MultiLineString(lines) => {
+                lines.iter().for_each(|line| validate_line(line, errors))
+            }
+            Value::Polygon(rings) => rings.iter().for_each(|ring| validate_ring(ring, errors)),
+            Value::MultiPolygon(polygons) => polygons
+                .iter()
+                .flatten()
+                .for_each(|ring| validate_ring(ring, errors)),
+            Value::GeometryCollection(geometries) => geometries
+                .iter()
+                .for_each(|geometry| geometry.validate_into(errors)),
+            Value::Unknown(_) => errors.push(ValidationError::UnknownGeometryType(
+                self.type_name().to_string(),
+            )),
+        }
+    }
+}
+
+impl Geometry {
+    /// Check the geometry's `bbox` and value against the RFC, returning every problem found
+    ///
+    /// See [`Value::validate`](enum.Value.html#method.validate).
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        self.validate_into(&mut errors);
+        errors
+    }
+
+    fn validate_into(&self, errors: &mut Vec<ValidationError>) {
+        if let Some(bbox) = &self.bbox {
+            validate_bbox(bbox, errors);
+        }
+        self.value.validate_into(errors);
+    }
+}
+
+impl Feature {
+    /// Check the feature's `bbox` and geometry against the RFC, returning every problem found
+    ///
+    /// See [`Value::validate`](enum.Value.html#method.validate).
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        if let Some(bbox) = &self.bbox {
+            validate_bbox(bbox, &mut errors);
+        }
+        if let Some(geometry) = &self.geometry {
+            geometry.validate_into(&mut errors);
+        }
+        errors
+    }
+}
+
+impl FeatureCollection {
+    /// Validate every feature, returning the index and problems of each one which has any
+    ///
+    /// The collection's own `bbox` isn't checked. See
+    /// [`Value::validate`](enum.Value.html#method.validate).
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection, Geometry, ValidationError, Value};
+    ///
+    /// let collection = FeatureCollection::from(vec![
+    ///     Feature::with_null_geometry(),
+    ///     Feature::with_computed_bbox(Geometry::new(Value::LineString(vec![vec![1., 2.]]))),
+    /// ]);
+    /// assert_eq!(
+    ///     collection.validate_all(),
+    ///     vec![(1, vec![ValidationError::LineStringTooShort { len: 1 }])]
+    /// );
+    /// ```
+    pub fn validate_all(&self) -> Vec<(usize, Vec<ValidationError>)> {
+        self.features
+            .iter()
+            .map(Feature::validate)
+            .enumerate()
+            .filter(|(_, errors)| !errors.is_empty())
+            .collect()
+    }
+}
+
+fn validate_position(position: &[f64], errors: &mut Vec<ValidationError>) {
+    if position.len() < 2 {
+        errors.push(ValidationError::PositionTooShort {
+            len: position.len(),
+        });
+    }
+    if position.iter().any(|coordinate| !coordinate.is_finite()) {
+        errors.push(ValidationError::NonFiniteCoordinate);
+    }
+}
+
+fn validate_positions(positions: &[Position], errors: &mut Vec<ValidationError>) {
+    positions
+        .iter()
+        .for_each(|position| validate_position(position, errors));
+}
+
+fn validate_line(line: &[Position], errors: &mut Vec<ValidationError>) {
+    if line.len() < 2 {
+        errors.push(ValidationError::LineStringTooShort { len: line.len() });
+    }
+    validate_positions(line, errors);
+}
+
+fn validate_ring(ring: &[Position], errors: &mut Vec<ValidationError>) {
+    if ring.len() < 4 {
+        errors.push(ValidationError::RingTooShort { len: ring.len() });
+    }
+    if ring.first() != ring.last() {
+        errors.push(ValidationError::RingNotClosed);
+    }
+    validate_positions(ring, errors);
+}
+
+fn validate_bbox(bbox: &[f64], errors: &mut Vec<ValidationError>) {
+    if bbox.len() < 4 || !bbox.len().is_multiple_of(2) {
+        errors.push(ValidationError::BboxInvalidLength { len: bbox.len() });
+        return;
+    }
+    let dims = bbox.len() / 2;
+    for axis in 1..dims {
+        if bbox[axis] > bbox[dims + axis] {
+            errors.push(ValidationError::BboxMinExceedsMax { axis });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValidationError;
+    use crate::{Feature, FeatureCollection, Geometry, Value};
+
+    #[test]
+    fn validate_all_reports_every_bad_feature() {
+        let good = Feature::with_computed_bbox(Geometry::new(Value::Point(vec![1., 2.])));
+        let mut bad_bbox = good.clone();
+        bad_bbox.bbox = Some(vec![5., 3., 4., 2., 1.]);
+        let mut antimeridian = good.clone();
+        antimeridian.bbox = Some(vec![170., -10., -170., 10.]);
+        let mut inverted = good.clone();
+        inverted.bbox = Some(vec![0., 3., 0., 1., 2., 1.]);
+
+        let mut nested = Geometry::new(Value::MultiPoint(vec![vec![f64::NAN, 0.], vec![1.]]));
+        nested.bbox = Some(vec![]);
+        let collection = FeatureCollection::from(vec![
+            good,
+            bad_bbox,
+            antimeridian,
+            Feature::with_computed_bbox(Geometry::new(Value::GeometryCollection(vec![nested]))),
+            inverted,
+        ]);
+
+        assert_eq!(
+            collection.validate_all(),
+            vec![
+                (1, vec![ValidationError::BboxInvalidLength { len: 5 }]),
+                (
+                    3,
+                    vec![
+                        ValidationError::BboxInvalidLength { len: 0 },
+                        ValidationError::NonFiniteCoordinate,
+                        ValidationError::PositionTooShort { len: 1 },
+                    ]
+                ),
+                (4, vec![ValidationError::BboxMinExceedsMax { axis: 1 }]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Feature, FeatureCollection, GeoJson, Geometry, Position, Value};
+
+/// Hooks called while walking a GeoJSON object with [`GeoJson::accept`](enum.GeoJson.html#method.accept)
+///
+/// Every method does nothing by default, so implementors only write the hooks they need.
+///
+/// Objects are visited depth-first in document order, each before its contents: a
+/// `FeatureCollection`, then each of its features, where a `Feature` is followed by its
+/// geometry, if it has one. A `Geometry` is followed by its positions, or for a
+/// `GeometryCollection` by each of its members in turn.
+///
+/// ```
+/// use geojson::{GeoJson, Position, Visitor};
+///
+/// #[derive(Default)]
+/// struct MaxElevation(Option<f64>);
+///
+/// impl Visitor for MaxElevation {
+///     fn visit_position(&mut self, position: &Position) {
+///         if let Some(&z) = position.get(2) {
+///             self.0 = Some(self.0.map_or(z, |max| max.max(z)));
+///         }
+///     }
+/// }
+///
+/// let geojson: GeoJson = r#"{"type": "LineString", "coordinates": [[0, 0, 12], [1, 1, 30]]}"#
+///     .parse()
+///     .unwrap();
+/// let mut max = MaxElevation::default();
+/// geojson.accept(&mut max);
+/// assert_eq!(max.0, Some(30.0));
+/// ```
+pub trait Visitor {
+    /// Called for a `FeatureCollection`, before its features
+    fn visit_feature_collection(&mut self, _collection: &FeatureCollection) {}
+
+    /// Called for each `Feature`, before its geometry
+    fn visit_feature(&mut self, _feature: &Feature) {}
+
+    /// Called for each `Geometry`, including the members of a `GeometryCollection`, before its
+    /// positions or members
+    fn visit_geometry(&mut self, _geometry: &Geometry) {}
+
+    /// Called for each `Position`, in the order they appear in the geometry's coordinates
+    fn visit_position(&mut self, _position: &Position) {}
+}
+
+/// Hooks called while walking a GeoJSON object with
+/// [`GeoJson::accept_mut`](enum.GeoJson.html#method.accept_mut), which may modify what they
+/// are given
+///
+/// Every method does nothing by default. Objects are visited in the same order as by a
+/// [`Visitor`](trait.Visitor.html), each before its contents, so the walk continues into
+/// whatever a hook leaves behind: if `visit_feature` replaces a feature's geometry, it is the
+/// new geometry whose positions are visited next. `bbox`es aren't updated; use
+/// [`FeatureCollection::recompute_bbox`](struct.FeatureCollection.html#method.recompute_bbox)
+/// or similar afterwards if positions change.
+///
+/// ```
+/// use geojson::{GeoJson, Position, VisitorMut};
+///
+/// struct Round;
+///
+/// impl VisitorMut for Round {
+///     fn visit_position_mut(&mut self, position: &mut Position) {
+///         position.iter_mut().for_each(|coordinate| *coordinate = coordinate.round());
+///     }
+/// }
+///
+/// let mut geojson: GeoJson = r#"{"type": "Point", "coordinates": [1.4, 2.6]}"#
+///     .parse()
+///     .unwrap();
+/// geojson.accept_mut(&mut Round);
+/// assert_eq!(
+///     geojson.to_string(),
+///     r#"{"coordinates":[1.0,3.0],"type":"Point"}"#
+/// );
+/// ```
+pub trait VisitorMut {
+    /// Called for a `FeatureCollection`, before its features
+    fn visit_feature_collection_mut(&mut self, _collection: &mut FeatureCollection) {}
+
+    /// Called for each `Feature`, before its geometry
+    fn visit_feature_mut(&mut self, _feature: &mut Feature) {}
+
+    /// Called for each `Geometry`, including the members of a `GeometryCollection`, before its
+    /// positions or members
+    fn visit_geometry_mut(&mut self, _geometry: &mut Geometry) {}
+
+    /// Called for each `Position`, in the order they appear in the geometry's coordinates. The
+    /// position may be replaced outright, including by one with a different number of
+    /// coordinates.
+    fn visit_position_mut(&mut self, _position: &mut Position) {}
+}
+
+impl GeoJson {
+    /// Walk this object, calling the hooks of `visitor` for everything within it, in the order
+    /// described by [`Visitor`](trait.Visitor.html)
+    pub fn accept(&self, visitor: &mut impl Visitor) {
+        match self {
+            GeoJson::Geometry(geometry) => walk_geometry(geometry, visitor),
+            GeoJson::Feature(feature) => walk_feature(feature, visitor),
+            GeoJson::FeatureCollection(collection) => {
+                visitor.visit_feature_collection(collection);
+                for feature in &collection.features {
+                    walk_feature(feature, visitor);
+                }
+            }
+        }
+    }
+
+    /// Walk this object, calling the hooks of `visitor` for everything within it and letting
+    /// them modify it in place, in the order described by [`VisitorMut`](trait.VisitorMut.html)
+    pub fn accept_mut(&mut self, visitor: &mut impl VisitorMut) {
+        match self {
+            GeoJson::Geometry(geometry) => walk_geometry_mut(geometry, visitor),
+            GeoJson::Feature(feature) => walk_feature_mut(feature, visitor),
+            GeoJson::FeatureCollection(collection) => {
+                visitor.visit_feature_collection_mut(collection);
+                for feature in &mut collection.features {
+                    walk_feature_mut(feature, visitor);
+                }
+            }
+        }
+    }
+}
+
+fn walk_feature(feature: &Feature, visitor: &mut impl Visitor) {
+    visitor.visit_feature(feature);
+    if let Some(geometry) = &feature.geometry {
+        walk_geometry(geometry, visitor);
+    }
+}
+
+fn walk_geometry(geometry: &Geometry, visitor: &mut impl Visitor) {
+    visitor.visit_geometry(geometry);
+    match &geometry.value {
+        Value::GeometryCollection(geometries) => {
+            for member in geometries {
+                walk_geometry(member, visitor);
+            }
+        }
+        value => value
+            .coords_iter()
+            .for_each(|position| visitor.visit_position(position)),
+    }
+}
+
+fn walk_feature_mut(feature: &mut Feature, visitor: &mut impl VisitorMut) {
+    visitor.visit_feature_mut(feature);
+    if let Some(geometry) = &mut feature.geometry {
+        walk_geometry_mut(geometry, visitor);
+    }
+}
+
+fn walk_geometry_mut(geometry: &mut Geometry, visitor: &mut impl VisitorMut) {
+    visitor.visit_geometry_mut(geometry);
+    match &mut geometry.value {
+        Value::GeometryCollection(geometries) => {
+            for member in geometries {
+                walk_geometry_mut(member, visitor);
+            }
+        }
+        value => value
+            .coords_iter_mut()
+            .for_each(|position| visitor.visit_position_mut(position)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Visitor, VisitorMut};
+    use crate::{Feature, FeatureCollection, GeoJson, Geometry, Position, Value};
+
+    /// Records what was visited, in order
+    #[derive(Default)]
+    struct Trace(Vec<String>);
+
+    impl Visitor for Trace {
+        fn visit_feature_collection(&mut self, collection: &FeatureCollection) {
+            self.0
+                .push(format!("collection of {}", collection.features.len()));
+        }
+
+        fn visit_feature(&mut self, feature: &Feature) {
+            self.0
+                .push(format!("feature {:?}", feature.property("name")));
+        }
+
+        fn visit_geometry(&mut self, geometry: &Geometry) {
+            self.0.push(geometry.value.type_name().to_string());
+        }
+
+        fn visit_position(&mut self, position: &Position) {
+            self.0.push(format!("{:?}", position));
+        }
+    }
+
+    #[test]
+    fn traversal_order() {
+        let geojson: GeoJson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "a"}, "geometry": {
+                "type": "GeometryCollection", "geometries": [
+                    {"type": "Point", "coordinates": [0, 1]},
+                    {"type": "LineString", "coordinates": [[2, 3], [4, 5]]}
+                ]
+            }},
+            {"type": "Feature", "properties": null, "geometry": null}
+        ]}"#
+        .parse()
+        .unwrap();
+        let mut trace = Trace::default();
+        geojson.accept(&mut trace);
+        assert_eq!(
+            trace.0,
+            [
+                "collection of 2",
+                r#"feature Some(String("a"))"#,
+                "GeometryCollection",
+                "Point",
+                "[0.0, 1.0]",
+                "LineString",
+                "[2.0, 3.0]",
+                "[4.0, 5.0]",
+                "feature None",
+            ]
+        );
+    }
+
+    /// Renames a property, turns `MultiPoint`s into their first `Point`, and counts what it
+    /// sees, to check the walk follows each hook's changes
+    #[derive(Default)]
+    struct Edit {
+        positions: usize,
+    }
+
+    impl VisitorMut for Edit {
+        fn visit_feature_mut(&mut self, feature: &mut Feature) {
+            if let Some(name) = feature.remove_property("nom") {
+                feature.set_property("name", name);
+            }
+        }
+
+        fn visit_geometry_mut(&mut self, geometry: &mut Geometry) {
+            if let Value::MultiPoint(points) = &geometry.value {
+                geometry.value = Value::Point(points[0].clone());
+            }
+        }
+
+        fn visit_position_mut(&mut self, position: &mut Position) {
+            self.positions += 1;
+            position.push(0.);
+        }
+    }
+
+    #[test]
+    fn mutating_traversal() {
+        let mut geojson: GeoJson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"nom": "a"}, "geometry": {
+                "type": "MultiPoint", "coordinates": [[0, 1], [2, 3]]
+            }}
+        ]}"#
+        .parse()
+        .unwrap();
+        let mut edit = Edit::default();
+        geojson.accept_mut(&mut edit);
+        assert_eq!(edit.positions, 1);
+        assert_eq!(
+            geojson.to_string(),
+            r#"{"features":[{"geometry":{"coordinates":[0.0,1.0,0.0],"type":"Point"},"properties":{"name":"a"},"type":"Feature"}],"type":"FeatureCollection"}"#
+        );
+    }
+}
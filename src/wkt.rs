@@ -0,0 +1,202 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Write;
+
+use crate::{Error, Position, Value};
+
+impl Value {
+    /// Write this `Value` as Well-Known Text, e.g. `POINT (1 2)`
+    ///
+    /// A value is tagged `Z` and written with three coordinates per position if every one of
+    /// its positions has an elevation; otherwise only the first two coordinates are written.
+    /// Empty geometries are written as e.g. `LINESTRING EMPTY`.
+    ///
+    /// # Errors
+    ///
+    /// `Error::GeometryUnknownType` if this is, or a `GeometryCollection` contains, an
+    /// `Unknown` value.
+    ///
+    /// ```
+    /// use geojson::Value;
+    ///
+    /// let polygon = Value::Polygon(vec![vec![
+    ///     vec![0., 0.],
+    ///     vec![1., 0.],
+    ///     vec![0., 1.5],
+    ///     vec![0., 0.],
+    /// ]]);
+    /// assert_eq!(polygon.to_wkt().unwrap(), "POLYGON ((0 0, 1 0, 0 1.5, 0 0))");
+    /// ```
+    pub fn to_wkt(&self) -> Result<String, Error> {
+        let mut wkt = String::new();
+        write_value(&mut wkt, self)?;
+        Ok(wkt)
+    }
+}
+
+fn write_value(wkt: &mut String, value: &Value) -> Result<(), Error> {
+    let tag = match value {
+        Value::Point(_) => "POINT",
+        Value::MultiPoint(_) => "MULTIPOINT",
+        Value::LineString(_) => "LINESTRING",
+        Value::MultiLineString(_) => "MULTILINESTRING",
+        Value::Polygon(_) => "POLYGON",
+        Value::MultiPolygon(_) => "MULTIPOLYGON",
+        Value::GeometryCollection(_) => "GEOMETRYCOLLECTION",
+        Value::Unknown(_) => return Err(Error::GeometryUnknownType(value.type_name().to_string())),
+    };
+    wkt.push_str(tag);
+
+    let is_empty = match value {
+        Value::MultiPoint(positions) | Value::LineString(positions) => positions.is_empty(),
+        Value::MultiLineString(lines) | Value::Polygon(lines) => lines.is_empty(),
+        Value::MultiPolygon(polygons) => polygons.is_empty(),
+        Value::GeometryCollection(geometries) => geometries.is_empty(),
+        Value::Point(_) | Value::Unknown(_) => false,
+    };
+    if is_empty {
+        wkt.push_str(" EMPTY");
+        return Ok(());
+    }
+
+    let has_z = match value {
+        Value::GeometryCollection(_) => false,
+        _ => value.coords_iter().all(|position| position.len() > 2),
+    };
+    let dimensions = if has_z {
+        wkt.push_str(" Z");
+        3
+    } else {
+        2
+    };
+    wkt.push(' ');
+
+    match value {
+        Value::Point(position) => write_line(wkt, std::slice::from_ref(position), dimensions),
+        Value::MultiPoint(positions) => write_list(wkt, positions, |wkt, position| {
+            write_line(wkt, std::slice::from_ref(position), dimensions)
+        }),
+        Value::LineString(line) => write_line(wkt, line, dimensions),
+        Value::MultiLineString(lines) | Value::Polygon(lines) => {
+            write_list(wkt, lines, |wkt, line| write_line(wkt, line, dimensions))
+        }
+        Value::MultiPolygon(polygons) => write_list(wkt, polygons, |wkt, rings| {
+            write_list(wkt, rings, |wkt, ring| write_line(wkt, ring, dimensions))
+        }),
+        Value::GeometryCollection(geometries) => {
+            return write_list_fallible(wkt, geometries, |wkt, geometry| {
+                write_value(wkt, &geometry.value)
+            });
+        }
+        Value::Unknown(_) => unreachable!(),
+    }
+    Ok(())
+}
+
+/// Write `items` as a parenthesised, comma separated list
+fn write_list<T>(wkt: &mut String, items: &[T], mut write_item: impl FnMut(&mut String, &T)) {
+    let _ = write_list_fallible(wkt, items, |wkt, item| {
+        write_item(wkt, item);
+        Ok(())
+    });
+}
+
+fn write_list_fallible<T>(
+    wkt: &mut String,
+    items: &[T],
+    mut write_item: impl FnMut(&mut String, &T) -> Result<(), Error>,
+) -> Result<(), Error> {
+    wkt.push('(');
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            wkt.push_str(", ");
+        }
+        write_item(wkt, item)?;
+    }
+    wkt.push(')');
+    Ok(())
+}
+
+/// Write `line` as a parenthesised list of positions, each with `dimensions` coordinates
+fn write_line(wkt: &mut String, line: &[Position], dimensions: usize) {
+    write_list(wkt, line, |wkt, position| {
+        for (i, coordinate) in position.iter().take(dimensions).enumerate() {
+            if i > 0 {
+                wkt.push(' ');
+            }
+            // Writing to a `String` can't fail
+            let _ = write!(wkt, "{}", coordinate);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Geometry, Value};
+    use serde_json::json;
+
+    #[test]
+    fn every_variant() {
+        let cases = vec![
+            (Value::Point(vec![1., 2.]), "POINT (1 2)"),
+            (Value::Point(vec![1., 2., 3.]), "POINT Z (1 2 3)"),
+            (
+                Value::MultiPoint(vec![vec![1., 2.], vec![-3.25, 4.]]),
+                "MULTIPOINT ((1 2), (-3.25 4))",
+            ),
+            (Value::MultiPoint(vec![]), "MULTIPOINT EMPTY"),
+            (
+                Value::LineString(vec![vec![0., 0., 5.], vec![1., 1.]]),
+                "LINESTRING (0 0, 1 1)",
+            ),
+            (
+                Value::MultiLineString(vec![vec![vec![0., 0.], vec![1., 1.]], vec![]]),
+                "MULTILINESTRING ((0 0, 1 1), ())",
+            ),
+            (
+                Value::MultiPolygon(vec![vec![
+                    vec![vec![0., 0.], vec![4., 0.], vec![0., 4.], vec![0., 0.]],
+                    vec![vec![1., 1.], vec![1., 2.], vec![2., 1.], vec![1., 1.]],
+                ]]),
+                "MULTIPOLYGON (((0 0, 4 0, 0 4, 0 0), (1 1, 1 2, 2 1, 1 1)))",
+            ),
+            (
+                Value::GeometryCollection(vec![
+                    Geometry::new(Value::Point(vec![1., 2., 3.])),
+                    Geometry::new(Value::Polygon(vec![])),
+                ]),
+                "GEOMETRYCOLLECTION (POINT Z (1 2 3), POLYGON EMPTY)",
+            ),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(value.to_wkt().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn unknown_values_are_errors() {
+        let unknown = Value::Unknown(
+            json!({"type": "Circle", "radius": 3})
+                .as_object()
+                .unwrap()
+                .clone(),
+        );
+        let collection = Value::GeometryCollection(vec![Geometry::new(unknown)]);
+        match collection.to_wkt() {
+            Err(Error::GeometryUnknownType(name)) => assert_eq!(name, "Circle"),
+            other => panic!("expected an error, got {:?}", other),
+        }
+    }
+}
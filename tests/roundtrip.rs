@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod roundtrip_tests {
     use geojson::GeoJson;
-    use serde_json;
+
     use std::fs::File;
     use std::io::prelude::*;
 
@@ -27,6 +27,7 @@ mod roundtrip_tests {
     }
 
     roundtrip_tests! {
+        test_good_extended_positions: "good-extended-positions.geojson",
         test_good_feature_with_id: "good-feature-with-id.geojson",
         test_good_feature_with_string_id: "good-feature-with-string-id.geojson",
         test_good_feature: "good-feature.geojson",
@@ -45,10 +46,31 @@ mod roundtrip_tests {
         test_null_geometry: "nullgeometry.geojson",
     }
 
+    /// Positions with more than three coordinates keep all of them through the `serde` and
+    /// `FeatureReader` parsers as well as `FromStr`
+    #[test]
+    fn extended_positions_survive_every_parser() {
+        let file_contents =
+            std::fs::read_to_string("tests/fixtures/canonical/good-extended-positions.geojson")
+                .unwrap();
+        let original_json: serde_json::Value = serde_json::from_str(&file_contents).unwrap();
+
+        let deserialized: GeoJson = serde_json::from_str(&file_contents).unwrap();
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), original_json);
+
+        let features = geojson::FeatureReader::from_reader(file_contents.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&features).unwrap(),
+            original_json["features"]
+        );
+    }
+
     /// Verifies that we can parse and then reencode geojson back to the same representation
     /// without losing any data.
     fn test_round_trip(file_path: &str) {
-        let mut file = File::open(&file_path).unwrap();
+        let mut file = File::open(file_path).unwrap();
         let mut file_contents = String::new();
         let _ = file.read_to_string(&mut file_contents);
 